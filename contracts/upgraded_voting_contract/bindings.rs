@@ -0,0 +1,359 @@
+//! Description of the messages, events and error codes of the contract, used to generate typed
+//! clients (e.g. with polkadot-js).
+//!
+//! The description is derived from the ink! metadata but has a stable, versioned format:
+//! `BINDINGS_VERSION` is incremented whenever a field is renamed or removed. Types are given as
+//! Rust-like names with their generic parameters (e.g. `Result<Vec<u32>, Error>`) and the return
+//! types of messages are unwrapped from the `Result<_, LangError>` added by ink!.
+//!
+//! Events are described with their signature topic (the first topic of every emitted event). The
+//! signature topics are pinned to the ones derived by ink! 4, so that indexers can decode the
+//! events emitted before and after the upgrade to ink! 5 alike.
+//!
+//! Run `cargo run --example bindings --features bindings` to print the description.
+
+use ink::metadata::InkProject;
+use scale_info::{form::PortableForm, PortableRegistry, Type, TypeDef, TypeDefPrimitive};
+use serde_json::{json, Value};
+
+/// Version of the format of the description.
+pub const BINDINGS_VERSION: u32 = 1;
+
+extern "Rust" {
+    /// Generates the ink! metadata of the contract (defined by the `#[ink::contract]` macro).
+    fn __ink_generate_metadata() -> InkProject;
+}
+
+/// Returns the description of the messages, events and error codes of the contract.
+pub fn generate() -> Value {
+    // SAFETY: the function is generated by the contract macro with this exact signature.
+    let project = unsafe { __ink_generate_metadata() };
+    let registry = project.registry();
+    let spec = project.spec();
+
+    let args = |args: &[ink::metadata::MessageParamSpec<PortableForm>]| -> Vec<Value> {
+        args.iter()
+            .map(|arg| {
+                json!({
+                    "label": arg.label(),
+                    "type": type_name(registry, arg.ty().ty().id),
+                })
+            })
+            .collect()
+    };
+
+    let constructors: Vec<Value> = spec
+        .constructors()
+        .iter()
+        .map(|constructor| {
+            json!({
+                "label": constructor.label(),
+                "selector": hex(constructor.selector().to_bytes()),
+                "payable": constructor.payable(),
+                "args": args(constructor.args()),
+                "docs": docs(constructor.docs()),
+            })
+        })
+        .collect();
+
+    let messages: Vec<Value> = spec
+        .messages()
+        .iter()
+        .map(|message| {
+            json!({
+                "label": message.label(),
+                "selector": hex(message.selector().to_bytes()),
+                "mutates": message.mutates(),
+                "payable": message.payable(),
+                "args": args(message.args()),
+                "returnType": return_type_name(registry, message.return_type().ret_type().ty().id),
+                "docs": docs(message.docs()),
+            })
+        })
+        .collect();
+
+    let events: Vec<Value> = spec
+        .events()
+        .iter()
+        .map(|event| {
+            let fields: Vec<Value> = event
+                .args()
+                .iter()
+                .map(|arg| {
+                    json!({
+                        "label": arg.label(),
+                        "type": type_name(registry, arg.ty().ty().id),
+                        "indexed": arg.indexed(),
+                    })
+                })
+                .collect();
+
+            json!({
+                "label": event.label(),
+                "signatureTopic": event.signature_topic().map(|topic| hex(topic.as_bytes())),
+                "fields": fields,
+                "docs": docs(event.docs()),
+            })
+        })
+        .collect();
+
+    json!({
+        "bindingsVersion": BINDINGS_VERSION,
+        "contract": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "constructors": constructors,
+        "messages": messages,
+        "events": events,
+        "errors": errors(registry),
+    })
+}
+
+/// Returns the error codes of the contract (the variants of its `Error` enum), along with the
+/// fields of the structured ones.
+fn errors(registry: &PortableRegistry) -> Vec<Value> {
+    let error = registry
+        .types
+        .iter()
+        .map(|ty| &ty.ty)
+        .find(|ty| {
+            ty.path
+                .segments
+                .ends_with(&["voting_contract".into(), "Error".into()])
+        })
+        .expect("the contract defines an `Error` type");
+
+    let TypeDef::Variant(error) = &error.type_def else {
+        panic!("the `Error` type of the contract is an enum");
+    };
+
+    error
+        .variants
+        .iter()
+        .map(|variant| {
+            let fields: Vec<Value> = variant
+                .fields
+                .iter()
+                .map(|field| {
+                    json!({
+                        "label": field.name,
+                        "type": type_name(registry, field.ty.id),
+                    })
+                })
+                .collect();
+
+            json!({
+                "name": variant.name,
+                "code": variant.index,
+                "fields": fields,
+            })
+        })
+        .collect()
+}
+
+/// Returns the lines of documentation as a single string.
+fn docs(lines: &[String]) -> String {
+    lines
+        .iter()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns the hexadecimal representation of a selector or a topic.
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::from("0x"), |hex, byte| hex + &format!("{byte:02X}"))
+}
+
+/// Returns the type of the registry with the given id.
+fn resolve(registry: &PortableRegistry, id: u32) -> &Type<PortableForm> {
+    registry
+        .resolve(id)
+        .unwrap_or_else(|| panic!("type {id} is in the registry"))
+}
+
+/// Returns the name of the return type of a message, without the `Result<_, LangError>` added by
+/// ink!.
+fn return_type_name(registry: &PortableRegistry, id: u32) -> String {
+    let ty = resolve(registry, id);
+
+    match &ty.type_params[..] {
+        [ok, err]
+            if ty.path.segments.last().map(String::as_str) == Some("Result")
+                && err
+                    .ty
+                    .is_some_and(|err| type_name(registry, err.id) == "LangError") =>
+        {
+            ok.ty
+                .map_or_else(|| String::from("()"), |ok| type_name(registry, ok.id))
+        }
+        _ => type_name(registry, id),
+    }
+}
+
+/// Returns the name of a type of the registry with its generic parameters.
+fn type_name(registry: &PortableRegistry, id: u32) -> String {
+    let ty = resolve(registry, id);
+    let names = |ids: &mut dyn Iterator<Item = u32>| -> String {
+        ids.map(|id| type_name(registry, id))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match &ty.type_def {
+        TypeDef::Composite(_) | TypeDef::Variant(_) => {
+            let name = ty.path.segments.last().cloned().unwrap_or_default();
+            let params = names(
+                &mut ty
+                    .type_params
+                    .iter()
+                    .filter_map(|param| param.ty)
+                    .map(|ty| ty.id),
+            );
+
+            if params.is_empty() {
+                name
+            } else {
+                format!("{name}<{params}>")
+            }
+        }
+        TypeDef::Sequence(sequence) => {
+            format!("Vec<{}>", type_name(registry, sequence.type_param.id))
+        }
+        TypeDef::Array(array) => format!(
+            "[{}; {}]",
+            type_name(registry, array.type_param.id),
+            array.len
+        ),
+        TypeDef::Tuple(tuple) => {
+            format!(
+                "({})",
+                names(&mut tuple.fields.iter().map(|field| field.id))
+            )
+        }
+        TypeDef::Primitive(TypeDefPrimitive::Str) => String::from("String"),
+        TypeDef::Primitive(primitive) => format!("{primitive:?}").to_lowercase(),
+        TypeDef::Compact(compact) => {
+            format!("Compact<{}>", type_name(registry, compact.type_param.id))
+        }
+        TypeDef::BitSequence(_) => String::from("BitVec"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the entry of a list of the description with the given value for the given key.
+    fn find<'a>(bindings: &'a Value, list: &str, key: &str, value: &str) -> &'a Value {
+        bindings[list]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|entry| entry[key] == value)
+            .unwrap()
+    }
+
+    /// Tests that messages are described with their selectors and typed arguments.
+    #[test]
+    fn test_messages() {
+        let bindings = generate();
+
+        assert_eq!(bindings["bindingsVersion"], BINDINGS_VERSION);
+
+        let vote = find(&bindings, "messages", "label", "vote");
+        assert_eq!(vote["selector"], "0x083BE260");
+        assert_eq!(vote["mutates"], true);
+        assert_eq!(vote["payable"], true);
+        assert_eq!(
+            vote["args"],
+            json!([
+                { "label": "poll_id", "type": "u64" },
+                { "label": "choice_id", "type": "u32" },
+            ])
+        );
+        assert_eq!(vote["returnType"], "Result<u64, Error>");
+
+        let get_choices = find(&bindings, "messages", "label", "get_choices");
+        assert_eq!(get_choices["mutates"], false);
+        assert_eq!(get_choices["returnType"], "Vec<(u32, Choice)>");
+    }
+
+    /// Tests that events are described with their typed and indexed fields.
+    #[test]
+    fn test_events() {
+        let bindings = generate();
+
+        let poll_created = find(&bindings, "events", "label", "PollCreated");
+        assert_eq!(
+            poll_created["fields"][1],
+            json!({ "label": "poll_id", "type": "u64", "indexed": true })
+        );
+    }
+
+    /// Tests that the signature topics of the events are the first topics of the emitted events,
+    /// and are the ones derived by ink! 4.
+    #[ink::test]
+    fn test_signature_topics() {
+        use crate::voting_contract::VotingContract;
+
+        let bindings = generate();
+
+        let mut voting_contract = VotingContract::default();
+        assert_eq!(
+            voting_contract.create_poll(1, "test".to_string(), 0, 24 * 60 * 60 * 1000),
+            Ok(())
+        );
+        assert_eq!(voting_contract.set_finalizer(1, None), Ok(()));
+
+        // The signature of `PollCreated` fits in a topic, while the one of `FinalizerChanged` is
+        // hashed.
+        let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+
+        for (event, label, signature_topic) in [
+            (
+                &emitted_events[0],
+                "PollCreated",
+                "0x00566F74696E67436F6E74726163743A3A506F6C6C4372656174656400000000",
+            ),
+            (
+                &emitted_events[2],
+                "FinalizerChanged",
+                "0x5ECAB2402A14AE2AE510EB88147B5B0D6D9B2D1FB46D0EBB1641D4ECBDBA0C48",
+            ),
+        ] {
+            let event_bindings = find(&bindings, "events", "label", label);
+            assert_eq!(event_bindings["signatureTopic"], signature_topic);
+            assert_eq!(hex(&event.topics[0]), signature_topic);
+        }
+
+        // None of the events is anonymous.
+        assert!(bindings["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|event| event["signatureTopic"].is_string()));
+    }
+
+    /// Tests that error codes are the indices of the variants of the `Error` enum.
+    #[test]
+    fn test_errors() {
+        let bindings = generate();
+
+        let error = find(&bindings, "errors", "name", "PollWithIdDoesNotExist");
+        assert_eq!(error["code"], 1);
+        assert_eq!(error["fields"], json!([]));
+
+        let error = find(&bindings, "errors", "name", "InvalidStatusTransition");
+        assert_eq!(
+            error["fields"],
+            json!([
+                { "label": "from", "type": "PollStatus" },
+                { "label": "to", "type": "PollStatus" },
+            ])
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Strings with a maximum length, independent from the storage of the contract.
+//!
+//! A `BoundedString<N>` holds at most `N` bytes. Its length is checked when it is created from a
+//! `String` and when it is decoded, so that values read from storage or passed to messages can
+//! never exceed the bound. It is encoded (and described in the metadata) exactly like a `String`,
+//! so replacing a `String` by a `BoundedString` changes neither the storage layout nor the ABI.
+
+use core::ops::Deref;
+
+use ink::prelude::string::String;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// String of at most `N` bytes.
+pub struct BoundedString<const N: usize>(String);
+
+impl<const N: usize> BoundedString<N> {
+    /// Returns the string, or `None` if it is longer than `N` bytes.
+    pub fn new(value: String) -> Option<Self> {
+        (value.len() <= N).then_some(Self(value))
+    }
+}
+
+impl<const N: usize> TryFrom<String> for BoundedString<N> {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.len() <= N {
+            Ok(Self(value))
+        } else {
+            Err(value)
+        }
+    }
+}
+
+impl<const N: usize> From<BoundedString<N>> for String {
+    fn from(value: BoundedString<N>) -> Self {
+        value.0
+    }
+}
+
+impl<const N: usize> Deref for BoundedString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const N: usize> PartialEq<str> for BoundedString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for BoundedString<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl<const N: usize> PartialEq<String> for BoundedString<N> {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+impl<const N: usize> scale::Encode for BoundedString<N> {
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl<const N: usize> scale::EncodeLike for BoundedString<N> {}
+
+impl<const N: usize> scale::EncodeLike<String> for BoundedString<N> {}
+
+impl<const N: usize> scale::Decode for BoundedString<N> {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        // Check the length prefix before reading (and allocating) the bytes of the string.
+        let len = scale::Compact::<u32>::decode(input)?.0 as usize;
+
+        if len > N {
+            return Err("string exceeds its maximum length".into());
+        }
+
+        let mut bytes = ink::prelude::vec![0; len];
+        input.read(&mut bytes)?;
+
+        String::from_utf8(bytes)
+            .map(Self)
+            .map_err(|_| "invalid utf8 string".into())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> scale_info::TypeInfo for BoundedString<N> {
+    type Identity = str;
+
+    fn type_info() -> scale_info::Type {
+        str::type_info()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> ink::storage::traits::StorageLayout for BoundedString<N> {
+    fn layout(key: &ink::primitives::Key) -> ink::metadata::layout::Layout {
+        ink::metadata::layout::Layout::Leaf(ink::metadata::layout::LeafLayout::from_key::<Self>(
+            ink::metadata::layout::LayoutKey::from(key),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use scale::{Decode, Encode};
+
+    /// Tests that the length of a bounded string is checked on creation and on decoding.
+    #[test]
+    fn test_bound() {
+        assert_eq!(
+            BoundedString::<4>::new("test".into()).as_deref(),
+            Some("test")
+        );
+        assert_eq!(BoundedString::<3>::new("test".into()), None);
+
+        let encoded = String::from("test").encode();
+
+        assert_eq!(
+            BoundedString::<4>::new("test".into()).unwrap().encode(),
+            encoded
+        );
+        assert_eq!(
+            BoundedString::<4>::decode(&mut &encoded[..]).unwrap(),
+            "test"
+        );
+        assert!(BoundedString::<3>::decode(&mut &encoded[..]).is_err());
+    }
+}
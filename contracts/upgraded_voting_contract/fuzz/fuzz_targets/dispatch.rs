@@ -0,0 +1,80 @@
+//! Feeds arbitrary call data (a message selector followed by its SCALE-encoded arguments) into
+//! the generated message dispatch of the contract, in the off-chain environment.
+//!
+//! Run with `cargo fuzz run dispatch` from the directory of the contract.
+
+#![no_main]
+
+use std::{panic, process, sync::Once};
+
+use ink::{
+    env::DefaultEnvironment,
+    reflect::{ContractMessageDecoder, DecodeDispatch, ExecuteDispatchable},
+    storage::traits::StorageKey,
+};
+use libfuzzer_sys::fuzz_target;
+use upgraded_voting_contract::VotingContract;
+
+/// Part of the messages of the panics raised by the off-chain environment for the functions it
+/// does not implement. Such panics end the dispatch of a message without revealing a bug: the
+/// off-chain environment panics when a dispatched message returns its value, as well as for
+/// cross-contract calls and code hash changes.
+const OFF_CHAIN_ENV_PANIC: &str = "off-chain env";
+
+/// Installs a panic hook which aborts (and thereby reports a crash) on every panic except the
+/// ones raised by the off-chain environment.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let default_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if !is_off_chain_env_panic(info.payload()) {
+                default_hook(info);
+                process::abort();
+            }
+        }));
+    });
+}
+
+/// Returns true if the panic payload is the one of a panic raised by the off-chain environment.
+fn is_off_chain_env_panic(payload: &(dyn std::any::Any + Send)) -> bool {
+    payload
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&str>().copied())
+        .is_some_and(|message| message.contains(OFF_CHAIN_ENV_PANIC))
+}
+
+fuzz_target!(|data: &[u8]| {
+    install_panic_hook();
+
+    // Malformed call data must be rejected by the decoder without panicking.
+    let Ok(message) =
+        <<VotingContract as ContractMessageDecoder>::Type as DecodeDispatch>::decode_dispatch(
+            &mut &data[..],
+        )
+    else {
+        return;
+    };
+
+    ink::env::test::run_test::<DefaultEnvironment, _>(|_| {
+        // Messages load the contract from its root storage key.
+        ink::env::set_contract_storage(
+            &<VotingContract as StorageKey>::KEY,
+            &VotingContract::default(),
+        );
+
+        if let Err(payload) = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let _ = message.execute_dispatchable();
+        })) {
+            if !is_off_chain_env_panic(payload.as_ref()) {
+                panic::resume_unwind(payload);
+            }
+        }
+
+        Ok(())
+    })
+    .unwrap();
+});
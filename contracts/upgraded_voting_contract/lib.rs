@@ -1,206 +1,915 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
-#[ink::contract]
+/// Chain extension exposing the staking information of the parachain.
+#[cfg(feature = "staking-weight")]
+#[ink::chain_extension(extension = 0)]
+pub trait StakingExtension {
+    type ErrorCode = StakingErrorCode;
+
+    /// Returns the balance staked (bonded or nominated) by the given account.
+    #[ink(function = 0x5701, handle_status = false)]
+    fn staked_balance(account: ink::primitives::AccountId) -> u128;
+}
+
+/// Error codes returned by the staking chain extension.
+#[cfg(feature = "staking-weight")]
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum StakingErrorCode {
+    /// Returned if the chain extension call failed.
+    Failed,
+}
+
+#[cfg(feature = "staking-weight")]
+impl ink::env::chain_extension::FromStatusCode for StakingErrorCode {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match status_code {
+            0 => Ok(()),
+            _ => Err(Self::Failed),
+        }
+    }
+}
+
+/// Environment of a contracts node which exposes the staking chain extension.
+#[cfg(feature = "staking-weight")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum StakingEnvironment {}
+
+#[cfg(feature = "staking-weight")]
+impl ink::env::Environment for StakingEnvironment {
+    const MAX_EVENT_TOPICS: usize =
+        <ink::env::DefaultEnvironment as ink::env::Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <ink::env::DefaultEnvironment as ink::env::Environment>::AccountId;
+    type Balance = <ink::env::DefaultEnvironment as ink::env::Environment>::Balance;
+    type Hash = <ink::env::DefaultEnvironment as ink::env::Environment>::Hash;
+    type BlockNumber = <ink::env::DefaultEnvironment as ink::env::Environment>::BlockNumber;
+    type Timestamp = <ink::env::DefaultEnvironment as ink::env::Environment>::Timestamp;
+
+    type ChainExtension = StakingExtension;
+}
+
+/// Interface of contracts that are notified when a poll they observe ends.
+#[ink::trait_definition]
+pub trait PollObserver {
+    /// Called by the voting contract when the observed poll ends.
+    #[ink(message)]
+    fn on_poll_ended(&mut self, poll_id: u64, winner: Option<u32>);
+}
+
+/// Interface of contracts that relay poll results (e.g. to another chain over XCM).
+#[ink::trait_definition]
+pub trait ResultsPublisher {
+    /// Called by the voting contract with the result of every poll that ends.
+    #[ink(message)]
+    fn publish_result(&mut self, poll_id: u64, winner: Option<u32>, total_votes: u128);
+}
+
+/// Interface of contracts that resolve accounts to human-readable identities (e.g. a registry
+/// mirroring the display names of the identity pallet).
+#[ink::trait_definition]
+pub trait IdentityRegistry {
+    /// Returns the display name registered for an account (if any).
+    #[ink(message)]
+    fn display_name(
+        &self,
+        account: ink::primitives::AccountId,
+    ) -> Option<ink::prelude::string::String>;
+}
+
+/// Interface of contracts that verify zero-knowledge proofs of membership in the set of eligible
+/// voters of a poll (e.g. a Semaphore-style group).
+#[ink::trait_definition]
+pub trait MembershipVerifier {
+    /// Returns true if the proof is valid for the verifier key, the nullifier and the signal.
+    #[ink(message)]
+    fn verify_membership(
+        &self,
+        verifier_key: ink::prelude::vec::Vec<u8>,
+        proof: ink::prelude::vec::Vec<u8>,
+        nullifier: [u8; 32],
+        signal: [u8; 32],
+    ) -> bool;
+}
+
+/// Interface of read-model contracts fed with incremental updates of the polls, so that heavy
+/// aggregate queries are served without the voting contract.
+#[ink::trait_definition]
+pub trait PollStats {
+    /// Called by the voting contract when a poll is created.
+    #[ink(message)]
+    fn on_poll_created(&mut self, poll_id: u64, owner: ink::primitives::AccountId);
+
+    /// Called by the voting contract when a ballot is cast on a poll.
+    #[ink(message)]
+    fn on_vote_cast(&mut self, poll_id: u64, ballot_number: u64);
+}
+
+/// Interface of contracts that assign numbered indexes to the members of a known set of accounts
+/// (e.g. the members of a DAO), used by polls storing the eligibility of members as a bitmap.
+#[ink::trait_definition]
+pub trait MemberRegistry {
+    /// Returns the index of an account in the registry (if it is a member).
+    #[ink(message)]
+    fn member_index(&self, account: ink::primitives::AccountId) -> Option<u32>;
+}
+
+/// Interface of contracts that record when accounts joined a membership (e.g. the members of a
+/// DAO), used to boost the votes of long-term members.
+#[ink::trait_definition]
+pub trait TenureRegistry {
+    /// Returns the date (timestamp in milliseconds) at which an account joined (if it is a
+    /// member).
+    #[ink(message)]
+    fn member_since(&self, account: ink::primitives::AccountId) -> Option<u64>;
+}
+
+mod bounded;
+mod merkle;
+mod tally;
+
+#[cfg(feature = "bindings")]
+pub mod bindings;
+
+pub use self::voting_contract::VotingContract;
+
+/// Types, errors, events and internal helpers of the voting contract. The `ink::contract` macro
+/// does not accept file modules, so they are declared next to the contract module rather than in it.
+#[path = "voting_contract"]
+mod contract {
+    use ink::{
+        codegen::Env as _,
+        env::call::{build_call, ExecutionInput, Selector},
+        prelude::{string::String, vec, vec::Vec},
+        primitives::KeyComposer,
+        storage::{traits::ManualKey, Mapping},
+    };
+
+    use crate::voting_contract::*;
+
+    use self::{errors::*, events::*, types::*, voting::bitmap_position};
+
+    type Environment = <VotingContract as ink::env::ContractEnv>::Env;
+    type AccountId = <Environment as ink::env::Environment>::AccountId;
+    type Balance = <Environment as ink::env::Environment>::Balance;
+    type Timestamp = <Environment as ink::env::Environment>::Timestamp;
+    type BlockNumber = <Environment as ink::env::Environment>::BlockNumber;
+
+    pub(crate) mod admin;
+    pub(crate) mod errors;
+    pub(crate) mod events;
+    pub(crate) mod external;
+    pub(crate) mod guard;
+    pub(crate) mod migration;
+    pub(crate) mod payments;
+    pub(crate) mod polls;
+    pub(crate) mod queries;
+    pub(crate) mod tally;
+    pub(crate) mod types;
+    pub(crate) mod voting;
+}
+
+#[cfg_attr(
+    feature = "staking-weight",
+    ink::contract(env = crate::StakingEnvironment)
+)]
+#[cfg_attr(not(feature = "staking-weight"), ink::contract)]
 mod voting_contract {
     use ink::{
-        prelude::{format, string::String, vec::Vec},
-        storage::Mapping,
+        env::call::{build_call, ExecutionInput, Selector},
+        prelude::{format, string::String, vec, vec::Vec},
+        primitives::KeyComposer,
+        storage::{traits::ManualKey, Lazy, Mapping},
+    };
+
+    use crate::{
+        bounded::BoundedString,
+        contract::{
+            errors::*, events::*, guard::ReentrancyGuard, types::*, voting::bitmap_position,
+        },
+        merkle::MerkleTree,
+        tally::CountRound,
     };
 
     /// Defines the type of poll identifiers.
-    type PollId = u64;
+    pub(crate) type PollId = u64;
 
     /// Defines the type of choice identifiers.
-    type ChoiceId = u8;
-
-    #[ink(event)]
-    /// Event emitted when a poll is created.
-    pub struct PollCreated {
-        #[ink(topic)]
-        /// Id of the poll.
-        poll_id: PollId,
-        /// Description of the poll.
-        description: String,
-        #[ink(topic)]
-        /// Account that created the poll.
-        owner: AccountId,
-    }
+    pub(crate) type ChoiceId = u32;
+
+    /// Defines the type of question identifiers (of polls with multiple questions).
+    pub(crate) type QuestionId = u32;
+
+    /// Defines the type of poll template identifiers.
+    pub(crate) type TemplateId = u32;
+
+    /// Defines the type of choice identifiers used by polls with storage version 0.
+    pub(crate) type ChoiceIdV0 = u8;
+
+    /// Defines the type of rankings of choices (most preferred first).
+    pub(crate) type Ranking = Vec<ChoiceId>;
+
+    /// Defines the type of keys of pairwise preferences: the poll id and a pair of choices.
+    pub(crate) type ChoicePair = (PollId, ChoiceId, ChoiceId);
+
+    /// Defines the type of pages of paged bitmaps: the bits of 256 consecutive indexes (of
+    /// members or choices).
+    pub(crate) type BitmapPage = [u8; 32];
+
+    /// Defines the type of the descriptions of polls and choices.
+    pub(crate) type Description = BoundedString<{ MAX_DESCRIPTION_LENGTH as usize }>;
+
+    /// Defines the type of cursors of `export_state`: the index of the next record to export.
+    pub(crate) type ExportCursor = u32;
+
+    /// Defines the type of ranked ballots counted by STV polls: a ranking and its weight.
+    pub(crate) type WeightedRanking = (Ranking, u128);
+
+    /// Defines the type of the decryption shares of encrypted polls.
+    pub(crate) type DecryptionShare = Vec<u8>;
+
+    /// Defines the type of the waitlists of full choices: the waiting voters, in order.
+    pub(crate) type Waitlist = Vec<AccountId>;
+
+    /// Storage key of the flag of the reentrancy guard (see `ReentrancyGuard`).
+    pub(crate) const ENTERED_KEY: u32 = KeyComposer::from_str("VotingContract::entered");
+
+    /// Storage key of the admin of the proxy (see `enable_proxy`), shared with the proxy.
+    pub(crate) const PROXY_ADMIN_KEY: u32 = KeyComposer::from_str("VotingProxy::admin");
+
+    /// Storage key of the code hash of the implementation of the proxy (see `enable_proxy`),
+    /// shared with the proxy.
+    pub(crate) const PROXY_IMPLEMENTATION_KEY: u32 =
+        KeyComposer::from_str("VotingProxy::implementation");
+
+    /// Storage key of the metadata of the instance (see `set_instance_metadata`).
+    pub(crate) const INSTANCE_METADATA_KEY: u32 =
+        KeyComposer::from_str("VotingContract::instance_metadata");
+
+    /// Storage key of the discovery feed of the running polls (see `get_active_feed`).
+    pub(crate) const ACTIVE_FEED_KEY: u32 = KeyComposer::from_str("VotingContract::active_feed");
+
+    /// Storage version of the polls written by this version of the contract.
+    ///
+    /// - Version 0: choice ids are stored as `u8` and vote counts as `u64`.
+    /// - Version 1: choice ids are stored as `u32` and vote counts as `u64`.
+    /// - Version 2: choice ids are stored as `u32` and vote counts as `u128`.
+    /// - Version 3: the existence of choices is also stored as a bitmap (see `choice_bitmaps`).
+    /// - Version 4: descriptions of polls are stored apart from the polls (see
+    ///   `poll_descriptions`).
+    /// - Version 5: lists of choices are stored one id at a time (see `choice_slots`).
+    pub(crate) const POLL_STORAGE_VERSION: u8 = 5;
+
+    /// Version of this code of the contract. It is announced to `set_code` when upgrading to this
+    /// code and recorded by `migrate`, which refuses to run if an older code is running than the
+    /// one which last migrated the storage (see `get_code_version`).
+    pub(crate) const CODE_VERSION: u32 = 2;
+
+    /// Version of the schema of the events emitted by the contract. It is the first field of every
+    /// event and is bumped whenever the fields of an event change, so that indexers can decode
+    /// historical events correctly.
+    ///
+    /// - Version 1: initial schema.
+    /// - Version 2: the events of a poll (except the events of its creation) carry the namespace
+    ///   of the poll (see `set_topic_seed`).
+    /// - Version 3: the data of the events is no longer prefixed by the index of the event in the
+    ///   `Event` enum generated by ink! 4.
+    /// - Version 4: `PollEnded` carries the winner as a topic (see `NO_WINNER_TOPIC`).
+    /// - Version 5: `PollEnded` carries the number of votes and voters of the winner.
+    pub(crate) const EVENT_SCHEMA_VERSION: u8 = 5;
+
+    /// Winner topic of the `PollEnded` events of polls ended without a winner (in case of a tie).
+    /// It is out of the range of choice ids, unlike the topic of `None` which is the topic of the
+    /// choice `0`.
+    pub(crate) const NO_WINNER_TOPIC: u64 = u64::MAX;
+
+    /// Version of the scheme of the poll topics (see `get_topic_scheme`).
+    pub(crate) const TOPIC_SCHEME_VERSION: u8 = 3;
+
+    /// Domain hashed with the id of a poll to derive the value of its topic.
+    pub(crate) const POLL_TOPIC_DOMAIN: &str = "poll";
+
+    /// Domain hashed with the owner and the topic seed of a poll to derive its namespace.
+    pub(crate) const NAMESPACE_TOPIC_DOMAIN: &str = "namespace";
+
+    /// Bitset of the tally strategies supported by the contract (see `TallyStrategy::flag`).
+    pub(crate) const SUPPORTED_TALLY_STRATEGIES: u32 = TallyStrategy::Plurality.flag()
+        | TallyStrategy::Condorcet.flag()
+        | TallyStrategy::Borda.flag()
+        | TallyStrategy::Stv { seats: 0 }.flag()
+        | TallyStrategy::WeightedMedian.flag();
+
+    /// Maximum number of characters of the poll description included in a `PollSummary`.
+    pub(crate) const SUMMARY_DESCRIPTION_LENGTH: usize = 64;
+
+    /// Maximum number of polls that can be summarized in a single call to `get_summaries`.
+    pub(crate) const MAX_SUMMARY_BATCH_SIZE: u32 = 50;
+
+    /// Maximum number of voters resolved in a single call to `get_report_with_identities`.
+    pub(crate) const MAX_IDENTITY_PAGE_SIZE: u32 = 20;
+
+    /// Maximum number of entries retained in the admin log (older entries are overwritten).
+    pub(crate) const ADMIN_LOG_CAPACITY: u64 = 256;
+
+    /// Maximum number of admin log entries returned by a single call to `get_admin_log`.
+    pub(crate) const MAX_ADMIN_LOG_PAGE_SIZE: u32 = 50;
 
-    #[ink(event)]
-    /// Event emitted when a choice is added to a poll.
-    pub struct ChoiceAdded {
-        #[ink(topic)]
-        /// Id of the poll.
-        poll_id: PollId,
-        #[ink(topic)]
-        /// Id of the choice.
-        choice_id: ChoiceId,
-        /// Description of the choice.
-        description: String,
+    /// Maximum number of choices added in a single call to `add_choices`.
+    pub(crate) const MAX_CHOICE_BATCH_SIZE: u32 = 50;
+
+    /// Maximum length (in bytes) of the description of a poll or a choice.
+    pub(crate) const MAX_DESCRIPTION_LENGTH: u32 = 1_024;
+
+    /// Maximum number of voter weights uploaded in a single call to `set_voter_weights`.
+    pub(crate) const MAX_VOTER_WEIGHT_BATCH_SIZE: u32 = 100;
+
+    /// Maximum number of accounts added in a single call to `add_eligible_voters_chunk`.
+    pub(crate) const MAX_ELIGIBLE_VOTER_CHUNK_SIZE: u32 = 500;
+
+    /// Maximum number of records exported in a single call to `export_state`.
+    pub(crate) const MAX_EXPORT_CHUNK_SIZE: u32 = 50;
+
+    /// Maximum number of voters waiting for a seat on a choice (see `vote_or_wait`).
+    pub(crate) const MAX_WAITLIST_LENGTH: u32 = 100;
+
+    /// Maximum length (in bytes) of the name and of the URI of the instance.
+    pub(crate) const MAX_INSTANCE_METADATA_LENGTH: u32 = 256;
+
+    /// Maximum number of polls in each list of the discovery feed (see `get_active_feed`).
+    pub(crate) const MAX_FEED_LENGTH: u32 = 10;
+
+    /// Maximum number of buckets of the histograms of the answers to numeric questions.
+    pub(crate) const MAX_NUMERIC_BUCKETS: u32 = 128;
+
+    /// Maximum gas (reference time) forwarded to a poll observer when notifying it.
+    pub(crate) const OBSERVER_GAS_LIMIT: u64 = 5_000_000_000;
+
+    /// Maximum gas (reference time) forwarded to the results publisher when publishing a result.
+    pub(crate) const PUBLISHER_GAS_LIMIT: u64 = 5_000_000_000;
+
+    /// Maximum gas (reference time) forwarded to the membership verifier when verifying a proof.
+    pub(crate) const VERIFIER_GAS_LIMIT: u64 = 20_000_000_000;
+
+    /// Maximum gas (reference time) forwarded to the identity registry when resolving an account.
+    pub(crate) const IDENTITY_GAS_LIMIT: u64 = 1_000_000_000;
+
+    /// Maximum gas (reference time) forwarded to the member registry when resolving an account.
+    pub(crate) const MEMBER_REGISTRY_GAS_LIMIT: u64 = 1_000_000_000;
+
+    /// Maximum gas (reference time) forwarded to the tenure registry when resolving an account.
+    pub(crate) const TENURE_REGISTRY_GAS_LIMIT: u64 = 1_000_000_000;
+
+    /// Maximum gas (reference time) forwarded to the stats contract when pushing an update.
+    pub(crate) const STATS_GAS_LIMIT: u64 = 1_000_000_000;
+
+    /// Maximum protocol fee in basis points (10%).
+    pub(crate) const MAX_FEE_BPS: u16 = 1_000;
+
+    /// Default minimum duration of a poll (1 hour in milliseconds).
+    pub(crate) const DEFAULT_MIN_DURATION: Timestamp = 60 * 60 * 1000;
+
+    /// Default maximum duration of a poll (30 days in milliseconds).
+    pub(crate) const DEFAULT_MAX_DURATION: Timestamp = 30 * 24 * 60 * 60 * 1000;
+
+    /// Duration of the buckets of the vote timelines (1 hour in milliseconds).
+    pub(crate) const TIMELINE_BUCKET_DURATION: Timestamp = 60 * 60 * 1000;
+
+    /// Duration of a year of membership in tenure schedules (365 days in milliseconds).
+    pub(crate) const TENURE_YEAR: Timestamp = 365 * 24 * 60 * 60 * 1000;
+
+    /// Source of the weight of a vote cast by an account.
+    pub trait WeightSource {
+        /// Returns the weight of a vote cast by the given account.
+        fn weight_of(&self, account: AccountId) -> u128;
     }
 
-    #[ink(event)]
-    /// Event emitted when a poll is started.
-    pub struct PollStarted {
-        #[ink(topic)]
-        /// Id of the poll.
-        poll_id: PollId,
+    /// Weight source which gives every account exactly one vote.
+    #[cfg_attr(feature = "staking-weight", allow(dead_code))]
+    pub struct UnitWeight;
+
+    impl WeightSource for UnitWeight {
+        fn weight_of(&self, _account: AccountId) -> u128 {
+            1
+        }
     }
 
-    #[ink(event)]
-    /// Event emitted when a poll is ended.
-    pub struct PollEnded {
-        #[ink(topic)]
-        /// Id of the poll.
-        poll_id: PollId,
-        /// Id of the winning choice (not present in case of a tie).
-        winner: Option<ChoiceId>,
+    /// Weight source which weights every vote by the balance staked by the account (queried
+    /// using the staking chain extension).
+    #[cfg(feature = "staking-weight")]
+    pub struct StakedBalanceWeight;
+
+    #[cfg(feature = "staking-weight")]
+    impl WeightSource for StakedBalanceWeight {
+        fn weight_of(&self, account: AccountId) -> u128 {
+            use ink::ChainExtensionInstance;
+
+            <crate::StakingExtension as ChainExtensionInstance>::instantiate()
+                .staked_balance(account)
+        }
     }
 
+    /// Defines the weight source used for votes.
+    #[cfg(not(feature = "staking-weight"))]
+    pub(crate) const WEIGHT_SOURCE: UnitWeight = UnitWeight;
+
+    /// Defines the weight source used for votes.
+    #[cfg(feature = "staking-weight")]
+    pub(crate) const WEIGHT_SOURCE: StakedBalanceWeight = StakedBalanceWeight;
+
     /// Defines the storage of the contract.
     #[ink(storage)]
     pub struct VotingContract {
         /// Stores all the polls. Maps the poll id to the poll.
-        polls: Mapping<PollId, Poll>,
+        pub(crate) polls: Mapping<PollId, Poll>,
         /// Stores all the choices. Maps the poll id and the choice id to the choice.
-        choices: Mapping<(PollId, ChoiceId), Choice>,
-        /// Stores all the choice ids for a poll. Maps the poll id to a vector of choice ids.
-        choice_ids: Mapping<PollId, Vec<ChoiceId>>,
+        pub(crate) choices: Mapping<(PollId, ChoiceId), Choice>,
+        /// Stores the length of the list of choices of each poll. Maps the poll id to the number
+        /// of choice ids in the list.
+        pub(crate) choice_count: Mapping<PollId, u32>,
+        /// Stores the lists of choices of the polls one id at a time, so that adding a choice does
+        /// not rewrite the whole list. Maps the poll id and a position in the list (below the
+        /// `choice_count` of the poll) to the choice id.
+        pub(crate) choice_slots: Mapping<(PollId, u32), ChoiceId>,
+        /// Stores the next choice id to be assigned by `auto_add_choice`. Maps the poll id to the choice id.
+        pub(crate) next_choice_ids: Mapping<PollId, ChoiceId>,
         /// Stores all the votes. Maps the poll id and the choice id to the number of votes.
-        vote_counts: Mapping<(PollId, ChoiceId), u64>,
+        pub(crate) vote_counts: Mapping<(PollId, ChoiceId), u128>,
         /// Used to keep track of which account has voted on a poll so that they can't vote a second time.
-        voted_by: Mapping<(PollId, AccountId), bool>,
+        pub(crate) voted_by: Mapping<(PollId, AccountId), bool>,
+        /// Stores the choice each account voted for. Maps the poll id and the account to the choice id.
+        pub(crate) ballots: Mapping<(PollId, AccountId), ChoiceId>,
+        /// Stores the per-poll delegations. Maps the poll id and the delegator to the delegate.
+        pub(crate) poll_delegations: Mapping<(PollId, AccountId), Delegation>,
+        /// Stores the global delegations. Maps the delegator to the delegate.
+        pub(crate) global_delegations: Mapping<AccountId, Delegation>,
+        /// Stores the number of accounts that voted on a poll. Maps the poll id to the number of ballots.
+        pub(crate) total_ballots: Mapping<PollId, u64>,
         /// Admin of the contract.
-        admin: AccountId,
+        pub(crate) admin: AccountId,
         /// Stores whether the contract is paused or not.
-        paused: bool,
-    }
-
-    #[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
-    #[cfg_attr(
-        feature = "std",
-        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
-    )]
-    /// A poll that can be voted on.
-    pub struct Poll {
-        /// Description of the poll
-        description: String,
-        /// Status of the poll.
-        status: PollStatus,
-        /// Account that created the poll.
-        owner: AccountId,
-        /// Winner of the poll (present if the poll has ended).
-        winner: Option<ChoiceId>,
-    }
-
-    #[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
-    #[cfg_attr(
-        feature = "std",
-        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
-    )]
-    /// A choice that can be voted on.
-    pub struct Choice {
-        /// Description of the choice
-        description: String,
-    }
-
-    #[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
-    #[cfg_attr(
-        feature = "std",
-        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
-    )]
-    /// Status of a poll.
-    pub enum PollStatus {
-        /// Poll has not started yet.
-        NotStarted,
-        /// Poll has started.
-        Started,
-        /// Poll has ended.
-        Ended,
-    }
-
-    /// Report generated for a poll.
-    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub struct PollReport {
-        /// Id of the poll.
-        id: PollId,
-        /// Description of the poll
-        description: String,
-        /// Status of the poll.
-        status: PollStatus,
-        /// Account that created the poll.
-        owner: AccountId,
-        /// List of choices for the poll.
-        choices: Vec<ChoiceReport>,
-        /// Id of the winning choice (present if the poll has ended).
-        winner: Option<ChoiceId>,
-    }
-
-    /// Report generated for a choice.
-    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub struct ChoiceReport {
-        /// Id of the choice.
-        id: ChoiceId,
-        /// Description of the choice.
-        description: String,
-        /// Number of votes for the choice.
-        vote_count: u64,
-    }
-
-    /// Errors that can occur in the voting contract.
-    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub enum Error {
-        /// Returned if the poll with the given id already exist.
-        PollWithIdAlreadyExists,
-        /// Returned if the poll with the given id does not exist.
-        PollWithIdDoesNotExist,
-        /// Returned if the poll has not started yet.
-        PollHasNotStarted,
-        /// Returned if the poll has ended.
-        PollHasEnded,
-        /// Returned if the poll has already started.
-        PollHasStarted,
-        /// Returned if the caller is not the owner of the poll.
-        OnlyOwnerCanAddChoice,
-        /// Returned if the caller is not the owner of the poll.
-        OnlyOwnerCanStartPoll,
-        /// Returned if the owner tries to start a poll with no choices.
-        CannotStartPollWithNoChoices,
-        /// Returned if the caller is not the owner of the poll.
-        OnlyOwnerCanEndPoll,
-        /// Returned if the choice with the given id already exist.
-        ChoiceWithIdAlreadyExists,
-        /// Returned if the choice with the given id does not exist.
-        ChoiceWithIdDoesNotExist,
-        /// Returned if the caller has already voted on the poll.
-        CallerAlreadyVotedOnPoll,
-        /// Returned when the contract is paused by the admin.
-        ContractIsPaused,
-        /// Returned when the caller is not the admin.
-        CallerIsNotAdmin,
-        /// Returned when the contract fails to set the code hash.
-        FailedToSetCodeHash(String),
+        pub(crate) paused: bool,
+        /// Stores which individual operations of the contract are paused.
+        pub(crate) circuit_breakers: CircuitBreakers,
+        /// Ring buffer of administrative actions. Maps the slot (entry index modulo
+        /// `ADMIN_LOG_CAPACITY`) to the entry.
+        pub(crate) admin_log: Mapping<u64, AdminLogEntry>,
+        /// Total number of entries ever written to the admin log.
+        pub(crate) admin_log_length: u64,
+        /// Minimum duration (in milliseconds) allowed between the start and end of a poll.
+        pub(crate) min_duration: Timestamp,
+        /// Maximum duration (in milliseconds) allowed between the start and end of a poll.
+        pub(crate) max_duration: Timestamp,
+        /// Stores the fee pool of each poll. Maps the poll id to the balance of the pool.
+        pub(crate) fee_pools: Mapping<PollId, Balance>,
+        /// Amount paid from the poll's fee pool to the account that finalizes an expired poll.
+        pub(crate) finalization_bounty: Balance,
+        /// Stores the storage version of each poll. Maps the poll id to the version (missing for
+        /// polls with storage version 0).
+        pub(crate) poll_versions: Mapping<PollId, u8>,
+        /// Deposit that must be transferred to the contract when creating a poll.
+        pub(crate) poll_deposit: Balance,
+        /// Stores the deposit of each poll. Maps the poll id to the deposit that can be claimed
+        /// back by the owner once the poll has ended.
+        pub(crate) deposits: Mapping<PollId, Balance>,
+        /// Stores the accounts with the moderator role.
+        pub(crate) moderators: Mapping<AccountId, ()>,
+        /// Stores whether poll creation is restricted to the creator allow-list.
+        pub(crate) creator_allow_list_enabled: bool,
+        /// Stores the accounts allowed to create polls when the creator allow-list is enabled.
+        pub(crate) creators: Mapping<AccountId, ()>,
+        /// Stores the Blake2x256 hashes of the normalized terms that are not allowed in poll and
+        /// choice descriptions (see `hash_term`).
+        pub(crate) denied_terms: Mapping<[u8; 32], ()>,
+        /// Number of terms in the description denylist.
+        pub(crate) denied_term_count: u32,
+        /// Stores the polls that only accept private votes (see `vote_private`).
+        pub(crate) private_polls: Mapping<PollId, ()>,
+        /// Stores the voting keys registered for private polls. Maps the poll id and the account
+        /// derived from the key to the voting weight of the account that registered it.
+        pub(crate) voting_keys: Mapping<(PollId, AccountId), u128>,
+        /// Stores the accounts that registered a voting key. Maps the poll id and the account to
+        /// unit.
+        pub(crate) key_registrations: Mapping<(PollId, AccountId), ()>,
+        /// Total number of polls created.
+        pub(crate) poll_count: u64,
+        /// Stores the observer contract of each poll. Maps the poll id to the account of the
+        /// contract implementing `PollObserver`.
+        pub(crate) poll_observers: Mapping<PollId, AccountId>,
+        /// Stores the polls whose observer could not be notified when the poll ended.
+        pub(crate) failed_notifications: Mapping<PollId, ()>,
+        /// Stores the polls in prediction mode (voters can stake value on their ballot).
+        pub(crate) prediction_polls: Mapping<PollId, ()>,
+        /// Stores the stake attached to each ballot. Maps the poll id and the voter to the stake
+        /// (removed once the winnings are claimed).
+        pub(crate) stakes: Mapping<(PollId, AccountId), Balance>,
+        /// Stores the total stake backing each choice. Maps the poll id and the choice id to the
+        /// total stake.
+        pub(crate) choice_stakes: Mapping<(PollId, ChoiceId), Balance>,
+        /// Stores the total stake of each poll. Maps the poll id to the pot.
+        pub(crate) prediction_pots: Mapping<PollId, Balance>,
+        /// Protocol fee configuration.
+        pub(crate) fee_config: FeeConfig,
+        /// PSP22 token used for the payments of new polls (native currency if not present).
+        pub(crate) payment_token: Option<AccountId>,
+        /// Stores the PSP22 token used for the payments of each poll. Maps the poll id to the
+        /// token (missing for polls paid in the native currency).
+        pub(crate) poll_tokens: Mapping<PollId, AccountId>,
+        /// Contract implementing `ResultsPublisher` to which the results of ended polls are pushed.
+        pub(crate) results_publisher: Option<AccountId>,
+        /// Contract implementing `MembershipVerifier` which verifies the proofs of anonymous votes.
+        pub(crate) membership_verifier: Option<AccountId>,
+        /// Stores the verifier keys of polls accepting anonymous votes. Maps the poll id to the key.
+        pub(crate) verifier_keys: Mapping<PollId, Vec<u8>>,
+        /// Stores the encryption configuration of polls with encrypted ballots. Maps the poll id to
+        /// the configuration.
+        pub(crate) ballot_encryption: Mapping<PollId, BallotEncryption>,
+        /// Stores the encrypted ballots. Maps the poll id and the index of the ballot to the ballot.
+        pub(crate) encrypted_ballots: Mapping<(PollId, u32), EncryptedBallot>,
+        /// Stores the number of encrypted ballots of each poll.
+        pub(crate) encrypted_ballot_counts: Mapping<PollId, u32>,
+        /// Stores the decryption shares submitted by the committee. Maps the poll id and the
+        /// member to the share.
+        pub(crate) decryption_shares: Mapping<(PollId, AccountId), DecryptionShare>,
+        /// Stores the number of decryption shares submitted for each poll.
+        pub(crate) decryption_share_counts: Mapping<PollId, u32>,
+        /// Stores the verification hashes of the posted tallies of encrypted polls.
+        pub(crate) tally_hashes: Mapping<PollId, [u8; 32]>,
+        /// Stores the polls whose per-choice results are hidden from everyone but the owner while
+        /// they are running.
+        pub(crate) hidden_results: Mapping<PollId, ()>,
+        /// Stores the visibility of each poll. Maps the poll id to the visibility (missing for
+        /// public polls).
+        pub(crate) poll_visibility: Mapping<PollId, PollVisibility>,
+        /// Contract implementing `IdentityRegistry` used to resolve accounts to display names.
+        pub(crate) identity_registry: Option<AccountId>,
+        /// Stores the number of voter slots of polls with public ballots (see `public_voters`).
+        /// Maps the poll id to the number of slots (missing for polls with secret ballots).
+        pub(crate) public_ballots: Mapping<PollId, u32>,
+        /// Stores the voters of polls with public ballots in slots numbered in order of voting
+        /// (the slot of a retracted vote stays empty). Maps the poll id and slot to the voter.
+        pub(crate) public_voters: Mapping<(PollId, u32), AccountId>,
+        /// Stores the slot of each voter listed in `public_voters`. Maps the poll id and voter to
+        /// the slot.
+        pub(crate) public_voter_slots: Mapping<(PollId, AccountId), u32>,
+        /// Storage deposit charged by the chain per byte of stored data (mirrors the
+        /// `DepositPerByte` of the contracts pallet).
+        pub(crate) deposit_per_byte: Balance,
+        /// Storage deposit charged by the chain per stored item (mirrors the `DepositPerItem` of
+        /// the contracts pallet).
+        pub(crate) deposit_per_item: Balance,
+        /// Stores the polls whose choices are shuffled per account in `get_report_for`.
+        pub(crate) shuffled_polls: Mapping<PollId, ()>,
+        /// Stores the polls whose voting weights come from the weights uploaded by the owner
+        /// instead of the weight source.
+        pub(crate) weighted_polls: Mapping<PollId, ()>,
+        /// Stores the uploaded voting weights. Maps the poll id and the voter to the weight.
+        pub(crate) voter_weights: Mapping<(PollId, AccountId), u128>,
+        /// Stores the tally strategy of each poll. Maps the poll id to the strategy (missing for
+        /// plurality polls).
+        pub(crate) tally_strategies: Mapping<PollId, TallyStrategy>,
+        /// Stores the ranked ballots of polls with a ranked tally strategy. Maps the poll id and
+        /// the voter to the ranking of choices (most preferred first).
+        pub(crate) ranked_ballots: Mapping<(PollId, AccountId), Ranking>,
+        /// Stores the pairwise preferences of polls with the Condorcet tally strategy. Maps the
+        /// poll id and a pair of choices `(a, b)` to the weight of the voters preferring `a` to `b`.
+        pub(crate) pairwise_preferences: Mapping<ChoicePair, u128>,
+        /// Stores the Smith set of ended polls with the Condorcet tally strategy. Maps the poll id
+        /// to the choices in the Smith set.
+        pub(crate) smith_sets: Mapping<PollId, Vec<ChoiceId>>,
+        /// Stores the points of the choices of polls with the Borda tally strategy. Maps the poll
+        /// id and the choice id to the points.
+        pub(crate) borda_points: Mapping<(PollId, ChoiceId), u128>,
+        /// Stores the ranked ballots of polls with the STV tally strategy (identical rankings are
+        /// merged). Maps the poll id to the rankings and their weights.
+        pub(crate) stv_ballots: Mapping<PollId, Vec<WeightedRanking>>,
+        /// Stores the rounds of the count of ended polls with the STV tally strategy. Maps the
+        /// poll id to the rounds.
+        pub(crate) count_rounds: Mapping<PollId, Vec<CountRound>>,
+        /// Stores the elected choices of ended polls with the STV tally strategy. Maps the poll id
+        /// to the choices (in order of election).
+        pub(crate) winners: Mapping<PollId, Vec<ChoiceId>>,
+        /// Hash of the terms that voters must accept before voting (not present if voting does
+        /// not require accepting terms).
+        pub(crate) terms_hash: Option<[u8; 32]>,
+        /// Version of the terms, incremented whenever new terms are set.
+        pub(crate) terms_version: u32,
+        /// Stores the latest version of the terms accepted by each account.
+        pub(crate) terms_acceptances: Mapping<AccountId, u32>,
+        /// Deposit that must be transferred to the contract when challenging the result of a
+        /// poll.
+        pub(crate) challenge_deposit: Balance,
+        /// Stores the challenge period of each poll. Maps the poll id to the period (in
+        /// milliseconds) after the end of the poll during which its result can be challenged.
+        pub(crate) challenge_periods: Mapping<PollId, Timestamp>,
+        /// Stores the end of the challenge period of ended polls whose result is not final yet.
+        /// Maps the poll id to the timestamp (in milliseconds).
+        pub(crate) challenge_deadlines: Mapping<PollId, Timestamp>,
+        /// Stores the pending challenges. Maps the poll id to the challenge of its result.
+        pub(crate) challenges: Mapping<PollId, Challenge>,
+        /// Stores the accounts with the resolver role (see `resolve_challenge`).
+        pub(crate) resolvers: Mapping<AccountId, ()>,
+        /// Stores the polls voided by a resolver.
+        pub(crate) voided_polls: Mapping<PollId, ()>,
+        /// Stores the poll templates. Maps the template id to the template.
+        pub(crate) templates: Mapping<TemplateId, PollTemplate>,
+        /// Total number of poll templates created (also the id of the last template).
+        pub(crate) template_count: TemplateId,
+        /// Stores the series of polls created from each template. Maps the template id to the
+        /// poll ids (in order of creation).
+        pub(crate) series: Mapping<TemplateId, Vec<PollId>>,
+        /// Stores the template of each poll created from a template. Maps the poll id to the
+        /// template id.
+        pub(crate) poll_templates: Mapping<PollId, TemplateId>,
+        /// Stores the polls that only accept votes from registered accounts (see `register`). Maps
+        /// the poll id to the deposit required to register.
+        pub(crate) registration_deposits: Mapping<PollId, Balance>,
+        /// Stores the registrations. Maps the poll id and the account to the deposit it paid
+        /// (removed once the deposit is claimed back).
+        pub(crate) registrations: Mapping<(PollId, AccountId), Balance>,
+        /// Stores the number of accounts registered for each poll.
+        pub(crate) registered_counts: Mapping<PollId, u64>,
+        /// Stores the total voting weight of the accounts registered for each poll (at the time
+        /// of their registration).
+        pub(crate) registered_weights: Mapping<PollId, u128>,
+        /// Stores the quorum of each poll. Maps the poll id to the share (in basis points) of the
+        /// eligible weight that must be cast for the poll to be valid (missing for polls without
+        /// quorum).
+        pub(crate) quorum_weight_bps: Mapping<PollId, u16>,
+        /// Stores the total eligible weight of polls attested by their owner.
+        pub(crate) eligible_weights: Mapping<PollId, u128>,
+        /// Stores the total of the voting weights uploaded for each poll (see `set_voter_weights`).
+        pub(crate) uploaded_weights: Mapping<PollId, u128>,
+        /// Stores the maximum number of rate-limited calls an account can make per block (not
+        /// present if calls are not rate-limited).
+        pub(crate) rate_limit: Option<u32>,
+        /// Stores the block of the last rate-limited call of each account and the number of
+        /// such calls made by the account in that block.
+        pub(crate) call_counters: Mapping<AccountId, (BlockNumber, u32)>,
+        /// Stores the milestones reported by `MilestoneReached` events for each poll.
+        pub(crate) milestone_configs: Mapping<PollId, MilestoneConfig>,
+        /// Stores the choice leading each poll with lead milestones (see `MilestoneConfig`).
+        pub(crate) leaders: Mapping<PollId, ChoiceId>,
+        /// Stores the Merkle tree of the ballots recorded for each poll.
+        pub(crate) ballot_trees: Mapping<PollId, MerkleTree>,
+        /// Stores the block in which each poll ended.
+        pub(crate) end_blocks: Mapping<PollId, BlockNumber>,
+        /// Stores the operators approved by each account (principal) to vote on its behalf.
+        /// Maps `(principal, operator)` to nothing.
+        pub(crate) operators: Mapping<(AccountId, AccountId), ()>,
+        /// Stores the ids of the polls created by each account.
+        pub(crate) owned_polls: Mapping<AccountId, Vec<PollId>>,
+        /// Stores the number of the ballot of each voter on a poll (see `VoteCast`).
+        pub(crate) ballot_numbers: Mapping<(PollId, AccountId), u64>,
+        /// Stores the bounty funded by the owner of each poll for the account finalizing it.
+        pub(crate) keeper_bounties: Mapping<PollId, Balance>,
+        /// Stores the claims of the keeper bounties of the polls.
+        pub(crate) keeper_claims: Mapping<PollId, KeeperClaim>,
+        /// Stores the configuration inherited by new polls.
+        pub(crate) default_poll_config: PollConfig,
+        /// Stores the upload sessions of the lists of eligible voters of the polls.
+        pub(crate) voter_list_uploads: Mapping<PollId, VoterListUpload>,
+        /// Contract implementing `MemberRegistry` used to resolve accounts to member indexes.
+        pub(crate) member_registry: Option<AccountId>,
+        /// Stores the eligibility of the members of the member registry as a paged bitmap. Maps
+        /// the poll id and the page to the bits of its 256 members.
+        pub(crate) eligible_members: Mapping<(PollId, u32), BitmapPage>,
+        /// Stores the members that voted as a paged bitmap (see `eligible_members`).
+        pub(crate) member_votes: Mapping<(PollId, u32), BitmapPage>,
+        /// Stores the vote timelines of the polls: the number of ballots cast during each bucket
+        /// of `TIMELINE_BUCKET_DURATION` (buckets without ballots are omitted).
+        pub(crate) vote_timelines: Mapping<PollId, Vec<TimelineBucket>>,
+        /// Stores the existence of the choices of the polls as a paged bitmap, so that votes
+        /// check it with a fixed-size read. Maps the poll id and the page to the bits of its 256
+        /// choice ids (missing for pages without choices).
+        pub(crate) choice_bitmaps: Mapping<(PollId, u32), BitmapPage>,
+        /// Contract implementing `PollStats` to which incremental updates of the polls are
+        /// pushed.
+        pub(crate) stats_contract: Option<AccountId>,
+        /// State of the migration mode of the contract.
+        pub(crate) migration_mode: MigrationMode,
+        /// Stores the descriptions of the polls, apart from the polls so that votes do not read
+        /// them.
+        pub(crate) poll_descriptions: Mapping<PollId, Description>,
+        /// Stores the accounts which can't become the admin of the contract.
+        pub(crate) denied_admins: Mapping<AccountId, ()>,
+        /// Stores the accounts allowed to end the polls besides their owners. Maps the poll id to
+        /// the finalizer.
+        pub(crate) finalizers: Mapping<PollId, AccountId>,
+        /// Schedule boosting the votes of long-term members (not present if votes are not
+        /// boosted).
+        pub(crate) tenure_schedule: Option<TenureSchedule>,
+        /// Maximum number of polls which can be active (started) at the same time (not present
+        /// if there is no limit).
+        pub(crate) max_active_polls: Option<u32>,
+        /// Number of polls which are active (started).
+        pub(crate) active_poll_count: u32,
+        /// Stores the ids of the deleted polls, which can't be reused.
+        pub(crate) deleted_polls: Mapping<PollId, ()>,
+        /// Stores the maximum number of votes each choice of a poll can receive.
+        pub(crate) choice_capacities: Mapping<PollId, u32>,
+        /// Stores the number of votes received by the choices of polls with a choice capacity.
+        pub(crate) choice_ballots: Mapping<(PollId, ChoiceId), u32>,
+        /// Stores the weight of the votes cast on polls with a choice capacity (which can be
+        /// retracted).
+        pub(crate) retractable_weights: Mapping<(PollId, AccountId), u128>,
+        /// Stores the voters waiting for a seat on the full choices of polls, in order.
+        pub(crate) waitlists: Mapping<(PollId, ChoiceId), Waitlist>,
+        /// Stores the choice each voter is waiting for a seat on.
+        pub(crate) waitlisted_by: Mapping<(PollId, AccountId), ChoiceId>,
+        /// Stores the ids of the questions of polls with multiple questions, in order.
+        pub(crate) question_ids: Mapping<PollId, Vec<QuestionId>>,
+        /// Stores the questions of polls with multiple questions.
+        pub(crate) questions: Mapping<(PollId, QuestionId), Question>,
+        /// Stores the question each choice of a poll with multiple questions belongs to.
+        pub(crate) choice_questions: Mapping<(PollId, ChoiceId), QuestionId>,
+        /// Stores the ranges of the numeric questions of polls with multiple questions.
+        pub(crate) numeric_ranges: Mapping<(PollId, QuestionId), NumericRange>,
+        /// Stores the running tallies of the answers to numeric questions.
+        pub(crate) numeric_tallies: Mapping<(PollId, QuestionId), NumericTally>,
+        /// Stores the namespaces of the events of the polls with a topic seed.
+        pub(crate) topic_namespaces: Mapping<PollId, [u8; 32]>,
+        /// Version of the code which last migrated the storage (see `CODE_VERSION`).
+        pub(crate) code_version: u32,
+        /// Version of the code announced by the last `set_code`, verified by the next `migrate`
+        /// (not present if it was verified).
+        pub(crate) pending_code_version: Option<u32>,
+        /// Stores the number of accounts that voted for each choice of a poll, regardless of the
+        /// weight of their votes (see `vote_counts`).
+        pub(crate) voter_counts: Mapping<(PollId, ChoiceId), u64>,
+        /// Stores the polls whose owner may not vote on them (see `set_owner_may_vote`).
+        pub(crate) abstaining_owners: Mapping<PollId, ()>,
+        /// Stores the accounts recused from voting on polls (see `recuse`).
+        pub(crate) recusals: Mapping<(PollId, AccountId), ()>,
+        /// Stores the number of accounts recused from voting on each poll.
+        pub(crate) recusal_counts: Mapping<PollId, u32>,
+        /// Stores the voters who retracted their vote on a poll and have not voted again.
+        pub(crate) retracted_voters: Mapping<(PollId, AccountId), ()>,
+        /// Stores the number of ballots of each poll cast again after being retracted.
+        pub(crate) changed_ballots: Mapping<PollId, u64>,
+        /// Human-readable name and link of the instance, kept in its own storage cell.
+        pub(crate) instance_metadata: Lazy<InstanceMetadata, ManualKey<INSTANCE_METADATA_KEY>>,
+        /// Recently started polls and running polls ending soon, kept in their own storage cell.
+        pub(crate) active_feed: Lazy<ActiveFeed, ManualKey<ACTIVE_FEED_KEY>>,
+        /// Stores the compact result records of the archived polls (see `archive_poll`).
+        pub(crate) results: Mapping<PollId, ResultRecord>,
     }
 
+    /// Selectors of the messages are fixed to the values derived from their original names, so
+    /// that messages can be renamed or moved without breaking existing callers. New messages use
+    /// the first four bytes of the BLAKE2b-256 hash of their name.
     impl VotingContract {
         /// Constructor for the voting contract.
         #[ink(constructor)]
+        // The constructor keeps its name (and selector) although the contract is exported.
+        #[allow(clippy::should_implement_trait)]
         pub fn default() -> Self {
             Self {
                 polls: Mapping::new(),
                 choices: Mapping::new(),
-                choice_ids: Mapping::new(),
+                choice_count: Mapping::new(),
+                choice_slots: Mapping::new(),
+                next_choice_ids: Mapping::new(),
                 vote_counts: Mapping::new(),
                 voted_by: Mapping::new(),
+                ballots: Mapping::new(),
+                poll_delegations: Mapping::new(),
+                global_delegations: Mapping::new(),
+                total_ballots: Mapping::new(),
                 admin: Self::env().caller(),
                 paused: false,
+                circuit_breakers: CircuitBreakers::default(),
+                admin_log: Mapping::new(),
+                admin_log_length: 0,
+                min_duration: DEFAULT_MIN_DURATION,
+                max_duration: DEFAULT_MAX_DURATION,
+                fee_pools: Mapping::new(),
+                finalization_bounty: 0,
+                poll_versions: Mapping::new(),
+                poll_deposit: 0,
+                deposits: Mapping::new(),
+                moderators: Mapping::new(),
+                creator_allow_list_enabled: false,
+                creators: Mapping::new(),
+                denied_terms: Mapping::new(),
+                denied_term_count: 0,
+                private_polls: Mapping::new(),
+                voting_keys: Mapping::new(),
+                key_registrations: Mapping::new(),
+                poll_count: 0,
+                poll_observers: Mapping::new(),
+                failed_notifications: Mapping::new(),
+                prediction_polls: Mapping::new(),
+                stakes: Mapping::new(),
+                choice_stakes: Mapping::new(),
+                prediction_pots: Mapping::new(),
+                fee_config: FeeConfig {
+                    bps: 0,
+                    recipient: Self::env().caller(),
+                },
+                payment_token: None,
+                poll_tokens: Mapping::new(),
+                results_publisher: None,
+                membership_verifier: None,
+                verifier_keys: Mapping::new(),
+                ballot_encryption: Mapping::new(),
+                encrypted_ballots: Mapping::new(),
+                encrypted_ballot_counts: Mapping::new(),
+                decryption_shares: Mapping::new(),
+                decryption_share_counts: Mapping::new(),
+                tally_hashes: Mapping::new(),
+                hidden_results: Mapping::new(),
+                poll_visibility: Mapping::new(),
+                identity_registry: None,
+                public_ballots: Mapping::new(),
+                public_voters: Mapping::new(),
+                public_voter_slots: Mapping::new(),
+                deposit_per_byte: 0,
+                deposit_per_item: 0,
+                shuffled_polls: Mapping::new(),
+                weighted_polls: Mapping::new(),
+                voter_weights: Mapping::new(),
+                tally_strategies: Mapping::new(),
+                ranked_ballots: Mapping::new(),
+                pairwise_preferences: Mapping::new(),
+                smith_sets: Mapping::new(),
+                borda_points: Mapping::new(),
+                stv_ballots: Mapping::new(),
+                count_rounds: Mapping::new(),
+                winners: Mapping::new(),
+                terms_hash: None,
+                terms_version: 0,
+                terms_acceptances: Mapping::new(),
+                challenge_deposit: 0,
+                challenge_periods: Mapping::new(),
+                challenge_deadlines: Mapping::new(),
+                challenges: Mapping::new(),
+                resolvers: Mapping::new(),
+                voided_polls: Mapping::new(),
+                templates: Mapping::new(),
+                template_count: 0,
+                series: Mapping::new(),
+                poll_templates: Mapping::new(),
+                registration_deposits: Mapping::new(),
+                registrations: Mapping::new(),
+                registered_counts: Mapping::new(),
+                registered_weights: Mapping::new(),
+                quorum_weight_bps: Mapping::new(),
+                eligible_weights: Mapping::new(),
+                uploaded_weights: Mapping::new(),
+                rate_limit: None,
+                call_counters: Mapping::new(),
+                milestone_configs: Mapping::new(),
+                leaders: Mapping::new(),
+                ballot_trees: Mapping::new(),
+                end_blocks: Mapping::new(),
+                operators: Mapping::new(),
+                owned_polls: Mapping::new(),
+                ballot_numbers: Mapping::new(),
+                keeper_bounties: Mapping::new(),
+                keeper_claims: Mapping::new(),
+                default_poll_config: PollConfig::default(),
+                voter_list_uploads: Mapping::new(),
+                member_registry: None,
+                eligible_members: Mapping::new(),
+                member_votes: Mapping::new(),
+                vote_timelines: Mapping::new(),
+                choice_bitmaps: Mapping::new(),
+                stats_contract: None,
+                migration_mode: MigrationMode::default(),
+                poll_descriptions: Mapping::new(),
+                denied_admins: Mapping::new(),
+                finalizers: Mapping::new(),
+                tenure_schedule: None,
+                max_active_polls: None,
+                active_poll_count: 0,
+                deleted_polls: Mapping::new(),
+                choice_capacities: Mapping::new(),
+                choice_ballots: Mapping::new(),
+                retractable_weights: Mapping::new(),
+                waitlists: Mapping::new(),
+                waitlisted_by: Mapping::new(),
+                question_ids: Mapping::new(),
+                questions: Mapping::new(),
+                choice_questions: Mapping::new(),
+                numeric_ranges: Mapping::new(),
+                numeric_tallies: Mapping::new(),
+                topic_namespaces: Mapping::new(),
+                code_version: CODE_VERSION,
+                pending_code_version: None,
+                voter_counts: Mapping::new(),
+                abstaining_owners: Mapping::new(),
+                recusals: Mapping::new(),
+                recusal_counts: Mapping::new(),
+                retracted_voters: Mapping::new(),
+                changed_ballots: Mapping::new(),
+                instance_metadata: Lazy::new(),
+                active_feed: Lazy::new(),
+                results: Mapping::new(),
             }
         }
 
-        #[ink(message)]
+        #[ink(message, selector = 0x81E0C604)]
         /// Pauses the contract.
         pub fn pause(&mut self) -> Result<(), Error> {
             // Check if the caller is the admin.
@@ -211,10 +920,13 @@ mod voting_contract {
             // Pause the contract.
             self.paused = true;
 
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::Pause, &());
+
             Ok(())
         }
 
-        #[ink(message)]
+        #[ink(message, selector = 0x67616649)]
         /// Unpauses the contract.
         pub fn unpause(&mut self) -> Result<(), Error> {
             // Check if the caller is the admin.
@@ -225,743 +937,10205 @@ mod voting_contract {
             // Unpause the contract.
             self.paused = false;
 
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::Unpause, &());
+
             Ok(())
         }
 
-        /// Modifies the code which is used to execute calls to this contract address (`AccountId`).
-        #[ink(message)]
-        pub fn set_code(&mut self, code_hash: [u8; 32]) -> Result<(), Error> {
+        #[ink(message, selector = 0x1244D0CF)]
+        /// Sets the circuit breakers which pause individual operations of the contract.
+        pub fn set_circuit_breakers(
+            &mut self,
+            circuit_breakers: CircuitBreakers,
+        ) -> Result<(), Error> {
             // Check if the caller is the admin.
             if self.env().caller() != self.admin {
                 return Err(Error::CallerIsNotAdmin);
             }
 
-            ink::env::set_code_hash(&code_hash)
-                .map_err(|err| Error::FailedToSetCodeHash(format!("{:?}", err)))
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetCircuitBreakers, &circuit_breakers);
+
+            // Change the circuit breakers.
+            self.circuit_breakers = circuit_breakers;
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xCEF4972B)]
+        /// Get the circuit breakers which pause individual operations of the contract.
+        pub fn get_circuit_breakers(&self) -> CircuitBreakers {
+            self.circuit_breakers.clone()
         }
 
-        #[ink(message)]
-        /// Changes the admin of the contract.
-        pub fn change_admin(&mut self, new_admin: AccountId) -> Result<(), Error> {
+        #[ink(message, selector = 0x55121B58)]
+        /// Limits the number of votes (and registrations) an account can submit per block, or
+        /// removes the limit (if not present).
+        pub fn set_rate_limit(&mut self, rate_limit: Option<u32>) -> Result<(), Error> {
             // Check if the caller is the admin.
             if self.env().caller() != self.admin {
                 return Err(Error::CallerIsNotAdmin);
             }
 
-            // Change the admin.
-            self.admin = new_admin;
+            // Change the rate limit.
+            self.rate_limit = rate_limit;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetRateLimit, &rate_limit);
 
             Ok(())
         }
 
-        #[ink(message)]
-        /// Creates a new poll.
-        pub fn create_poll(&mut self, poll_id: PollId, description: String) -> Result<(), Error> {
-            // Check if the contract is paused.
-            if self.paused {
-                return Err(Error::ContractIsPaused);
-            }
+        #[ink(message, selector = 0x1ECD5405)]
+        /// Get the maximum number of votes (and registrations) an account can submit per block
+        /// (not present if there is no limit).
+        pub fn get_rate_limit(&self) -> Option<u32> {
+            self.rate_limit
+        }
 
-            // Check if the poll already exists.
-            if self.polls.get(&poll_id).is_some() {
-                return Err(Error::PollWithIdAlreadyExists);
+        #[ink(message, selector = 0x52666DDB)]
+        /// Limits the number of polls which can be active (started) at the same time, or removes
+        /// the limit (if not present). Polls which are already active are not affected.
+        pub fn set_max_active_polls(&mut self, max_active_polls: Option<u32>) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
             }
 
-            // Create the poll.
-            let poll = Poll {
-                description: description.clone(),
-                status: PollStatus::NotStarted,
-                owner: self.env().caller(),
-                winner: None,
-            };
-
-            // Insert the poll into the storage.
-            self.polls.insert(poll_id, &poll);
+            // Change the maximum number of active polls.
+            self.max_active_polls = max_active_polls;
 
-            // Emit the event.
-            self.env().emit_event(PollCreated {
-                poll_id,
-                description,
-                owner: self.env().caller(),
-            });
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetMaxActivePolls, &max_active_polls);
 
             Ok(())
         }
 
-        #[ink(message)]
-        /// Adds a choice to a poll.
-        pub fn add_choice(
-            &mut self,
-            poll_id: PollId,
-            choice_id: ChoiceId,
-            description: String,
-        ) -> Result<(), Error> {
-            // Check if the contract is paused.
-            if self.paused {
-                return Err(Error::ContractIsPaused);
-            }
+        #[ink(message, selector = 0x27FAF56B)]
+        /// Get the maximum number of polls which can be active at the same time (not present if
+        /// there is no limit).
+        pub fn get_max_active_polls(&self) -> Option<u32> {
+            self.max_active_polls
+        }
 
-            // Get the poll and return error if it does not exist.
-            let poll = self
-                .polls
-                .get(&poll_id)
-                .ok_or(Error::PollWithIdDoesNotExist)?;
+        #[ink(message, selector = 0x58E64AEA)]
+        /// Get the number of active (started) polls.
+        pub fn get_active_poll_count(&self) -> u32 {
+            self.active_poll_count
+        }
 
-            // Check if the caller is the owner of the poll.
-            if self.env().caller() != poll.owner {
-                return Err(Error::OnlyOwnerCanAddChoice);
-            }
+        #[ink(message, selector = 0x50BEA2A0)]
+        /// Get the retained entries of the admin log (oldest first), skipping the first `offset`
+        /// entries and returning at most `limit` entries.
+        pub fn get_admin_log(&self, offset: u64, limit: u32) -> Result<Vec<AdminLogEntry>, Error> {
+            self.admin_log_page(offset, limit)
+        }
 
-            // Check if the poll has started or ended.
-            match poll.status {
-                PollStatus::Started => return Err(Error::PollHasStarted),
-                PollStatus::Ended => return Err(Error::PollHasEnded),
-                PollStatus::NotStarted => {}
+        /// Modifies the code which is used to execute calls to this contract address (`AccountId`).
+        /// The version of the new code (see `CODE_VERSION`) can't be lower than the version of the
+        /// code which last migrated the storage, and is verified by the next `migrate`.
+        #[ink(message, selector = 0x694FB50F)]
+        pub fn set_code(&mut self, code_hash: [u8; 32], code_version: u32) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
             }
 
-            // Check if the choice already exists.
-            if self.choices.contains((poll_id, choice_id)) {
-                return Err(Error::ChoiceWithIdAlreadyExists);
+            // Check if upgrades are paused.
+            if self.circuit_breakers.pause_upgrades {
+                return Err(Error::UpgradesArePaused);
             }
 
-            // Create the choice.
-            let choice = Choice {
-                description: description.clone(),
-            };
-
-            // Get the list of choice ids for the poll.
-            let mut choice_id_list = self.choice_ids.get(&poll_id).unwrap_or_default();
+            // Check if the new code is not older than the code which last migrated the storage.
+            if code_version < self.code_version {
+                return Err(Error::CodeVersionTooLow);
+            }
 
-            // Add the choice to the list of choices for the poll.
-            choice_id_list.push(choice_id);
+            self.env()
+                .set_code_hash(&code_hash.into())
+                .map_err(|err| Error::FailedToSetCodeHash(format!("{:?}", err)))?;
 
-            // Insert the choice into the storage.
-            self.choices.insert((poll_id, choice_id), &choice);
+            // Remember the version of the new code until `migrate` verifies it.
+            self.pending_code_version = Some(code_version);
 
-            // Insert the list of choice ids for the poll into the storage.
-            self.choice_ids.insert(&poll_id, &choice_id_list);
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetCode, &(code_hash, code_version));
 
             // Emit the event.
-            self.env().emit_event(ChoiceAdded {
-                poll_id,
-                choice_id,
-                description,
+            self.env().emit_event(UpgradeExecuted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                code_hash,
+                admin: self.admin,
             });
 
             Ok(())
         }
 
-        #[ink(message)]
-        /// Starts a poll.
-        pub fn start_poll(&mut self, poll_id: PollId) -> Result<(), Error> {
-            // Check if the contract is paused.
-            if self.paused {
-                return Err(Error::ContractIsPaused);
-            }
-
-            // Get the poll and return error if it does not exist.
-            let mut poll = self
-                .polls
-                .get(&poll_id)
-                .ok_or(Error::PollWithIdDoesNotExist)?;
-
-            // Check if the caller is the owner of the poll.
-            if self.env().caller() != poll.owner {
-                return Err(Error::OnlyOwnerCanStartPoll);
+        #[ink(message, selector = 0xBCA1463A)]
+        /// Changes the code of the contract to the proxy with the given code hash, which delegates
+        /// all the calls to the current code of the contract (its implementation) and can later be
+        /// pointed to other implementations, or back to the previous one, by the admin. The admin
+        /// of the contract becomes the admin of the proxy.
+        pub fn enable_proxy(&mut self, proxy_code_hash: [u8; 32]) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
             }
 
-            // Check if the poll has started or ended.
-            match poll.status {
-                PollStatus::Started => return Err(Error::PollHasStarted),
-                PollStatus::Ended => return Err(Error::PollHasEnded),
-                PollStatus::NotStarted => {}
+            // Check if upgrades are paused.
+            if self.circuit_breakers.pause_upgrades {
+                return Err(Error::UpgradesArePaused);
             }
 
-            self.choice_ids
-                .get(&poll_id)
-                .ok_or(Error::CannotStartPollWithNoChoices)?;
+            // Record the admin and the implementation under the storage keys of the proxy.
+            let implementation = self
+                .env()
+                .own_code_hash()
+                .map_err(|err| Error::FailedToSetCodeHash(format!("{:?}", err)))?;
+            ink::env::set_contract_storage(&PROXY_ADMIN_KEY, &self.admin);
+            ink::env::set_contract_storage(&PROXY_IMPLEMENTATION_KEY, &implementation);
 
-            // Change the status of the poll.
-            poll.status = PollStatus::Started;
+            self.env()
+                .set_code_hash(&proxy_code_hash.into())
+                .map_err(|err| Error::FailedToSetCodeHash(format!("{:?}", err)))?;
 
-            // Insert the poll into the storage.
-            self.polls.insert(poll_id, &poll);
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::EnableProxy, &proxy_code_hash);
 
             // Emit the event.
-            self.env().emit_event(PollStarted { poll_id });
+            self.env().emit_event(UpgradeExecuted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                code_hash: proxy_code_hash,
+                admin: self.admin,
+            });
 
             Ok(())
         }
 
-        #[ink(message)]
-        /// Ends a poll.
-        pub fn end_poll(&mut self, poll_id: PollId) -> Result<(), Error> {
-            // Check if the contract is paused.
-            if self.paused {
-                return Err(Error::ContractIsPaused);
-            }
-
-            // Get the poll and return error if it does not exist.
-            let mut poll = self
-                .polls
-                .get(&poll_id)
-                .ok_or(Error::PollWithIdDoesNotExist)?;
-
-            // Check if the caller is the owner of the poll.
-            if self.env().caller() != poll.owner {
-                return Err(Error::OnlyOwnerCanEndPoll);
+        #[ink(message, selector = 0x060D3F50)]
+        /// Migrates the storage of a poll written by an older version of the contract to the
+        /// current storage layout, and records the version of the current code (see
+        /// `get_code_version`).
+        pub fn migrate(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
             }
 
-            match poll.status {
-                PollStatus::Started => {}
-                PollStatus::Ended => return Err(Error::PollHasEnded),
-                PollStatus::NotStarted => return Err(Error::PollHasNotStarted),
-            }
+            // Record the version of the current code.
+            let code_version_changed = self.record_code_version()?;
 
-            // Change the status of the poll.
-            poll.status = PollStatus::Ended;
+            // Check if the poll needs to be migrated (unless only the code version is recorded).
+            let version = self.poll_versions.get(poll_id).unwrap_or_default();
 
-            // Get the list of choices for the poll.
-            let choices: Vec<(ChoiceId, u64)> = self
-                .choice_ids
-                .get(&poll_id)
-                .unwrap_or_default()
-                .into_iter()
-                .map(|choice_id| {
-                    let vote_count = self
-                        .vote_counts
-                        .get(&(poll_id, choice_id))
-                        .unwrap_or_default();
+            if version >= POLL_STORAGE_VERSION {
+                if code_version_changed {
+                    return Ok(());
+                }
 
-                    (choice_id, vote_count)
-                })
-                .collect();
+                return Err(Error::PollAlreadyMigrated);
+            }
 
-            // Get the winner of the poll.
-            let winner = {
-                let mut candidate_winner = &choices[0];
-                let mut found_tie = false;
+            // Migrate the poll from `u8` choice ids to `u32` choice ids.
+            if version < 1 {
+                self.migrate_choice_ids(poll_id)?;
+            }
 
-                for choice in choices.iter().skip(1) {
-                    if choice.1 == candidate_winner.1 {
-                        found_tie = true;
-                    }
+            // Migrate the poll from `u64` vote counts to `u128` vote counts.
+            if version < 2 {
+                self.migrate_vote_counts(poll_id);
+            }
 
-                    if choice.1 > candidate_winner.1 {
-                        candidate_winner = choice;
-                        found_tie = false;
-                    }
-                }
+            // Build the choice bitmap of the poll.
+            if version < 3 {
+                self.migrate_choice_bitmap(poll_id);
+            }
 
-                if found_tie {
-                    None
-                } else {
-                    Some(candidate_winner.0)
-                }
-            };
+            // Move the description of the poll out of the poll.
+            if version < 4 {
+                self.migrate_poll_description(poll_id)?;
+            }
 
-            // Change the winner of the poll.
-            poll.winner = winner;
+            // Split the list of choices of the poll into slots.
+            if version < 5 {
+                self.migrate_choice_slots(poll_id);
+            }
 
-            // Insert the poll into the storage.
-            self.polls.insert(poll_id, &poll);
+            // Insert the storage version of the poll into the storage.
+            self.poll_versions.insert(poll_id, &POLL_STORAGE_VERSION);
 
-            // Emit the event.
-            self.env().emit_event(PollEnded { poll_id, winner });
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::Migrate, &poll_id);
 
             Ok(())
         }
 
-        #[ink(message)]
-        /// Votes on a poll.
-        pub fn vote(&mut self, poll_id: PollId, choice_id: ChoiceId) -> Result<(), Error> {
-            // Check if the contract is paused.
-            if self.paused {
-                return Err(Error::ContractIsPaused);
+        #[ink(message, selector = 0xA2F7652D)]
+        /// Get the version of the code which last migrated the storage (see `migrate`).
+        pub fn get_code_version(&self) -> u32 {
+            self.code_version
+        }
+
+        #[ink(message, selector = 0xB3A4A116)]
+        /// Enters migration mode, during which historical polls can be imported with
+        /// `import_poll`. Migration mode can only be entered once.
+        pub fn enter_migration_mode(&mut self) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
             }
 
-            // Get the poll and return error if it does not exist.
-            let poll = self
-                .polls
-                .get(&poll_id)
-                .ok_or(Error::PollWithIdDoesNotExist)?;
+            // Check if migration mode has already been entered.
+            if self.migration_mode != MigrationMode::Inactive {
+                return Err(Error::MigrationModeUnavailable);
+            }
+
+            // Enter migration mode.
+            self.migration_mode = MigrationMode::Active;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::EnterMigrationMode, &());
+
+            Ok(())
+        }
 
-            // Check the status and return error if the poll has not started or has ended.
-            match poll.status {
-                PollStatus::NotStarted => return Err(Error::PollHasNotStarted),
-                PollStatus::Ended => return Err(Error::PollHasEnded),
-                PollStatus::Started => {}
+        #[ink(message, selector = 0xAE1AD319)]
+        /// Exits migration mode permanently.
+        pub fn exit_migration_mode(&mut self) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
             }
 
-            // Check if the choice exists.
-            if !self.choices.contains((poll_id, choice_id)) {
+            // Check if migration mode is active.
+            if self.migration_mode != MigrationMode::Active {
+                return Err(Error::MigrationModeIsNotActive);
+            }
+
+            // Exit migration mode.
+            self.migration_mode = MigrationMode::Closed;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::ExitMigrationMode, &());
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x639E820C)]
+        /// Get the state of the migration mode of the contract.
+        pub fn get_migration_mode(&self) -> MigrationMode {
+            self.migration_mode
+        }
+
+        #[ink(message, selector = 0x9329F63E)]
+        /// Imports a historical poll from another instance of the contract, with its description,
+        /// its choices and the vote counts of its choices, while migration mode is active. The
+        /// import is recorded with a `PollImported` event.
+        pub fn import_poll(
+            &mut self,
+            poll_id: PollId,
+            poll: Poll,
+            description: String,
+            choices: Vec<(ChoiceId, String)>,
+            counts: Vec<(ChoiceId, u128)>,
+        ) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Check if migration mode is active.
+            if self.migration_mode != MigrationMode::Active {
+                return Err(Error::MigrationModeIsNotActive);
+            }
+
+            // Check if the batch is too large.
+            if choices.len() > MAX_CHOICE_BATCH_SIZE as usize {
+                return Err(Error::BatchTooLarge);
+            }
+
+            // Check if the poll already exists (or existed and has been deleted).
+            if self.polls.contains(poll_id) || self.deleted_polls.contains(poll_id) {
+                return Err(Error::PollWithIdAlreadyExists);
+            }
+
+            // Check if the counts and the winner refer to the imported choices.
+            let has_choice = |choice_id: &ChoiceId| choices.iter().any(|(id, _)| id == choice_id);
+
+            if !counts.iter().all(|(choice_id, _)| has_choice(choice_id))
+                || !poll.winner.iter().all(has_choice)
+            {
                 return Err(Error::ChoiceWithIdDoesNotExist);
             }
 
-            // Get the caller.
-            let caller = self.env().caller();
+            let data_hash = self.env().hash_encoded::<ink::env::hash::Blake2x256, _>(&(
+                &poll,
+                &description,
+                &choices,
+                &counts,
+            ));
+            let description = self.check_description(&description)?;
 
-            // Check if the caller has already voted on the poll.
-            if self.voted_by.contains((poll_id, caller)) {
-                return Err(Error::CallerAlreadyVotedOnPoll);
+            // Insert the choices into the storage.
+            let mut choice_id_list = Vec::new();
+
+            for (choice_id, description) in choices {
+                if self.choices.contains((poll_id, choice_id)) {
+                    return Err(Error::ChoiceWithIdAlreadyExists);
+                }
+
+                let description = self.check_description(&description)?;
+                self.choices
+                    .insert((poll_id, choice_id), &Choice { description });
+                self.set_choice_bit(poll_id, choice_id, true);
+                choice_id_list.push(choice_id);
             }
 
-            // Get the current vote count.
-            let current_vote_count = self
-                .vote_counts
-                .get((poll_id, choice_id))
-                .unwrap_or_default();
+            if let Some(&max_choice_id) = choice_id_list.iter().max() {
+                self.next_choice_ids
+                    .insert(poll_id, &max_choice_id.saturating_add(1));
+            }
 
-            // Calculate the new vote count (increment the current vote count by 1).
-            let new_vote_count = current_vote_count + 1;
+            self.set_choice_id_list(poll_id, &choice_id_list);
 
-            // Insert the new vote count into storage.
-            self.vote_counts
-                .insert((poll_id, choice_id), &new_vote_count);
+            // Insert the vote counts into the storage.
+            for (choice_id, count) in counts {
+                self.vote_counts.insert((poll_id, choice_id), &count);
+            }
 
-            // Insert the caller into storage.
-            self.voted_by.insert((poll_id, caller), &true);
+            // Insert the poll and its description into the storage.
+            let owner = poll.owner;
+            self.polls.insert(poll_id, &poll);
+            self.poll_descriptions.insert(poll_id, &description);
+
+            // Count the poll among the active polls if it has started.
+            if poll.status == PollStatus::Started {
+                self.active_poll_count = self.active_poll_count.saturating_add(1);
+                self.add_to_feed(poll_id, poll.end_at);
+            }
+
+            // Increment the number of polls.
+            self.poll_count = self.poll_count.saturating_add(1);
+
+            // List the poll among the polls of its owner.
+            let mut owned_polls = self.owned_polls.get(owner).unwrap_or_default();
+            owned_polls.push(poll_id);
+            self.owned_polls.insert(owner, &owned_polls);
+
+            // Insert the storage version of the poll into the storage.
+            self.poll_versions.insert(poll_id, &POLL_STORAGE_VERSION);
+
+            // Emit the event.
+            self.env().emit_event(PollImported {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                owner,
+                data_hash,
+            });
 
             Ok(())
         }
 
-        #[ink(message)]
-        /// Get all the choices for a poll.
-        pub fn get_choices(&self, poll_id: PollId) -> Vec<(ChoiceId, Choice)> {
-            // Get the list of choice ids for the poll.
-            let choice_list = self.choice_ids.get(&poll_id).unwrap_or_default();
+        #[ink(message, selector = 0x15B783B3)]
+        /// Removes the ids of the choices missing from the storage from the list of choices of a
+        /// poll and moves the next automatically assigned choice id past the remaining ones.
+        /// Returns the number of removed ids.
+        pub fn repair_indexes(&mut self, poll_id: PollId) -> Result<u32, Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Check if the poll exists.
+            if !self.polls.contains(poll_id) {
+                return Err(Error::PollWithIdDoesNotExist);
+            }
 
-            // Get the choices from storage.
-            choice_list
+            // Remove the ids of the missing choices (from the list and from the bitmap).
+            let (choice_id_list, missing): (Vec<_>, Vec<_>) = self
+                .choice_id_list(poll_id)
                 .into_iter()
-                .map(|choice_id| (choice_id, self.choices.get(&(poll_id, choice_id)).unwrap()))
-                .collect()
+                .partition(|choice_id| self.choices.contains((poll_id, *choice_id)));
+            let removed = missing.len() as u32;
+
+            if removed > 0 {
+                self.set_choice_id_list(poll_id, &choice_id_list);
+            }
+
+            for choice_id in missing {
+                self.set_choice_bit(poll_id, choice_id, false);
+            }
+
+            // Move the next choice id past the remaining choices.
+            if let Some(max_choice_id) = choice_id_list.iter().max() {
+                let next_choice_id = self.next_choice_ids.get(poll_id).unwrap_or_default();
+
+                if *max_choice_id >= next_choice_id {
+                    self.next_choice_ids
+                        .insert(poll_id, &max_choice_id.saturating_add(1));
+                }
+            }
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::RepairIndexes, &poll_id);
+
+            Ok(removed)
         }
 
-        #[ink(message)]
-        /// Get the report for a poll.
-        pub fn get_report(&self, poll_id: PollId) -> Result<PollReport, Error> {
-            let poll = self
-                .polls
-                .get(&poll_id)
-                .ok_or(Error::PollWithIdDoesNotExist)?;
+        #[ink(message, selector = 0x61AE97D7)]
+        /// Changes the admin of the contract. The new admin can't be the account of the contract,
+        /// the zero account or an account of the denylist of admins, as no one could administer
+        /// the contract anymore.
+        pub fn change_admin(&mut self, new_admin: AccountId) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
 
-            let choices: Vec<ChoiceReport> = self
-                .choice_ids
-                .get(&poll_id)
-                .unwrap_or_default()
-                .into_iter()
-                .map(|choice_id| {
-                    let choice = self.choices.get(&(poll_id, choice_id)).unwrap();
+            // Check if the new admin is a valid target.
+            if new_admin == self.env().account_id()
+                || new_admin == AccountId::from([0; 32])
+                || self.denied_admins.contains(new_admin)
+            {
+                return Err(Error::InvalidAdmin);
+            }
 
-                    let vote_count = self
-                        .vote_counts
-                        .get(&(poll_id, choice_id))
-                        .unwrap_or_default();
+            // Change the admin.
+            self.admin = new_admin;
 
-                    ChoiceReport {
-                        id: choice_id,
-                        description: choice.description,
-                        vote_count,
-                    }
-                })
-                .collect();
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::ChangeAdmin, &new_admin);
 
-            let report = PollReport {
-                id: poll_id,
-                description: poll.description,
-                status: poll.status,
-                owner: poll.owner,
-                choices,
-                winner: poll.winner,
-            };
+            Ok(())
+        }
 
-            Ok(report)
+        #[ink(message, selector = 0x660C375A)]
+        /// Returns true if an account is the admin of the contract.
+        pub fn is_admin(&self, account: AccountId) -> bool {
+            account == self.admin
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use ink::env::test::EmittedEvent;
+        #[ink(message, selector = 0x2107D2A7)]
+        /// Adds an account to the denylist of admins, e.g. a known burn address.
+        pub fn add_denied_admin(&mut self, account: AccountId) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
 
-        /// Imports all the definitions from the outer scope so we can use them here.
-        use super::*;
+            // Insert the account into storage.
+            self.denied_admins.insert(account, &());
 
-        type Event = <VotingContract as ::ink::reflect::ContractEventBase>::Type;
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::AddDeniedAdmin, &account);
 
-        fn assert_poll_created_event(
-            event: &EmittedEvent,
-            expected_poll_id: PollId,
-            expected_description: &str,
-            expected_owner: AccountId,
-        ) {
-            let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
-                .expect("encountered invalid contract event data buffer");
+            Ok(())
+        }
 
-            if let Event::PollCreated(PollCreated {
-                poll_id,
-                description,
-                owner,
-            }) = decoded_event
-            {
-                assert_eq!(poll_id, expected_poll_id);
-                assert_eq!(description, expected_description);
-                assert_eq!(owner, expected_owner);
-            } else {
-                panic!("encountered unexpected contract event kind: expected `PollCreated`")
+        #[ink(message, selector = 0xAC0F69D0)]
+        /// Removes an account from the denylist of admins.
+        pub fn remove_denied_admin(&mut self, account: AccountId) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
             }
+
+            // Remove the account from storage.
+            self.denied_admins.remove(account);
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::RemoveDeniedAdmin, &account);
+
+            Ok(())
         }
 
-        fn assert_add_choice_event(
-            event: &EmittedEvent,
-            expected_poll_id: PollId,
-            expected_choice_id: ChoiceId,
-            expected_description: &str,
-        ) {
-            let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
-                .expect("encountered invalid contract event data buffer");
+        #[ink(message, selector = 0x3E14CB60)]
+        /// Returns true if an account is in the denylist of admins.
+        pub fn is_admin_denied(&self, account: AccountId) -> bool {
+            self.denied_admins.contains(account)
+        }
 
-            if let Event::ChoiceAdded(ChoiceAdded {
-                poll_id,
-                choice_id,
-                description,
-            }) = decoded_event
-            {
-                assert_eq!(poll_id, expected_poll_id);
-                assert_eq!(choice_id, expected_choice_id);
-                assert_eq!(description, expected_description);
-            } else {
-                panic!("encountered unexpected contract event kind: expected `ChoiceAdded`")
+        #[ink(message, selector = 0x5CDB0523)]
+        /// Sets the minimum and maximum duration (in milliseconds) allowed for new polls.
+        pub fn set_duration_bounds(
+            &mut self,
+            min_duration: Timestamp,
+            max_duration: Timestamp,
+        ) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Check if the bounds are valid.
+            if min_duration == 0 || min_duration > max_duration {
+                return Err(Error::InvalidPollDuration);
             }
+
+            // Change the bounds.
+            self.min_duration = min_duration;
+            self.max_duration = max_duration;
+
+            // Record the action in the admin log.
+            self.log_admin_action(
+                AdminAction::SetDurationBounds,
+                &(min_duration, max_duration),
+            );
+
+            Ok(())
         }
 
-        fn assert_start_poll_event(event: &EmittedEvent, expected_poll_id: PollId) {
-            let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
-                .expect("encountered invalid contract event data buffer");
+        #[ink(message, selector = 0xF7DD9A44)]
+        /// Get the minimum and maximum duration (in milliseconds) allowed for new polls.
+        pub fn get_duration_bounds(&self) -> (Timestamp, Timestamp) {
+            (self.min_duration, self.max_duration)
+        }
 
-            if let Event::PollStarted(PollStarted { poll_id }) = decoded_event {
-                assert_eq!(poll_id, expected_poll_id);
-            } else {
-                panic!("encountered unexpected contract event kind: expected `PollStarted`")
+        #[ink(message, selector = 0xD5C88E3E)]
+        /// Sets the configuration inherited by new polls (their duration is bounded by the
+        /// duration bounds, see `set_duration_bounds`).
+        pub fn set_default_poll_config(&mut self, config: PollConfig) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Check if the strategy fills at least one seat.
+            if config.tally_strategy == (TallyStrategy::Stv { seats: 0 }) {
+                return Err(Error::InvalidSeatCount);
             }
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetDefaultPollConfig, &config);
+
+            // Change the default configuration.
+            self.default_poll_config = config;
+
+            Ok(())
         }
 
-        fn assert_end_poll_event(
-            event: &EmittedEvent,
-            expected_poll_id: PollId,
-            expected_winner: Option<ChoiceId>,
-        ) {
-            let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
-                .expect("encountered invalid contract event data buffer");
+        #[ink(message, selector = 0xED98B5F2)]
+        /// Get the configuration inherited by new polls.
+        pub fn get_default_poll_config(&self) -> PollConfig {
+            self.default_poll_config.clone()
+        }
 
-            if let Event::PollEnded(PollEnded { poll_id, winner }) = decoded_event {
-                assert_eq!(poll_id, expected_poll_id);
-                assert_eq!(winner, expected_winner);
-            } else {
-                panic!("encountered unexpected contract event kind: expected `PollEnded`")
+        #[ink(message, selector = 0x87286642)]
+        /// Sets the amount paid to the account that finalizes an expired poll.
+        pub fn set_finalization_bounty(&mut self, bounty: Balance) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
             }
+
+            // Change the bounty.
+            self.finalization_bounty = bounty;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetFinalizationBounty, &bounty);
+
+            Ok(())
         }
 
-        #[ink::test]
-        /// Tests that `default` constructor sets `admin` properly.
-        fn test_contract_admin() {
-            let voting_contract = VotingContract::default();
+        #[ink(message, selector = 0xFE430A3A)]
+        /// Sets the deposit that must be transferred to the contract when creating a poll.
+        pub fn set_poll_deposit(&mut self, deposit: Balance) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Change the deposit.
+            self.poll_deposit = deposit;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetPollDeposit, &deposit);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xCFA9B7C4)]
+        /// Get the deposit that must be transferred to the contract when creating a poll.
+        pub fn get_poll_deposit(&self) -> Balance {
+            self.poll_deposit
+        }
+
+        #[ink(message, selector = 0x2057EE72)]
+        /// Sets the deposit required to challenge the result of a poll.
+        pub fn set_challenge_deposit(&mut self, deposit: Balance) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Change the deposit.
+            self.challenge_deposit = deposit;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetChallengeDeposit, &deposit);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x5E54B19D)]
+        /// Get the deposit required to challenge the result of a poll.
+        pub fn get_challenge_deposit(&self) -> Balance {
+            self.challenge_deposit
+        }
+
+        #[ink(message, selector = 0xBE172E69)]
+        /// Sets the protocol fee configuration (the fee can be at most `MAX_FEE_BPS`).
+        pub fn set_fee_config(&mut self, fee_config: FeeConfig) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Check if the fee is within the allowed range.
+            if fee_config.bps > MAX_FEE_BPS {
+                return Err(Error::FeeTooHigh);
+            }
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetFeeConfig, &fee_config);
+
+            // Emit the event.
+            self.env().emit_event(FeeConfigUpdated {
+                schema_version: EVENT_SCHEMA_VERSION,
+                bps: fee_config.bps,
+                recipient: fee_config.recipient,
+            });
+
+            // Change the fee configuration.
+            self.fee_config = fee_config;
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x12136F86)]
+        /// Get the protocol fee configuration.
+        pub fn get_fee_config(&self) -> FeeConfig {
+            self.fee_config.clone()
+        }
+
+        #[ink(message, selector = 0xE08DC145)]
+        /// Sets the PSP22 token used for the deposits, fees and reward pools of new polls (the
+        /// native currency is used if not present). Existing polls keep their currency.
+        pub fn set_payment_token(&mut self, token: Option<AccountId>) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Change the payment token.
+            self.payment_token = token;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetPaymentToken, &token);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xCEF6E268)]
+        /// Get the PSP22 token used for the payments of new polls.
+        pub fn get_payment_token(&self) -> Option<AccountId> {
+            self.payment_token
+        }
+
+        #[ink(message, selector = 0xD99F1162)]
+        /// Get the PSP22 token used for the payments of a poll (not present if the poll is paid in
+        /// the native currency).
+        pub fn get_poll_token(&self, poll_id: PollId) -> Option<AccountId> {
+            self.poll_tokens.get(poll_id)
+        }
+
+        #[ink(message, selector = 0x3308F3F9)]
+        /// Sets (or removes) the contract to which the results of ended polls are pushed.
+        pub fn set_results_publisher(&mut self, publisher: Option<AccountId>) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Change the results publisher.
+            self.results_publisher = publisher;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetResultsPublisher, &publisher);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xE5A021D1)]
+        /// Get the contract to which the results of ended polls are pushed.
+        pub fn get_results_publisher(&self) -> Option<AccountId> {
+            self.results_publisher
+        }
+
+        #[ink(message, selector = 0x97052CC6)]
+        /// Sets (or removes) the contract to which incremental updates of the polls (polls
+        /// created and ballots cast) are pushed.
+        pub fn set_stats_contract(&mut self, stats: Option<AccountId>) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Change the stats contract.
+            self.stats_contract = stats;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetStatsContract, &stats);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x89964C8D)]
+        /// Get the contract to which incremental updates of the polls are pushed.
+        pub fn get_stats_contract(&self) -> Option<AccountId> {
+            self.stats_contract
+        }
+
+        #[ink(message, selector = 0xE3222DB2)]
+        /// Sets (or removes) the contract which verifies the membership proofs of anonymous votes.
+        pub fn set_membership_verifier(
+            &mut self,
+            verifier: Option<AccountId>,
+        ) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Change the membership verifier.
+            self.membership_verifier = verifier;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetMembershipVerifier, &verifier);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xA65EC48C)]
+        /// Get the contract which verifies the membership proofs of anonymous votes.
+        pub fn get_membership_verifier(&self) -> Option<AccountId> {
+            self.membership_verifier
+        }
+
+        #[ink(message, selector = 0xFFB2D3A8)]
+        /// Sets (or removes) the contract used to resolve accounts to display names in reports.
+        pub fn set_identity_registry(&mut self, registry: Option<AccountId>) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Change the identity registry.
+            self.identity_registry = registry;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetIdentityRegistry, &registry);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x0FC135CD)]
+        /// Get the contract used to resolve accounts to display names in reports.
+        pub fn get_identity_registry(&self) -> Option<AccountId> {
+            self.identity_registry
+        }
+
+        #[ink(message, selector = 0x6F01B374)]
+        /// Sets the human-readable name and link of the instance, shown by indexers listing
+        /// several deployments of the contract.
+        pub fn set_instance_metadata(&mut self, name: String, uri: String) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Check the length of the name and of the URI.
+            if name.len() > MAX_INSTANCE_METADATA_LENGTH as usize
+                || uri.len() > MAX_INSTANCE_METADATA_LENGTH as usize
+            {
+                return Err(Error::InstanceMetadataTooLong);
+            }
+
+            // Change the metadata of the instance.
+            let metadata = InstanceMetadata { name, uri };
+            self.instance_metadata.set(&metadata);
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetInstanceMetadata, &metadata);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x91D878ED)]
+        /// Get the human-readable name and link of the instance (not present if not set).
+        pub fn get_instance_metadata(&self) -> Option<InstanceMetadata> {
+            self.instance_metadata.get()
+        }
+
+        #[ink(message, selector = 0x7DAB6420)]
+        /// Get the discovery feed of the running polls: the most recently started polls (most
+        /// recent first) and the polls ending the soonest (soonest first, with their end).
+        pub fn get_active_feed(&self) -> ActiveFeed {
+            let mut feed = self.active_feed.get().unwrap_or_default();
+
+            // Skip the polls whose voting window has passed but which have not been ended yet.
+            let now = self.env().block_timestamp();
+            feed.ending_soon.retain(|(_, end_at)| *end_at > now);
+
+            feed
+        }
+
+        #[ink(message, selector = 0x1061D765)]
+        /// Sets (or removes) the contract used to resolve accounts to member indexes for
+        /// `vote_as_member`.
+        pub fn set_member_registry(&mut self, registry: Option<AccountId>) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Change the member registry.
+            self.member_registry = registry;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetMemberRegistry, &registry);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x37A05821)]
+        /// Get the contract used to resolve accounts to member indexes.
+        pub fn get_member_registry(&self) -> Option<AccountId> {
+            self.member_registry
+        }
+
+        #[ink(message, selector = 0x38E3D055)]
+        /// Sets (or removes) the schedule boosting the votes of long-term members. Votes weighted
+        /// by the weight source are boosted; uploaded voter weights and voting keys are not.
+        pub fn set_tenure_schedule(
+            &mut self,
+            schedule: Option<TenureSchedule>,
+        ) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetTenureSchedule, &schedule);
+
+            // Change the tenure schedule.
+            self.tenure_schedule = schedule;
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xE5926802)]
+        /// Get the schedule boosting the votes of long-term members.
+        pub fn get_tenure_schedule(&self) -> Option<TenureSchedule> {
+            self.tenure_schedule.clone()
+        }
+
+        #[ink(message, selector = 0xB801517A)]
+        /// Sets the storage deposit rates of the chain used by `estimate_poll_deposit`. The
+        /// contracts API does not expose these rates, so they must match the runtime configuration.
+        pub fn set_storage_deposit_rates(
+            &mut self,
+            per_byte: Balance,
+            per_item: Balance,
+        ) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Change the rates.
+            self.deposit_per_byte = per_byte;
+            self.deposit_per_item = per_item;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetStorageDepositRates, &(per_byte, per_item));
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xE72A70B9)]
+        /// Get the storage deposit rates (per byte and per item) used by `estimate_poll_deposit`.
+        pub fn get_storage_deposit_rates(&self) -> (Balance, Balance) {
+            (self.deposit_per_byte, self.deposit_per_item)
+        }
+
+        #[ink(message, selector = 0x4B6A804F)]
+        /// Estimates the storage deposit of creating a poll and adding its choices, given the
+        /// length (in bytes) of the description, the number of choices and the average length of
+        /// the choice descriptions.
+        pub fn estimate_poll_deposit(
+            &self,
+            description_len: u32,
+            num_choices: u32,
+            avg_choice_len: u32,
+        ) -> Balance {
+            self.deposit_estimate(description_len, num_choices, avg_choice_len)
+        }
+
+        #[ink(message, selector = 0x5D1EDDD9)]
+        /// Grants the moderator role to an account.
+        pub fn add_moderator(&mut self, account: AccountId) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Insert the moderator into storage.
+            self.moderators.insert(account, &());
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::AddModerator, &account);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x401FBD79)]
+        /// Revokes the moderator role from an account.
+        pub fn remove_moderator(&mut self, account: AccountId) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Remove the moderator from storage.
+            self.moderators.remove(account);
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::RemoveModerator, &account);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x62122129)]
+        /// Returns true if the account has the moderator role.
+        pub fn is_moderator(&self, account: AccountId) -> bool {
+            self.moderators.contains(account)
+        }
+
+        #[ink(message, selector = 0x09FC81F9)]
+        /// Grants the resolver role to an account, allowing it to resolve challenges.
+        pub fn add_resolver(&mut self, account: AccountId) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Insert the resolver into storage.
+            self.resolvers.insert(account, &());
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::AddResolver, &account);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xA502D9AF)]
+        /// Revokes the resolver role from an account.
+        pub fn remove_resolver(&mut self, account: AccountId) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Remove the resolver from storage.
+            self.resolvers.remove(account);
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::RemoveResolver, &account);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x3E4F54DD)]
+        /// Returns true if the account has the resolver role.
+        pub fn is_resolver(&self, account: AccountId) -> bool {
+            self.resolvers.contains(account)
+        }
+
+        #[ink(message, selector = 0x8D754A9B)]
+        /// Enables or disables restricting poll creation to the creator allow-list.
+        pub fn set_creator_allow_list(&mut self, enabled: bool) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Change the creator allow-list mode.
+            self.creator_allow_list_enabled = enabled;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetCreatorAllowList, &enabled);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xF639A884)]
+        /// Returns true if poll creation is restricted to the creator allow-list.
+        pub fn is_creator_allow_list_enabled(&self) -> bool {
+            self.creator_allow_list_enabled
+        }
+
+        #[ink(message, selector = 0x5D5F4C3C)]
+        /// Adds an account to the creator allow-list.
+        pub fn add_creator(&mut self, account: AccountId) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Insert the creator into storage.
+            self.creators.insert(account, &());
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::AddCreator, &account);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x14BD33F9)]
+        /// Removes an account from the creator allow-list.
+        pub fn remove_creator(&mut self, account: AccountId) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Remove the creator from storage.
+            self.creators.remove(account);
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::RemoveCreator, &account);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xB5800E90)]
+        /// Returns true if the account is on the creator allow-list.
+        pub fn is_creator(&self, account: AccountId) -> bool {
+            self.creators.contains(account)
+        }
+
+        #[ink(message, selector = 0xF592546D)]
+        /// Sets the hash of the terms that voters must accept (see `accept_terms`) before voting,
+        /// or disables the requirement. Setting new terms increments their version, so that every
+        /// voter has to accept them again. Anonymous votes are not linked to an account, so they
+        /// are not subject to the requirement.
+        pub fn set_terms(&mut self, terms_hash: Option<[u8; 32]>) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Change the terms and increment their version.
+            if terms_hash.is_some() {
+                self.terms_version += 1;
+            }
+
+            self.terms_hash = terms_hash;
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::SetTerms, &terms_hash);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xC5D5331B)]
+        /// Get the hash and version of the terms that voters must accept before voting (not
+        /// present if voting does not require accepting terms).
+        pub fn get_terms(&self) -> Option<([u8; 32], u32)> {
+            self.terms_hash
+                .map(|terms_hash| (terms_hash, self.terms_version))
+        }
+
+        #[ink(message, selector = 0xA7F33294)]
+        /// Accepts the current terms of the contract, identified by their hash.
+        pub fn accept_terms(&mut self, terms_hash: [u8; 32]) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Check if the hash is the one of the current terms.
+            match self.terms_hash {
+                None => return Err(Error::NoTermsToAccept),
+                Some(current_hash) if current_hash != terms_hash => {
+                    return Err(Error::TermsHashMismatch)
+                }
+                Some(_) => {}
+            }
+
+            // Record the acceptance of the current version of the terms.
+            self.terms_acceptances.insert(caller, &self.terms_version);
+
+            self.env().emit_event(TermsAccepted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account: caller,
+                terms_hash,
+                version: self.terms_version,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xB2C6C6A6)]
+        /// Returns true if the account has accepted the current terms of the contract.
+        pub fn has_accepted(&self, account: AccountId) -> bool {
+            self.terms_hash.is_some()
+                && self.terms_acceptances.get(account) == Some(self.terms_version)
+        }
+
+        #[ink(message, selector = 0x326C32E5)]
+        /// Adds the hash of a normalized term (see `hash_term`) to the description denylist.
+        pub fn add_denied_term(&mut self, term_hash: [u8; 32]) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Insert the term into storage.
+            if !self.denied_terms.contains(term_hash) {
+                self.denied_terms.insert(term_hash, &());
+                self.denied_term_count = self.denied_term_count.saturating_add(1);
+            }
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::AddDeniedTerm, &term_hash);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x1C4859AF)]
+        /// Removes the hash of a normalized term from the description denylist.
+        pub fn remove_denied_term(&mut self, term_hash: [u8; 32]) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if self.env().caller() != self.admin {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            // Remove the term from storage.
+            if self.denied_terms.contains(term_hash) {
+                self.denied_terms.remove(term_hash);
+                self.denied_term_count = self.denied_term_count.saturating_sub(1);
+            }
+
+            // Record the action in the admin log.
+            self.log_admin_action(AdminAction::RemoveDeniedTerm, &term_hash);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xFBC1FA68)]
+        /// Returns true if the hash of a normalized term is in the description denylist.
+        pub fn is_term_denied(&self, term_hash: [u8; 32]) -> bool {
+            self.denied_terms.contains(term_hash)
+        }
+
+        #[ink(message, selector = 0xB86F9A64)]
+        /// Get the hash under which a term is stored in the description denylist: the Blake2x256
+        /// hash of the term in lowercase.
+        pub fn hash_term(&self, term: String) -> [u8; 32] {
+            self.env()
+                .hash_bytes::<ink::env::hash::Blake2x256>(term.to_lowercase().as_bytes())
+        }
+
+        #[ink(message, payable, selector = 0x40867DAC)]
+        /// Creates a new poll. The transferred value is kept as the deposit of the poll and must be
+        /// at least the configured poll deposit.
+        pub fn create_poll(
+            &mut self,
+            poll_id: PollId,
+            description: String,
+            start_at: Timestamp,
+            end_at: Timestamp,
+        ) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Check if poll creation is paused.
+            if self.circuit_breakers.pause_poll_creation {
+                return Err(Error::PollCreationIsPaused);
+            }
+
+            // Check if the caller is allowed to create polls.
+            if self.creator_allow_list_enabled && !self.creators.contains(self.env().caller()) {
+                return Err(Error::CallerIsNotAllowedToCreatePoll);
+            }
+
+            // Check if the poll already exists (or existed and has been deleted).
+            if self.polls.get(poll_id).is_some() || self.deleted_polls.contains(poll_id) {
+                return Err(Error::PollWithIdAlreadyExists);
+            }
+
+            // Check the length of the description and if it contains a denied term.
+            let stored_description = self.check_description(&description)?;
+
+            // Check if the duration of the poll is within the configured bounds.
+            let duration = end_at
+                .checked_sub(start_at)
+                .ok_or(Error::InvalidPollDuration)?;
+
+            if duration < self.min_duration || duration > self.max_duration {
+                return Err(Error::InvalidPollDuration);
+            }
+
+            // Get the deposit of the poll (the configured deposit is pulled for polls paid in a
+            // PSP22 token).
+            let deposit = match self.payment_token {
+                Some(_) if self.env().transferred_value() > 0 => {
+                    return Err(Error::NativeValueNotAccepted)
+                }
+                Some(_) => self.poll_deposit,
+                None => self.env().transferred_value(),
+            };
+
+            // Check if the deposit covers the poll deposit.
+            if deposit < self.poll_deposit {
+                return Err(Error::InsufficientDeposit);
+            }
+
+            // Create the poll.
+            let poll = Poll {
+                status: PollStatus::NotStarted,
+                owner: self.env().caller(),
+                winner: None,
+                start_at,
+                end_at,
+            };
+
+            // Insert the poll and its description into the storage.
+            self.polls.insert(poll_id, &poll);
+            self.poll_descriptions.insert(poll_id, &stored_description);
+
+            // Increment the number of polls.
+            self.poll_count = self.poll_count.saturating_add(1);
+
+            // List the poll among the polls of its owner.
+            let mut owned_polls = self.owned_polls.get(poll.owner).unwrap_or_default();
+            owned_polls.push(poll_id);
+            self.owned_polls.insert(poll.owner, &owned_polls);
+
+            // Insert the storage version of the poll into the storage.
+            self.poll_versions.insert(poll_id, &POLL_STORAGE_VERSION);
+
+            // Insert the payment token of the poll into the storage.
+            if let Some(token) = self.payment_token {
+                self.poll_tokens.insert(poll_id, &token);
+            }
+
+            // Insert the deposit of the poll (net of the protocol fee) into the storage.
+            let deposit = self.collect_payment(poll_id, deposit)?;
+
+            if deposit > 0 {
+                self.deposits.insert(poll_id, &deposit);
+            }
+
+            // Apply the default configuration of new polls.
+            self.apply_poll_config(poll_id, &self.default_poll_config.clone());
+
+            // Push the poll to the stats contract.
+            self.push_stats(
+                poll_id,
+                ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                    "PollStats::on_poll_created"
+                )))
+                .push_arg(poll_id)
+                .push_arg(poll.owner),
+            );
+
+            // Emit the event.
+            self.env().emit_event(PollCreated {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                description,
+                owner: self.env().caller(),
+            });
+            self.emit_lifecycle_event(poll_id, PollStage::Created);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x696D3426)]
+        /// Deletes a poll that has not started and has no choices (e.g. created with a wrong id
+        /// or description), returning its deposit and keeper bounty to its owner. The storage of
+        /// the poll is cleared, except for entries keyed by account (e.g. delegations), which
+        /// can't be enumerated. The id of a deleted poll can't be reused, so these are never
+        /// read again.
+        pub fn delete_poll(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Prevent reentrant calls while value is transferred.
+            let _guard = ReentrancyGuard::enter()?;
+
+            // Check if the poll can still be changed.
+            let poll = self.require_mutable(poll_id, Error::OnlyOwnerCanDeletePoll)?;
+
+            // Check if the poll has choices, registered voters or funds paid by other accounts.
+            if self.choice_count.get(poll_id).unwrap_or_default() > 0
+                || self.registered_counts.get(poll_id).unwrap_or_default() > 0
+                || self.fee_pools.get(poll_id).unwrap_or_default() > 0
+            {
+                return Err(Error::PollIsInUse);
+            }
+
+            // Return the deposit and the keeper bounty of the poll to its owner.
+            let refund = self
+                .deposits
+                .take(poll_id)
+                .unwrap_or_default()
+                .saturating_add(self.keeper_bounties.take(poll_id).unwrap_or_default());
+
+            if refund > 0 {
+                self.pay(poll_id, poll.owner, refund)?;
+            }
+
+            // Remove the poll and its configuration from the storage.
+            self.polls.remove(poll_id);
+            self.poll_descriptions.remove(poll_id);
+            self.poll_versions.remove(poll_id);
+            self.choice_count.remove(poll_id);
+            self.next_choice_ids.remove(poll_id);
+            self.fee_pools.remove(poll_id);
+            self.poll_tokens.remove(poll_id);
+            self.poll_visibility.remove(poll_id);
+            self.private_polls.remove(poll_id);
+            self.prediction_polls.remove(poll_id);
+            self.shuffled_polls.remove(poll_id);
+            self.weighted_polls.remove(poll_id);
+            self.hidden_results.remove(poll_id);
+            self.abstaining_owners.remove(poll_id);
+            self.recusal_counts.remove(poll_id);
+            self.changed_ballots.remove(poll_id);
+            self.public_ballots.remove(poll_id);
+            self.tally_strategies.remove(poll_id);
+            self.challenge_periods.remove(poll_id);
+            self.poll_observers.remove(poll_id);
+            self.verifier_keys.remove(poll_id);
+            self.ballot_encryption.remove(poll_id);
+            self.registration_deposits.remove(poll_id);
+            self.quorum_weight_bps.remove(poll_id);
+            self.eligible_weights.remove(poll_id);
+            self.uploaded_weights.remove(poll_id);
+            self.voter_list_uploads.remove(poll_id);
+            self.milestone_configs.remove(poll_id);
+            self.finalizers.remove(poll_id);
+            self.choice_capacities.remove(poll_id);
+
+            for question_id in self.question_ids.take(poll_id).unwrap_or_default() {
+                self.questions.remove((poll_id, question_id));
+                self.numeric_ranges.remove((poll_id, question_id));
+            }
+
+            // Remove the poll from the series of its template.
+            if let Some(template_id) = self.poll_templates.take(poll_id) {
+                let mut series = self.series.get(template_id).unwrap_or_default();
+                series.retain(|id| *id != poll_id);
+                self.series.insert(template_id, &series);
+            }
+
+            // Remove the poll from the polls of its owner.
+            let mut owned_polls = self.owned_polls.get(poll.owner).unwrap_or_default();
+            owned_polls.retain(|id| *id != poll_id);
+            self.owned_polls.insert(poll.owner, &owned_polls);
+
+            // Decrement the number of polls.
+            self.poll_count = self.poll_count.saturating_sub(1);
+
+            // Prevent the id of the poll from being reused.
+            self.deleted_polls.insert(poll_id, &());
+
+            // Emit the event.
+            self.env().emit_event(PollDeleted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                owner: poll.owner,
+            });
+            self.emit_lifecycle_event(poll_id, PollStage::Deleted);
+
+            // The namespace of the poll is removed once its last events have been emitted.
+            self.topic_namespaces.remove(poll_id);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x2A00FD75)]
+        /// Archives an ended poll whose result is final: its description, choices and counts
+        /// are pruned from the storage and a compact `ResultRecord` of its outcome is kept
+        /// instead (see `get_result_record`). The ballots and stakes of the poll are kept, so
+        /// winnings can still be claimed. Can only be called by the owner of the poll.
+        pub fn archive_poll(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll.
+            if self.env().caller() != poll.owner {
+                return Err(Error::OnlyOwnerCanArchivePoll);
+            }
+
+            // Check if the poll has already been archived.
+            if self.results.contains(poll_id) {
+                return Err(Error::PollIsArchived);
+            }
+
+            // Check if the result of the poll is final.
+            if !matches!(
+                self.get_result_status(poll_id)?,
+                ResultStatus::Final | ResultStatus::Voided
+            ) {
+                return Err(Error::ResultIsNotFinal);
+            }
+
+            // Record the result of the poll.
+            let choice_id_list = self.choice_id_list(poll_id);
+            let per_choice_totals = choice_id_list
+                .iter()
+                .map(|choice_id| {
+                    (
+                        *choice_id,
+                        self.vote_counts
+                            .get((poll_id, *choice_id))
+                            .unwrap_or_default(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            self.results.insert(
+                poll_id,
+                &ResultRecord {
+                    winner: poll.winner,
+                    total_votes: self.total_votes(poll_id),
+                    per_choice_totals_hash: self
+                        .env()
+                        .hash_encoded::<ink::env::hash::Blake2x256, _>(&per_choice_totals),
+                    ended_at: self.end_blocks.get(poll_id).unwrap_or_default(),
+                },
+            );
+
+            // Prune the description, the choices and the counts of the poll.
+            for (position, choice_id) in choice_id_list.into_iter().enumerate() {
+                self.choices.remove((poll_id, choice_id));
+                self.choice_slots.remove((poll_id, position as u32));
+                self.set_choice_bit(poll_id, choice_id, false);
+                self.vote_counts.remove((poll_id, choice_id));
+                self.voter_counts.remove((poll_id, choice_id));
+                self.choice_ballots.remove((poll_id, choice_id));
+                self.choice_questions.remove((poll_id, choice_id));
+            }
+
+            for question_id in self.question_ids.take(poll_id).unwrap_or_default() {
+                self.questions.remove((poll_id, question_id));
+                self.numeric_ranges.remove((poll_id, question_id));
+            }
+
+            self.choice_count.remove(poll_id);
+            self.poll_descriptions.remove(poll_id);
+            self.vote_timelines.remove(poll_id);
+
+            // Emit the event.
+            self.emit_lifecycle_event(poll_id, PollStage::Archived);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xAB24D061)]
+        /// Get the compact result record of an archived poll (not present if the poll has not
+        /// been archived).
+        pub fn get_result_record(&self, poll_id: PollId) -> Option<ResultRecord> {
+            self.results.get(poll_id)
+        }
+
+        #[ink(message, selector = 0xBF0CE3ED)]
+        /// Creates a template from which the caller can create recurring polls with the same
+        /// choices and configuration (see `instantiate_from_template`) and returns its id.
+        pub fn create_poll_template(
+            &mut self,
+            description: String,
+            choices: Vec<String>,
+            duration: Timestamp,
+            tally_strategy: TallyStrategy,
+            challenge_period: Timestamp,
+        ) -> Result<TemplateId, Error> {
+            let caller = self.env().caller();
+
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Check if the caller is allowed to create polls.
+            if self.creator_allow_list_enabled && !self.creators.contains(caller) {
+                return Err(Error::CallerIsNotAllowedToCreatePoll);
+            }
+
+            // Check if there are too many choices.
+            if choices.len() > MAX_CHOICE_BATCH_SIZE as usize {
+                return Err(Error::BatchTooLarge);
+            }
+
+            // Check the length of the descriptions and if they contain a denied term.
+            let description = self.check_description(&description)?;
+            let choices = choices
+                .iter()
+                .map(|choice| self.check_description(choice))
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            // Check if the duration of the polls is within the configured bounds.
+            if duration < self.min_duration || duration > self.max_duration {
+                return Err(Error::InvalidPollDuration);
+            }
+
+            // Check if the strategy fills at least one seat.
+            if tally_strategy == (TallyStrategy::Stv { seats: 0 }) {
+                return Err(Error::InvalidSeatCount);
+            }
+
+            // Insert the template into storage.
+            let template_id = self
+                .template_count
+                .checked_add(1)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            self.templates.insert(
+                template_id,
+                &PollTemplate {
+                    owner: caller,
+                    description,
+                    choices,
+                    duration,
+                    tally_strategy,
+                    challenge_period,
+                },
+            );
+            self.template_count = template_id;
+
+            // Emit the event.
+            self.env().emit_event(PollTemplateCreated {
+                schema_version: EVENT_SCHEMA_VERSION,
+                template_id,
+                owner: caller,
+            });
+
+            Ok(template_id)
+        }
+
+        #[ink(message, payable, selector = 0x738A96BE)]
+        /// Creates a poll from a template of the caller, starting at `start_at` and lasting the
+        /// duration of the template. The choices of the template get the ids `1, 2, ...` and the
+        /// poll deposit is paid as with `create_poll`.
+        pub fn instantiate_from_template(
+            &mut self,
+            template_id: TemplateId,
+            poll_id: PollId,
+            start_at: Timestamp,
+        ) -> Result<(), Error> {
+            // Get the template and return error if it does not exist.
+            let template = self
+                .templates
+                .get(template_id)
+                .ok_or(Error::TemplateWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the template.
+            if self.env().caller() != template.owner {
+                return Err(Error::OnlyOwnerCanInstantiateTemplate);
+            }
+
+            // Create the poll with the choices and configuration of the template.
+            let end_at = start_at
+                .checked_add(template.duration)
+                .ok_or(Error::InvalidPollDuration)?;
+
+            self.create_poll(poll_id, template.description.into(), start_at, end_at)?;
+
+            for (choice_id, description) in (1..).zip(template.choices) {
+                self.add_choice(poll_id, choice_id, description.into())?;
+            }
+
+            if template.tally_strategy != TallyStrategy::default() {
+                self.tally_strategies
+                    .insert(poll_id, &template.tally_strategy);
+            }
+
+            if template.challenge_period > 0 {
+                self.challenge_periods
+                    .insert(poll_id, &template.challenge_period);
+            }
+
+            // Add the poll to the series of the template.
+            let mut series = self.series.get(template_id).unwrap_or_default();
+            series.push(poll_id);
+            self.series.insert(template_id, &series);
+            self.poll_templates.insert(poll_id, &template_id);
+
+            // Emit the event.
+            self.env().emit_event(PollInstantiated {
+                schema_version: EVENT_SCHEMA_VERSION,
+                template_id,
+                poll_id,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x7729703A)]
+        /// Get a poll template.
+        pub fn get_poll_template(&self, template_id: TemplateId) -> Option<PollTemplate> {
+            self.templates.get(template_id)
+        }
+
+        #[ink(message, selector = 0xD8685597)]
+        /// Get the template from which a poll was created (not present for polls created with
+        /// `create_poll`).
+        pub fn get_template_of(&self, poll_id: PollId) -> Option<TemplateId> {
+            self.poll_templates.get(poll_id)
+        }
+
+        #[ink(message, selector = 0xBFA374E3)]
+        /// Get the summaries of the polls created from a template (in order of creation), showing
+        /// the historical results of the series.
+        pub fn get_series(&self, template_id: TemplateId) -> Vec<PollSummary> {
+            self.series
+                .get(template_id)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|poll_id| self.get_summary(poll_id).ok())
+                .collect()
+        }
+
+        #[ink(message, selector = 0xABBAFA71)]
+        /// Adds a choice to a poll.
+        pub fn add_choice(
+            &mut self,
+            poll_id: PollId,
+            choice_id: ChoiceId,
+            description: String,
+        ) -> Result<(), Error> {
+            // Check if the choices of the poll can be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanAddChoice)?;
+
+            // Check if the choice already exists.
+            if self.choices.contains((poll_id, choice_id)) {
+                return Err(Error::ChoiceWithIdAlreadyExists);
+            }
+
+            // Check the length of the description and if it contains a denied term.
+            let stored_description = self.check_description(&description)?;
+
+            // Create the choice.
+            let choice = Choice {
+                description: stored_description,
+            };
+
+            // Insert the choice into the storage.
+            self.choices.insert((poll_id, choice_id), &choice);
+            self.set_choice_bit(poll_id, choice_id, true);
+
+            // Add the choice to the list of choices for the poll.
+            self.push_choice_id(poll_id, choice_id);
+
+            // Move the next choice id past the added choice so that automatically assigned ids are
+            // always increasing and never collide with existing ones.
+            let next_choice_id = self.next_choice_ids.get(poll_id).unwrap_or_default();
+
+            if choice_id >= next_choice_id {
+                self.next_choice_ids
+                    .insert(poll_id, &choice_id.saturating_add(1));
+            }
+
+            // Emit the event.
+            self.env().emit_event(ChoiceAdded {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                choice_id,
+                description,
+            });
+            self.emit_lifecycle_event(poll_id, PollStage::ChoiceAdded);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x5BA400D1)]
+        /// Adds a batch of choices to a poll.
+        pub fn add_choices(
+            &mut self,
+            poll_id: PollId,
+            choices: Vec<(ChoiceId, String)>,
+        ) -> Result<(), Error> {
+            // Check if the batch is too large.
+            if choices.len() > MAX_CHOICE_BATCH_SIZE as usize {
+                return Err(Error::BatchTooLarge);
+            }
+
+            // Add the choices.
+            for (choice_id, description) in choices {
+                self.add_choice(poll_id, choice_id, description)?;
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xB4105837)]
+        /// Adds a question with its choices to a poll, which then accepts only ballots answering
+        /// its questions (see `vote_ballot`) instead of votes for a single choice. Choices added
+        /// with `add_choice` do not belong to any question.
+        pub fn add_question(
+            &mut self,
+            poll_id: PollId,
+            question_id: QuestionId,
+            description: String,
+            choices: Vec<(ChoiceId, String)>,
+        ) -> Result<(), Error> {
+            // Check if the questions of the poll can be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanAddQuestion)?;
+
+            // Check if the question already exists.
+            if self.questions.contains((poll_id, question_id)) {
+                return Err(Error::QuestionWithIdAlreadyExists);
+            }
+
+            // Check the length of the description and if it contains a denied term.
+            let description = self.check_description(&description)?;
+
+            // Add the choices of the question.
+            let choice_ids: Vec<ChoiceId> =
+                choices.iter().map(|(choice_id, _)| *choice_id).collect();
+            self.add_choices(poll_id, choices)?;
+
+            for choice_id in &choice_ids {
+                self.choice_questions
+                    .insert((poll_id, *choice_id), &question_id);
+            }
+
+            // Insert the question into the storage.
+            self.push_question(
+                poll_id,
+                question_id,
+                &Question {
+                    description,
+                    choice_ids,
+                },
+            );
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x5483F79D)]
+        /// Adds a numeric question to a poll, answered with a value between `min` and `max`
+        /// (included) instead of a choice. The report of the poll exposes the count, sum, mean
+        /// and median of the answers (and their weighted median for polls with the
+        /// `WeightedMedian` tally strategy).
+        pub fn add_numeric_question(
+            &mut self,
+            poll_id: PollId,
+            question_id: QuestionId,
+            description: String,
+            min: u64,
+            max: u64,
+        ) -> Result<(), Error> {
+            // Check if the questions of the poll can be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanAddQuestion)?;
+
+            // Check if the question already exists.
+            if self.questions.contains((poll_id, question_id)) {
+                return Err(Error::QuestionWithIdAlreadyExists);
+            }
+
+            // Check the range of the question.
+            if min > max {
+                return Err(Error::InvalidNumericRange);
+            }
+
+            // Check the length of the description and if it contains a denied term.
+            let description = self.check_description(&description)?;
+
+            // Insert the question into the storage.
+            self.push_question(
+                poll_id,
+                question_id,
+                &Question {
+                    description,
+                    choice_ids: Vec::new(),
+                },
+            );
+            self.numeric_ranges
+                .insert((poll_id, question_id), &NumericRange { min, max });
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xFC95F87E)]
+        /// Updates the description of a choice of a poll.
+        pub fn update_choice(
+            &mut self,
+            poll_id: PollId,
+            choice_id: ChoiceId,
+            description: String,
+        ) -> Result<(), Error> {
+            // Check if the choices of the poll can be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanEditChoice)?;
+
+            // Check if the choice exists.
+            if !self.choices.contains((poll_id, choice_id)) {
+                return Err(Error::ChoiceWithIdDoesNotExist);
+            }
+
+            // Check the length of the description and if it contains a denied term.
+            let stored_description = self.check_description(&description)?;
+
+            // Insert the updated choice into the storage.
+            self.choices.insert(
+                (poll_id, choice_id),
+                &Choice {
+                    description: stored_description,
+                },
+            );
+
+            // Emit the event.
+            self.env().emit_event(ChoiceUpdated {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                choice_id,
+                description,
+            });
+            self.emit_lifecycle_event(poll_id, PollStage::ChoiceUpdated);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x73E60CA6)]
+        /// Removes a choice from a poll.
+        pub fn remove_choice(&mut self, poll_id: PollId, choice_id: ChoiceId) -> Result<(), Error> {
+            // Check if the choices of the poll can be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanEditChoice)?;
+
+            // Remove the choice from the storage.
+            self.choices
+                .take((poll_id, choice_id))
+                .ok_or(Error::ChoiceWithIdDoesNotExist)?;
+            self.set_choice_bit(poll_id, choice_id, false);
+
+            // Remove the choice from the list of choices for the poll.
+            let mut choice_id_list = self.choice_id_list(poll_id);
+            choice_id_list.retain(|id| *id != choice_id);
+            self.set_choice_id_list(poll_id, &choice_id_list);
+
+            // Remove the choice from its question.
+            if let Some(question_id) = self.choice_questions.take((poll_id, choice_id)) {
+                if let Some(mut question) = self.questions.get((poll_id, question_id)) {
+                    question.choice_ids.retain(|id| *id != choice_id);
+                    self.questions.insert((poll_id, question_id), &question);
+                }
+            }
+
+            // Emit the event.
+            self.env().emit_event(ChoiceRemoved {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                choice_id,
+            });
+            self.emit_lifecycle_event(poll_id, PollStage::ChoiceRemoved);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x4AC35E21)]
+        /// Adds a choice to a poll with an automatically assigned id and returns the id.
+        pub fn auto_add_choice(
+            &mut self,
+            poll_id: PollId,
+            description: String,
+        ) -> Result<ChoiceId, Error> {
+            // Get the next choice id for the poll.
+            let choice_id = self.next_choice_ids.get(poll_id).unwrap_or_default();
+
+            // Check if all the choice ids have been used.
+            if self.choices.contains((poll_id, choice_id)) {
+                return Err(Error::ChoiceIdsExhausted);
+            }
+
+            // Add the choice.
+            self.add_choice(poll_id, choice_id, description)?;
+
+            Ok(choice_id)
+        }
+
+        #[ink(message, selector = 0x4F7428B6)]
+        /// Changes the order in which the choices of a poll are presented. The order must contain
+        /// every choice of the poll exactly once.
+        pub fn set_choice_order(
+            &mut self,
+            poll_id: PollId,
+            order: Vec<ChoiceId>,
+        ) -> Result<(), Error> {
+            // Check if the choices of the poll can be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanOrderChoices)?;
+
+            // Check if the order is a permutation of the choices of the poll.
+            let mut current = self.choice_id_list(poll_id);
+            let mut sorted_order = order.clone();
+
+            current.sort_unstable();
+            sorted_order.sort_unstable();
+
+            if current != sorted_order {
+                return Err(Error::InvalidChoiceOrder);
+            }
+
+            // Insert the new order of choice ids into the storage.
+            self.set_choice_id_list(poll_id, &order);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x3625FDC0)]
+        /// Enables or disables shuffling the choices of a poll per account in `get_report_for`.
+        pub fn set_choice_shuffle(&mut self, poll_id: PollId, enabled: bool) -> Result<(), Error> {
+            // Check if the choices of the poll can be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanOrderChoices)?;
+
+            // Change the shuffle mode of the poll.
+            if enabled {
+                self.shuffled_polls.insert(poll_id, &());
+            } else {
+                self.shuffled_polls.remove(poll_id);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x27C06999)]
+        /// Uploads a batch of `(account, weight)` pairs for a poll that has not started yet. Once
+        /// weights are uploaded, only the listed accounts can vote and their votes count with the
+        /// uploaded weight (a weight of 0 removes an account from the list).
+        pub fn set_voter_weights(
+            &mut self,
+            poll_id: PollId,
+            weights: Vec<(AccountId, u128)>,
+        ) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Check if the batch is too large.
+            if weights.len() > MAX_VOTER_WEIGHT_BATCH_SIZE as usize {
+                return Err(Error::BatchTooLarge);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll.
+            if self.env().caller() != poll.owner {
+                return Err(Error::OnlyOwnerCanSetVoterWeights);
+            }
+
+            // Check if the poll has started or ended.
+            poll.ensure_status(PollStatus::NotStarted)?;
+
+            // Check if the list of eligible voters has been sealed.
+            if self.is_voter_list_sealed(poll_id) {
+                return Err(Error::VoterListIsSealed);
+            }
+
+            // Insert the weights into the storage, keeping track of their total.
+            let mut uploaded_weight = self.uploaded_weights.get(poll_id).unwrap_or_default();
+
+            for (account, weight) in weights {
+                let previous_weight = self
+                    .voter_weights
+                    .get((poll_id, account))
+                    .unwrap_or_default();
+
+                if weight == 0 {
+                    self.voter_weights.remove((poll_id, account));
+                } else {
+                    self.voter_weights.insert((poll_id, account), &weight);
+                }
+
+                uploaded_weight = uploaded_weight
+                    .saturating_sub(previous_weight)
+                    .saturating_add(weight);
+            }
+
+            self.uploaded_weights.insert(poll_id, &uploaded_weight);
+
+            // Mark the poll as using the uploaded weights.
+            self.weighted_polls.insert(poll_id, &());
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xDDA6ED44)]
+        /// Get the weight with which the vote of an account on a poll is counted.
+        pub fn get_voter_weight(&self, poll_id: PollId, account: AccountId) -> u128 {
+            self.voting_weight(poll_id, account)
+        }
+
+        #[ink(message, selector = 0x4A2DF742)]
+        /// Get the voting power of an account on a poll without voting: the weight its vote would
+        /// be counted with under the weighting rules of the poll (voting key, uploaded weight, or
+        /// weight source boosted by the tenure schedule), so that clients can show it before the
+        /// vote is cast.
+        pub fn get_voting_power(&self, poll_id: PollId, account: AccountId) -> u128 {
+            self.voting_weight(poll_id, account)
+        }
+
+        #[ink(message, selector = 0x3E247FD7)]
+        /// Adds a chunk of accounts to the list of eligible voters of a poll that has not started
+        /// yet (with a voting weight of 1), so that lists too large for a single transaction can
+        /// be uploaded over several calls. Accounts already on the list keep their weight. The
+        /// list must be sealed with `seal_voter_list` before the poll can be started.
+        pub fn add_eligible_voters_chunk(
+            &mut self,
+            poll_id: PollId,
+            accounts: Vec<AccountId>,
+        ) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Check if the chunk is too large.
+            if accounts.len() > MAX_ELIGIBLE_VOTER_CHUNK_SIZE as usize {
+                return Err(Error::BatchTooLarge);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll.
+            if self.env().caller() != poll.owner {
+                return Err(Error::OnlyOwnerCanSetVoterWeights);
+            }
+
+            // Check if the poll has started or ended.
+            poll.ensure_status(PollStatus::NotStarted)?;
+
+            // Check if the list of eligible voters has been sealed.
+            let mut upload = self.voter_list_uploads.get(poll_id).unwrap_or_default();
+
+            if upload.sealed {
+                return Err(Error::VoterListIsSealed);
+            }
+
+            // Add the accounts that are not on the list yet, keeping track of the total weight.
+            let mut uploaded_weight = self.uploaded_weights.get(poll_id).unwrap_or_default();
+
+            for account in accounts {
+                if self.voter_weights.contains((poll_id, account)) {
+                    continue;
+                }
+
+                self.voter_weights.insert((poll_id, account), &1);
+                uploaded_weight = uploaded_weight.saturating_add(1);
+                upload.count = upload.count.saturating_add(1);
+            }
+
+            self.uploaded_weights.insert(poll_id, &uploaded_weight);
+            self.voter_list_uploads.insert(poll_id, &upload);
+
+            // Mark the poll as using the uploaded weights.
+            self.weighted_polls.insert(poll_id, &());
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xB76479BF)]
+        /// Seals the list of eligible voters of a poll that has not started yet: no accounts can
+        /// be added to it (or removed from it) afterwards.
+        pub fn seal_voter_list(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll.
+            if self.env().caller() != poll.owner {
+                return Err(Error::OnlyOwnerCanSetVoterWeights);
+            }
+
+            // Check if the poll has started or ended.
+            poll.ensure_status(PollStatus::NotStarted)?;
+
+            // Seal the list.
+            let mut upload = self.voter_list_uploads.get(poll_id).unwrap_or_default();
+
+            if upload.sealed {
+                return Err(Error::VoterListIsSealed);
+            }
+
+            upload.sealed = true;
+            self.voter_list_uploads.insert(poll_id, &upload);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x064B0AB0)]
+        /// Get the upload session of the list of eligible voters of a poll (not present if no
+        /// chunk has been uploaded and the list has not been sealed).
+        pub fn get_voter_list_upload(&self, poll_id: PollId) -> Option<VoterListUpload> {
+            self.voter_list_uploads.get(poll_id)
+        }
+
+        #[ink(message, selector = 0x2C60DC2C)]
+        /// Sets whether the per-choice results of a poll that has not started yet are hidden from
+        /// everyone but the owner until the poll ends (to prevent bandwagon effects).
+        pub fn set_hide_results_until_end(
+            &mut self,
+            poll_id: PollId,
+            hide_results_until_end: bool,
+        ) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanHideResults)?;
+
+            // Change the result visibility of the poll.
+            if hide_results_until_end {
+                self.hidden_results.insert(poll_id, &());
+            } else {
+                self.hidden_results.remove(poll_id);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x63312B67)]
+        /// Returns true if the per-choice results of a poll are hidden until the poll ends.
+        pub fn hides_results_until_end(&self, poll_id: PollId) -> bool {
+            self.hidden_results.contains(poll_id)
+        }
+
+        #[ink(message, selector = 0x6157C641)]
+        /// Sets whether the owner of a poll that has not started yet may vote on it (the owner
+        /// may vote by default).
+        pub fn set_owner_may_vote(
+            &mut self,
+            poll_id: PollId,
+            owner_may_vote: bool,
+        ) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanSetOwnerMayVote)?;
+
+            // Change whether the owner may vote on the poll.
+            if owner_may_vote {
+                self.abstaining_owners.remove(poll_id);
+            } else {
+                self.abstaining_owners.insert(poll_id, &());
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xD50F1120)]
+        /// Returns true if the owner of a poll may vote on it.
+        pub fn owner_may_vote(&self, poll_id: PollId) -> bool {
+            !self.abstaining_owners.contains(poll_id)
+        }
+
+        #[ink(message, selector = 0xB1B69737)]
+        /// Recuses an account from voting on a poll that has not started yet (e.g. because of a
+        /// conflict of interest). Can only be called by the owner of the poll or a moderator.
+        pub fn recuse(&mut self, poll_id: PollId, account: AccountId) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll or a moderator.
+            let caller = self.env().caller();
+
+            if caller != poll.owner && !self.moderators.contains(caller) {
+                return Err(Error::OnlyOwnerOrModeratorCanRecuse);
+            }
+
+            // Check if the poll has started or ended.
+            poll.ensure_status(PollStatus::NotStarted)?;
+
+            // Record the recusal (once per account).
+            if self.recusals.insert((poll_id, account), &()).is_none() {
+                let recusal_count = self.recusal_counts.get(poll_id).unwrap_or_default();
+                self.recusal_counts
+                    .insert(poll_id, &recusal_count.saturating_add(1));
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x0D3C5CB9)]
+        /// Returns true if the account is recused from voting on the poll.
+        pub fn is_recused(&self, poll_id: PollId, account: AccountId) -> bool {
+            self.recusals.contains((poll_id, account))
+        }
+
+        #[ink(message, selector = 0x906A510A)]
+        /// Sets the milestones of the choices of a poll that has not started yet reported by
+        /// `MilestoneReached` events, or removes them (if not present). Milestones are not
+        /// reported while the results of the poll are hidden.
+        pub fn set_milestones(
+            &mut self,
+            poll_id: PollId,
+            milestones: Option<MilestoneConfig>,
+        ) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanSetMilestones)?;
+
+            // Change the milestones of the poll.
+            if let Some(milestones) = milestones {
+                self.milestone_configs.insert(poll_id, &milestones);
+            } else {
+                self.milestone_configs.remove(poll_id);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x239B6FD5)]
+        /// Get the milestones of a poll (not present if the poll reports no milestones).
+        pub fn get_milestones(&self, poll_id: PollId) -> Option<MilestoneConfig> {
+            self.milestone_configs.get(poll_id)
+        }
+
+        #[ink(message, selector = 0x28E16E6B)]
+        /// Makes the ballots of a poll that has not started yet public: the voters and their
+        /// choices are listed in `get_report_with_identities`.
+        pub fn set_public_ballots(&mut self, poll_id: PollId, public: bool) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanSetPublicBallots)?;
+
+            // Change the ballot visibility of the poll.
+            if public {
+                self.public_ballots.insert(poll_id, &0);
+            } else {
+                self.public_ballots.remove(poll_id);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xB9AF0CC5)]
+        /// Returns true if the ballots of a poll are public.
+        pub fn has_public_ballots(&self, poll_id: PollId) -> bool {
+            self.public_ballots.contains(poll_id)
+        }
+
+        #[ink(message, selector = 0xBC730578)]
+        /// Sets who can view the choices and results of a poll that has not started yet.
+        pub fn set_poll_visibility(
+            &mut self,
+            poll_id: PollId,
+            visibility: PollVisibility,
+        ) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanSetVisibility)?;
+
+            // Change the visibility of the poll.
+            self.poll_visibility.insert(poll_id, &visibility);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x69ACB6C9)]
+        /// Get the visibility of a poll.
+        pub fn get_poll_visibility(&self, poll_id: PollId) -> PollVisibility {
+            self.poll_visibility.get(poll_id).unwrap_or_default()
+        }
+
+        #[ink(message, selector = 0x66CAC318)]
+        /// Sets the method used to compute the winner of a poll that has not started yet.
+        pub fn set_tally_strategy(
+            &mut self,
+            poll_id: PollId,
+            strategy: TallyStrategy,
+        ) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanSetTallyStrategy)?;
+
+            // Check if the strategy fills at least one seat.
+            if strategy == (TallyStrategy::Stv { seats: 0 }) {
+                return Err(Error::InvalidSeatCount);
+            }
+
+            // Change the tally strategy of the poll.
+            self.tally_strategies.insert(poll_id, &strategy);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x8A83B5CD)]
+        /// Get the tally strategy of a poll.
+        pub fn get_tally_strategy(&self, poll_id: PollId) -> TallyStrategy {
+            self.tally_strategies.get(poll_id).unwrap_or_default()
+        }
+
+        #[ink(message, selector = 0x70FFF616)]
+        /// Sets the period (in milliseconds) after the end of a poll that has not started yet
+        /// during which its result is provisional and can be challenged (`0` makes the result
+        /// final as soon as the poll ends).
+        pub fn set_challenge_period(
+            &mut self,
+            poll_id: PollId,
+            period: Timestamp,
+        ) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanSetChallengePeriod)?;
+
+            // Change the challenge period of the poll.
+            if period == 0 {
+                self.challenge_periods.remove(poll_id);
+            } else {
+                self.challenge_periods.insert(poll_id, &period);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xE69314DB)]
+        /// Get the challenge period (in milliseconds) of a poll.
+        pub fn get_challenge_period(&self, poll_id: PollId) -> Timestamp {
+            self.challenge_periods.get(poll_id).unwrap_or_default()
+        }
+
+        #[ink(message, selector = 0x54941A95)]
+        /// Sets the maximum number of votes each choice of a poll that has not started yet can
+        /// receive (e.g. the number of seats of each workshop of a slot allocation poll), or
+        /// removes the limit.
+        pub fn set_choice_capacity(
+            &mut self,
+            poll_id: PollId,
+            capacity: Option<u32>,
+        ) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanSetChoiceCapacity)?;
+
+            // Change the choice capacity of the poll.
+            if let Some(capacity) = capacity {
+                self.choice_capacities.insert(poll_id, &capacity);
+            } else {
+                self.choice_capacities.remove(poll_id);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x8F6564DD)]
+        /// Get the maximum number of votes each choice of a poll can receive.
+        pub fn get_choice_capacity(&self, poll_id: PollId) -> Option<u32> {
+            self.choice_capacities.get(poll_id)
+        }
+
+        #[ink(message, selector = 0xD3C3AEDA)]
+        /// Starts a poll.
+        pub fn start_poll(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let mut poll = self
+                .polls
+                .get(&poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll.
+            if self.env().caller() != poll.owner {
+                return Err(Error::OnlyOwnerCanStartPoll);
+            }
+
+            // Change the status of the poll.
+            poll.transition_to(PollStatus::Started)?;
+
+            // Polls with numeric questions can be answered without choices.
+            if self.choice_count.get(poll_id).unwrap_or_default() == 0
+                && !self.question_ids.contains(poll_id)
+            {
+                return Err(Error::CannotStartPollWithNoChoices);
+            }
+
+            // Check if the uploaded list of eligible voters has been sealed.
+            if self.voter_list_uploads.contains(poll_id) && !self.is_voter_list_sealed(poll_id) {
+                return Err(Error::VoterListIsNotSealed);
+            }
+
+            // Check if the maximum number of active polls has been reached.
+            if matches!(self.max_active_polls, Some(max) if self.active_poll_count >= max) {
+                return Err(Error::TooManyActivePolls);
+            }
+
+            // Insert the poll into the storage.
+            self.polls.insert(poll_id, &poll);
+            self.active_poll_count = self.active_poll_count.saturating_add(1);
+            self.add_to_feed(poll_id, poll.end_at);
+
+            // Emit the event.
+            self.env().emit_event(PollStarted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+            });
+            self.emit_lifecycle_event(poll_id, PollStage::Started);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x68489B95)]
+        /// Ends a poll. Can be called by the owner or the finalizer of the poll.
+        pub fn end_poll(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let mut poll = self
+                .polls
+                .get(&poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner or the finalizer of the poll.
+            let caller = self.env().caller();
+
+            if caller != poll.owner && self.finalizers.get(poll_id) != Some(caller) {
+                return Err(Error::OnlyOwnerCanEndPoll);
+            }
+
+            // Change the status of the poll.
+            poll.transition_to(PollStatus::Ended)?;
+
+            // Close the poll.
+            self.close_poll(poll_id, poll);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xEF8338D3)]
+        /// Appoints an account allowed to end a poll besides its owner (e.g. the account of a
+        /// committee publishing the result), or removes it (if not present).
+        pub fn set_finalizer(
+            &mut self,
+            poll_id: PollId,
+            finalizer: Option<AccountId>,
+        ) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll.
+            if self.env().caller() != poll.owner {
+                return Err(Error::OnlyOwnerCanSetFinalizer);
+            }
+
+            // Check if the poll has ended or has been cancelled.
+            poll.ensure_not_closed()?;
+
+            // Change the finalizer of the poll.
+            if let Some(finalizer) = finalizer {
+                self.finalizers.insert(poll_id, &finalizer);
+            } else {
+                self.finalizers.remove(poll_id);
+            }
+
+            // Emit the event.
+            self.env().emit_event(FinalizerChanged {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                finalizer,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xBB00EF1A)]
+        /// Get the finalizer of a poll (not present if only the owner can end the poll).
+        pub fn get_finalizer(&self, poll_id: PollId) -> Option<AccountId> {
+            self.finalizers.get(poll_id)
+        }
+
+        #[ink(message, selector = 0x3F72B844)]
+        /// Ends a poll whose voting window has passed. Can be called by anyone and pays the caller
+        /// the finalization bounty (if configured) from the poll's fee pool.
+        pub fn finalize_if_expired(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Prevent reentrant calls while value is transferred.
+            let _guard = ReentrancyGuard::enter()?;
+
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let mut poll = self
+                .polls
+                .get(&poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Change the status of the poll.
+            poll.transition_to(PollStatus::Ended)?;
+
+            // Check if the voting window of the poll has passed.
+            if self.env().block_timestamp() < poll.end_at {
+                return Err(Error::PollHasNotExpired);
+            }
+
+            // Close the poll.
+            self.close_poll(poll_id, poll);
+
+            // Pay the bounty to the caller (capped by the balance of the poll's fee pool).
+            let fee_pool = self.fee_pools.get(poll_id).unwrap_or_default();
+            let bounty = self.finalization_bounty.min(fee_pool);
+
+            if bounty > 0 {
+                self.fee_pools.insert(poll_id, &(fee_pool - bounty));
+
+                self.pay(poll_id, self.env().caller(), bounty)?;
+            }
+
+            // Pay the keeper bounty funded by the owner to the caller and record the claim.
+            if let Some(amount) = self.keeper_bounties.take(poll_id) {
+                let keeper = self.env().caller();
+
+                self.keeper_claims
+                    .insert(poll_id, &KeeperClaim { keeper, amount });
+
+                self.pay(poll_id, keeper, amount)?;
+
+                self.env().emit_event(KeeperBountyClaimed {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    poll_id,
+                    namespace: self.topic_namespaces.get(poll_id),
+                    keeper,
+                    amount,
+                });
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x6F85882A)]
+        /// Removes a poll for abuse: ends it without a winner and slashes its deposit (the slashed
+        /// deposit stays in the contract). Can only be called by a moderator.
+        pub fn remove_poll(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Check if the caller is a moderator.
+            if !self.moderators.contains(self.env().caller()) {
+                return Err(Error::CallerIsNotModerator);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let mut poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the poll has ended. Moderators can end a poll that has not started yet, so
+            // this bypasses the regular status transitions.
+            poll.ensure_not_closed()?;
+
+            // Slash the deposit of the poll.
+            self.deposits.remove(poll_id);
+
+            // The poll is no longer active.
+            if poll.status == PollStatus::Started {
+                self.active_poll_count = self.active_poll_count.saturating_sub(1);
+                self.remove_from_feed(poll_id);
+            }
+
+            // End the poll without a winner.
+            poll.status = PollStatus::Ended;
+            poll.winner = None;
+
+            // Insert the poll into the storage.
+            self.polls.insert(poll_id, &poll);
+
+            // Emit the event.
+            self.env().emit_event(PollEnded {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                winner: None,
+                total_ballots: self.total_ballots.get(poll_id).unwrap_or_default(),
+                winner_topic: NO_WINNER_TOPIC,
+                winner_vote_count: 0,
+                winner_voter_count: 0,
+            });
+            self.emit_lifecycle_event(poll_id, PollStage::Removed);
+
+            // Notify the observer of the poll.
+            self.notify_observer(poll_id, None);
+
+            Ok(())
+        }
+
+        #[ink(message, payable, selector = 0x9E1FFFB4)]
+        /// Challenges the provisional result of a poll for review by a resolver.
+        /// Only accounts eligible to vote on the poll can challenge its result, by transferring
+        /// the challenge deposit (pulled from the caller for polls paid in a PSP22 token).
+        pub fn challenge_result(&mut self, poll_id: PollId) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Check if the result of the poll can be challenged.
+            if self.get_result_status(poll_id)? != ResultStatus::Provisional {
+                return Err(Error::ResultIsNotProvisional);
+            }
+
+            // Check if the caller is eligible to vote on the poll.
+            if self.voting_weight(poll_id, caller) == 0 {
+                return Err(Error::CallerHasNoVotingWeight);
+            }
+
+            // Collect the deposit of the challenge.
+            let deposit = self.collect_deposit(poll_id, self.challenge_deposit)?;
+
+            // Insert the challenge into storage.
+            self.challenges.insert(
+                poll_id,
+                &Challenge {
+                    challenger: caller,
+                    deposit,
+                },
+            );
+
+            // Emit the event.
+            self.env().emit_event(ResultChallenged {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                challenger: caller,
+                deposit,
+            });
+            self.emit_lifecycle_event(poll_id, PollStage::Challenged);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x64911AE8)]
+        /// Resolves the challenge of the result of a poll, making the result final. The deposit
+        /// of the challenge is slashed if the result is upheld and returned to the challenger if
+        /// the winner is overturned or the poll is voided.
+        pub fn resolve_challenge(
+            &mut self,
+            poll_id: PollId,
+            outcome: ChallengeOutcome,
+        ) -> Result<(), Error> {
+            // Prevent reentrant calls while value is transferred.
+            let _guard = ReentrancyGuard::enter()?;
+
+            let caller = self.env().caller();
+
+            // Check if the caller is a resolver.
+            if !self.resolvers.contains(caller) {
+                return Err(Error::CallerIsNotResolver);
+            }
+
+            // Get the poll and the challenge and return error if the result is not challenged.
+            let mut poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+            let challenge = self
+                .challenges
+                .get(poll_id)
+                .ok_or(Error::ResultIsNotChallenged)?;
+
+            // Apply the outcome to the result of the poll.
+            let refunded = match outcome {
+                ChallengeOutcome::Uphold => 0,
+                ChallengeOutcome::Overturn { winner } => {
+                    if let Some(choice_id) = winner {
+                        if !self.choices.contains((poll_id, choice_id)) {
+                            return Err(Error::ChoiceWithIdDoesNotExist);
+                        }
+                    }
+
+                    poll.winner = winner;
+                    challenge.deposit
+                }
+                ChallengeOutcome::Void => {
+                    self.voided_polls.insert(poll_id, &());
+
+                    poll.winner = None;
+                    challenge.deposit
+                }
+            };
+
+            // Make the result final.
+            self.challenges.remove(poll_id);
+            self.challenge_deadlines.remove(poll_id);
+            self.polls.insert(poll_id, &poll);
+
+            // Return the deposit to the challenger (it is slashed otherwise).
+            if refunded > 0 {
+                self.pay(poll_id, challenge.challenger, refunded)?;
+            }
+
+            // Emit the event.
+            self.env().emit_event(ChallengeResolved {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                resolver: caller,
+                outcome,
+                refunded,
+            });
+            self.emit_lifecycle_event(poll_id, PollStage::Resolved);
+
+            // Push the new result to the results publisher.
+            if outcome != ChallengeOutcome::Uphold {
+                self.publish_result(poll_id, poll.winner);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xC6E9A2B9)]
+        /// Exports the state of a poll as a chunk of SCALE-encoded `ExportRecord`s (a `Vec`) of at
+        /// most `MAX_EXPORT_CHUNK_SIZE` records, starting at the given cursor (`0` for the first
+        /// chunk), so that archival services can snapshot polls of any size. Returns the chunk
+        /// and the cursor of the next chunk (not present if the chunk is the last one).
+        pub fn export_state(
+            &self,
+            poll_id: PollId,
+            cursor: ExportCursor,
+        ) -> Result<(Vec<u8>, Option<ExportCursor>), Error> {
+            self.export_chunk(poll_id, cursor)
+        }
+
+        #[ink(message, selector = 0x975522BB)]
+        /// Get the result of an ended poll as a SCALE-encoded `ResultAttestation`, suitable for
+        /// signing or bridging to other systems.
+        pub fn get_result_attestation(&self, poll_id: PollId) -> Result<Vec<u8>, Error> {
+            self.result_attestation(poll_id)
+        }
+
+        #[ink(message, selector = 0xC2D4E4E8)]
+        /// Get the status of the result of an ended poll.
+        pub fn get_result_status(&self, poll_id: PollId) -> Result<ResultStatus, Error> {
+            self.result_status(poll_id)
+        }
+
+        #[ink(message, selector = 0x4835A860)]
+        /// Get the pending challenge of the result of a poll.
+        pub fn get_challenge(&self, poll_id: PollId) -> Option<Challenge> {
+            self.challenges.get(poll_id)
+        }
+
+        #[ink(message, selector = 0x7AE0D358)]
+        /// Returns the deposit of a poll to its owner once the poll has ended.
+        pub fn claim_deposit(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Prevent reentrant calls while value is transferred.
+            let _guard = ReentrancyGuard::enter()?;
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll.
+            if self.env().caller() != poll.owner {
+                return Err(Error::OnlyOwnerCanClaimDeposit);
+            }
+
+            // Check if the poll has ended.
+            if poll.status != PollStatus::Ended {
+                return Err(Error::PollHasNotEnded);
+            }
+
+            // Take the deposit out of storage.
+            let deposit = self.deposits.take(poll_id).ok_or(Error::NoDepositToClaim)?;
+
+            // Transfer the deposit to the owner.
+            self.pay(poll_id, poll.owner, deposit)?;
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xC2BFD396)]
+        /// Get the deposit of a poll that can be claimed by its owner.
+        pub fn get_deposit(&self, poll_id: PollId) -> Balance {
+            self.deposits.get(poll_id).unwrap_or_default()
+        }
+
+        #[ink(message, payable, selector = 0x098B85FB)]
+        /// Adds the transferred value to the fee pool of a poll paid in the native currency.
+        pub fn fund_fee_pool(&mut self, poll_id: PollId) -> Result<(), Error> {
+            self.add_to_fee_pool(poll_id, 0)
+        }
+
+        #[ink(message, selector = 0x56E6B727)]
+        /// Pulls tokens from the caller into the fee pool of a poll paid in a PSP22 token (the
+        /// contract must be approved to spend the amount).
+        pub fn fund_fee_pool_with_tokens(
+            &mut self,
+            poll_id: PollId,
+            amount: Balance,
+        ) -> Result<(), Error> {
+            self.add_to_fee_pool(poll_id, amount)
+        }
+
+        #[ink(message, payable, selector = 0x10E63818)]
+        /// Adds the transferred value to the keeper bounty of a poll paid in the native currency,
+        /// claimed by the account that finalizes the poll with `finalize_if_expired`. Only the
+        /// owner of the poll can fund the bounty, before the poll ends.
+        pub fn fund_keeper_bounty(&mut self, poll_id: PollId) -> Result<(), Error> {
+            self.add_to_keeper_bounty(poll_id, 0)
+        }
+
+        #[ink(message, selector = 0x1D59E538)]
+        /// Pulls tokens from the caller into the keeper bounty of a poll paid in a PSP22 token
+        /// (the contract must be approved to spend the amount).
+        pub fn fund_keeper_bounty_with_tokens(
+            &mut self,
+            poll_id: PollId,
+            amount: Balance,
+        ) -> Result<(), Error> {
+            self.add_to_keeper_bounty(poll_id, amount)
+        }
+
+        #[ink(message, selector = 0x3E422CA5)]
+        /// Returns the unclaimed keeper bounty of a poll to its owner once the poll has been ended
+        /// without `finalize_if_expired`.
+        pub fn reclaim_keeper_bounty(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Prevent reentrant calls while value is transferred.
+            let _guard = ReentrancyGuard::enter()?;
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll.
+            if self.env().caller() != poll.owner {
+                return Err(Error::OnlyOwnerCanFundKeeperBounty);
+            }
+
+            // Check if the poll has ended.
+            if poll.status != PollStatus::Ended {
+                return Err(Error::PollHasNotEnded);
+            }
+
+            // Take the bounty out of storage.
+            let bounty = self
+                .keeper_bounties
+                .take(poll_id)
+                .ok_or(Error::NothingToClaim)?;
+
+            // Transfer the bounty to the owner.
+            self.pay(poll_id, poll.owner, bounty)?;
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xF26B8189)]
+        /// Get the unclaimed keeper bounty of a poll.
+        pub fn get_keeper_bounty(&self, poll_id: PollId) -> Balance {
+            self.keeper_bounties.get(poll_id).unwrap_or_default()
+        }
+
+        #[ink(message, selector = 0xFACD3C45)]
+        /// Get the claim of the keeper bounty of a poll (not present if it was not claimed).
+        pub fn get_keeper_claim(&self, poll_id: PollId) -> Option<KeeperClaim> {
+            self.keeper_claims.get(poll_id)
+        }
+
+        #[ink(message, selector = 0xC21A7528)]
+        /// Sets (or removes) the contract notified through `PollObserver::on_poll_ended` when the
+        /// poll ends.
+        pub fn set_poll_observer(
+            &mut self,
+            poll_id: PollId,
+            observer: Option<AccountId>,
+        ) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll.
+            if self.env().caller() != poll.owner {
+                return Err(Error::OnlyOwnerCanSetObserver);
+            }
+
+            // Check if the poll has ended.
+            poll.ensure_not_closed()?;
+
+            // Change the observer of the poll.
+            if let Some(observer) = observer {
+                self.poll_observers.insert(poll_id, &observer);
+            } else {
+                self.poll_observers.remove(poll_id);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x8EE6CECD)]
+        /// Get the observer contract of a poll.
+        pub fn get_poll_observer(&self, poll_id: PollId) -> Option<AccountId> {
+            self.poll_observers.get(poll_id)
+        }
+
+        #[ink(message, selector = 0x07082548)]
+        /// Returns true if the observer of a poll could not be notified when the poll ended.
+        pub fn observer_notification_failed(&self, poll_id: PollId) -> bool {
+            self.failed_notifications.contains(poll_id)
+        }
+
+        #[ink(message, selector = 0xB615630A)]
+        /// Get the balance of the fee pool of a poll.
+        pub fn get_fee_pool(&self, poll_id: PollId) -> Balance {
+            self.fee_pools.get(poll_id).unwrap_or_default()
+        }
+
+        #[ink(message, payable, selector = 0x083BE260)]
+        /// Votes on a poll and returns the number of the ballot. If the poll is in prediction
+        /// mode, the transferred value is staked on the chosen choice.
+        pub fn vote(&mut self, poll_id: PollId, choice_id: ChoiceId) -> Result<u64, Error> {
+            self.cast_vote(poll_id, vec![choice_id], 0)
+        }
+
+        #[ink(message, selector = 0xA6D0CF65)]
+        /// Votes on a poll with multiple questions by answering some or all of its questions with
+        /// one of their choices (or a value for numeric questions), and returns the number of the
+        /// ballot. Each choice counts with the full weight of the caller.
+        pub fn vote_ballot(
+            &mut self,
+            poll_id: PollId,
+            answers: Vec<(QuestionId, Answer)>,
+        ) -> Result<u64, Error> {
+            self.cast_ballot(poll_id, answers)
+        }
+
+        #[ink(message, selector = 0x8A7D85DF)]
+        /// Votes on a poll with a choice capacity and returns the number of the ballot or, if
+        /// the choice is full, adds the caller to its waitlist and returns `None`. The first
+        /// waitlisted voter is voted in as soon as a vote for the choice is retracted.
+        pub fn vote_or_wait(
+            &mut self,
+            poll_id: PollId,
+            choice_id: ChoiceId,
+        ) -> Result<Option<u64>, Error> {
+            match self.check_vote(poll_id, Some(choice_id), self.env().caller()) {
+                Err(Error::ChoiceCapacityReached) => {
+                    self.join_waitlist(poll_id, choice_id)?;
+                    Ok(None)
+                }
+                _ => self.cast_vote(poll_id, vec![choice_id], 0).map(Some),
+            }
+        }
+
+        #[ink(message, selector = 0xD752BAAE)]
+        /// Retracts the vote of the caller on a running poll with a choice capacity, freeing a
+        /// seat for the first voter of the waitlist of the choice. Only votes without a stake on
+        /// polls with the plurality tally strategy can be retracted. The ballot stays in the
+        /// Merkle tree of the ballots of the poll, which is append-only.
+        pub fn retract_vote(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the poll is running.
+            poll.ensure_status(PollStatus::Started)?;
+
+            if self.env().block_timestamp() >= poll.end_at {
+                return Err(Error::PollHasEnded);
+            }
+
+            // Check if the vote of the caller can be retracted.
+            let caller = self.env().caller();
+
+            let choice_id = self
+                .ballots
+                .get((poll_id, caller))
+                .ok_or(Error::CallerHasNotVotedOnPoll)?;
+
+            if !self.choice_capacities.contains(poll_id)
+                || self.get_tally_strategy(poll_id).is_ranked()
+                || self.stakes.contains((poll_id, caller))
+            {
+                return Err(Error::VoteCannotBeRetracted);
+            }
+
+            // Remove the vote of the caller.
+            let weight = self
+                .retractable_weights
+                .take((poll_id, caller))
+                .unwrap_or_default();
+            let vote_count = self
+                .vote_counts
+                .get((poll_id, choice_id))
+                .unwrap_or_default();
+            self.vote_counts
+                .insert((poll_id, choice_id), &vote_count.saturating_sub(weight));
+            self.remove_voter(poll_id, choice_id);
+
+            let ballots = self
+                .choice_ballots
+                .get((poll_id, choice_id))
+                .unwrap_or_default();
+            self.choice_ballots
+                .insert((poll_id, choice_id), &ballots.saturating_sub(1));
+
+            self.voted_by.remove((poll_id, caller));
+            self.ballots.remove((poll_id, caller));
+            self.ballot_numbers.remove((poll_id, caller));
+            self.retracted_voters.insert((poll_id, caller), &());
+
+            if let Some(slot) = self.public_voter_slots.take((poll_id, caller)) {
+                self.public_voters.remove((poll_id, slot));
+            }
+
+            // Emit the event.
+            self.env().emit_event(VoteRetracted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                voter: caller,
+                choice_id,
+            });
+
+            // Give the freed seat to the first waitlisted voter.
+            self.promote_waitlisted(poll_id, choice_id);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xB5301068)]
+        /// Get the voters waiting for a seat on a choice of a poll, in order.
+        pub fn get_waitlist(&self, poll_id: PollId, choice_id: ChoiceId) -> Vec<AccountId> {
+            self.waitlists.get((poll_id, choice_id)).unwrap_or_default()
+        }
+
+        #[ink(message, payable, selector = 0x66E05BEB)]
+        /// Votes on a poll with a ranked tally strategy by ranking choices (most preferred first).
+        /// Choices that are not ranked are considered less preferred than the ranked ones. Returns
+        /// the number of the ballot.
+        pub fn vote_ranked(
+            &mut self,
+            poll_id: PollId,
+            ranking: Vec<ChoiceId>,
+        ) -> Result<u64, Error> {
+            self.cast_vote(poll_id, ranking, 0)
+        }
+
+        #[ink(message, selector = 0xE6B16F93)]
+        /// Votes on a poll in prediction mode paid in a PSP22 token, staking the given amount of
+        /// tokens (the contract must be approved to spend the amount). Returns the number of the
+        /// ballot.
+        pub fn vote_with_tokens(
+            &mut self,
+            poll_id: PollId,
+            choice_id: ChoiceId,
+            stake: Balance,
+        ) -> Result<u64, Error> {
+            self.cast_vote(poll_id, vec![choice_id], stake)
+        }
+
+        #[ink(message, selector = 0x704166BA)]
+        /// Enables or disables prediction mode for a poll that has not started yet.
+        pub fn set_prediction_mode(&mut self, poll_id: PollId, enabled: bool) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll.
+            if self.env().caller() != poll.owner {
+                return Err(Error::OnlyOwnerCanSetPredictionMode);
+            }
+
+            // Check if the poll has started or ended.
+            poll.ensure_status(PollStatus::NotStarted)?;
+
+            // Change the prediction mode of the poll.
+            if enabled {
+                self.prediction_polls.insert(poll_id, &());
+            } else {
+                self.prediction_polls.remove(poll_id);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xF79DAE0A)]
+        /// Returns true if the poll is in prediction mode.
+        pub fn is_prediction_poll(&self, poll_id: PollId) -> bool {
+            self.prediction_polls.contains(poll_id)
+        }
+
+        #[ink(message, selector = 0x8B827BD6)]
+        /// Get the total stake of a poll in prediction mode.
+        pub fn get_prediction_pot(&self, poll_id: PollId) -> Balance {
+            self.prediction_pots.get(poll_id).unwrap_or_default()
+        }
+
+        #[ink(message, selector = 0x35C652CE)]
+        /// Pays out the caller's share of the pot of an ended poll in prediction mode.
+        ///
+        /// Voters who backed the winner split the entire pot pro-rata to their stake (rounded
+        /// down, the remainder stays in the contract). If the poll ended without a winner, or if
+        /// nobody staked on the winner, every voter gets their stake back.
+        pub fn claim_winnings(&mut self, poll_id: PollId) -> Result<Balance, Error> {
+            // Prevent reentrant calls while value is transferred.
+            let _guard = ReentrancyGuard::enter()?;
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the result of the poll is final (stakes are refunded on voided polls).
+            if !matches!(
+                self.get_result_status(poll_id)?,
+                ResultStatus::Final | ResultStatus::Voided
+            ) {
+                return Err(Error::ResultIsNotFinal);
+            }
+
+            // Get the caller and their stake.
+            let caller = self.env().caller();
+            let stake = self
+                .stakes
+                .get((poll_id, caller))
+                .ok_or(Error::NothingToClaim)?;
+
+            // Get the total stake on the winner (the stakes are refunded if nobody staked on it,
+            // so that the pot is not locked in the contract).
+            let winner = poll.winner.and_then(|winner| {
+                let winning_stake = self
+                    .choice_stakes
+                    .get((poll_id, winner))
+                    .unwrap_or_default();
+
+                (winning_stake > 0).then_some((winner, winning_stake))
+            });
+
+            // Calculate the winnings of the caller.
+            let winnings = match winner {
+                Some((winner, winning_stake)) => {
+                    if self.ballots.get((poll_id, caller)) != Some(winner) {
+                        return Err(Error::NothingToClaim);
+                    }
+
+                    let pot = self.prediction_pots.get(poll_id).unwrap_or_default();
+
+                    stake.checked_mul(pot).ok_or(Error::ArithmeticOverflow)? / winning_stake
+                }
+                None => stake,
+            };
+
+            // Remove the stake so that the winnings can't be claimed a second time.
+            self.stakes.remove((poll_id, caller));
+
+            // Transfer the winnings to the caller.
+            self.pay(poll_id, caller, winnings)?;
+
+            Ok(winnings)
+        }
+
+        #[ink(message, selector = 0x91F419CE)]
+        /// Performs all the checks `vote` would perform for an account without voting, so that
+        /// clients can validate a vote before submitting it.
+        pub fn can_vote(
+            &self,
+            poll_id: PollId,
+            choice_id: ChoiceId,
+            account: AccountId,
+        ) -> Result<(), Error> {
+            self.check_vote(poll_id, Some(choice_id), account)
+                .map(|_| ())
+        }
+
+        #[ink(message, selector = 0xC59654FE)]
+        /// Delegates the caller's vote on a poll to another account, optionally until the given
+        /// timestamp. Overrides the global delegation of the caller for this poll.
+        pub fn delegate(
+            &mut self,
+            poll_id: PollId,
+            to: AccountId,
+            expires_at: Option<Timestamp>,
+        ) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(&poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the poll has ended.
+            poll.ensure_not_closed()?;
+
+            // Get the caller.
+            let caller = self.env().caller();
+
+            // Check if the caller is delegating to themselves.
+            if caller == to {
+                return Err(Error::CannotDelegateToSelf);
+            }
+
+            // Create the delegation.
+            let delegation = self.new_delegation(to, expires_at)?;
+
+            // Insert the delegation into storage.
+            self.poll_delegations.insert((poll_id, caller), &delegation);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x2456DE34)]
+        /// Removes the caller's delegation for a poll (the global delegation applies again).
+        pub fn undelegate(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            self.poll_delegations.remove((poll_id, self.env().caller()));
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xEF8E9D63)]
+        /// Delegates the caller's vote on every poll to another account (unless overridden by a
+        /// per-poll delegation), optionally until the given timestamp.
+        pub fn delegate_all(
+            &mut self,
+            to: AccountId,
+            expires_at: Option<Timestamp>,
+        ) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the caller.
+            let caller = self.env().caller();
+
+            // Check if the caller is delegating to themselves.
+            if caller == to {
+                return Err(Error::CannotDelegateToSelf);
+            }
+
+            // Create the delegation.
+            let delegation = self.new_delegation(to, expires_at)?;
+
+            // Insert the delegation into storage.
+            self.global_delegations.insert(caller, &delegation);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x019D8CE2)]
+        /// Removes the caller's global delegation.
+        pub fn undelegate_all(&mut self) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            self.global_delegations.remove(self.env().caller());
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xF346B980)]
+        /// Get the account to which an account's vote on a poll is delegated.
+        ///
+        /// Delegations are resolved in the following order:
+        ///
+        /// 1. Per-poll delegation made with `delegate`.
+        /// 2. Global delegation made with `delegate_all`.
+        ///
+        /// Expired delegations are ignored.
+        pub fn get_delegate(&self, poll_id: PollId, account: AccountId) -> Option<AccountId> {
+            let now = self.env().block_timestamp();
+
+            self.poll_delegations
+                .get((poll_id, account))
+                .filter(|delegation| delegation.is_active(now))
+                .or_else(|| {
+                    self.global_delegations
+                        .get(account)
+                        .filter(|delegation| delegation.is_active(now))
+                })
+                .map(|delegation| delegation.delegate)
+        }
+
+        #[ink(message, selector = 0x0DB9C910)]
+        /// Get the global delegation of an account (including its expiry, even if it has expired).
+        pub fn get_delegation(&self, account: AccountId) -> Option<Delegation> {
+            self.global_delegations.get(account)
+        }
+
+        #[ink(message, selector = 0x69ECBDF8)]
+        /// Casts the vote of a delegator on a poll, following the choice of their delegate. Can be
+        /// called by anyone once the delegate has voted (and the delegator has not voted directly).
+        pub fn cast_delegated_vote(
+            &mut self,
+            poll_id: PollId,
+            delegator: AccountId,
+        ) -> Result<(), Error> {
+            // Count the call against the rate limit of the caller.
+            self.consume_rate_limit()?;
+
+            // Resolve the delegate of the delegator.
+            let delegate = self
+                .get_delegate(poll_id, delegator)
+                .ok_or(Error::NoDelegation)?;
+
+            // Get the choice of the delegate.
+            let choice_id = self
+                .ballots
+                .get((poll_id, delegate))
+                .ok_or(Error::DelegateHasNotVoted)?;
+
+            // Record the vote of the delegator.
+            self.record_vote(poll_id, choice_id, delegator)?;
+
+            // Record the ranking of the delegate for the delegator.
+            let ranking = self
+                .ranked_ballots
+                .get((poll_id, delegate))
+                .unwrap_or_else(|| vec![choice_id]);
+            self.record_ranking(poll_id, &ranking, delegator);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x9B9C3846)]
+        /// Approves an operator to vote on behalf of the caller (e.g. a multisig or DAO contract
+        /// that cannot easily call `vote` itself) using `vote_for`.
+        pub fn approve_operator(&mut self, operator: AccountId) -> Result<(), Error> {
+            // Check if the caller is approving themselves.
+            let caller = self.env().caller();
+
+            if caller == operator {
+                return Err(Error::CannotApproveSelf);
+            }
+
+            // Insert the operator into storage.
+            self.operators.insert((caller, operator), &());
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xBB00C769)]
+        /// Revokes the approval of an operator to vote on behalf of the caller.
+        pub fn revoke_operator(&mut self, operator: AccountId) -> Result<(), Error> {
+            // Remove the operator from storage.
+            self.operators.remove((self.env().caller(), operator));
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x9B8CC278)]
+        /// Returns true if the operator is approved to vote on behalf of the principal.
+        pub fn is_operator(&self, principal: AccountId, operator: AccountId) -> bool {
+            self.operators.contains((principal, operator))
+        }
+
+        #[ink(message, selector = 0x8293DD33)]
+        /// Votes on behalf of a principal that approved the caller as operator and returns the
+        /// number of the ballot. The ballot is recorded against the principal, with its voting
+        /// weight.
+        pub fn vote_for(
+            &mut self,
+            principal: AccountId,
+            poll_id: PollId,
+            choice_id: ChoiceId,
+        ) -> Result<u64, Error> {
+            // Count the call against the rate limit of the caller.
+            self.consume_rate_limit()?;
+
+            // Check if the caller is an operator of the principal.
+            if !self.is_operator(principal, self.env().caller()) {
+                return Err(Error::CallerIsNotOperator);
+            }
+
+            // Record the vote of the principal.
+            let ballot_number = self.record_vote(poll_id, choice_id, principal)?;
+            self.record_ranking(poll_id, &[choice_id], principal);
+
+            Ok(ballot_number)
+        }
+
+        #[ink(message, selector = 0x804A080C)]
+        /// Sets a page of the bitmap of the eligible members of a poll that has not started yet:
+        /// bit `i % 8` of byte `i % 256 / 8` of page `i / 256` is set if the member with index `i`
+        /// in the member registry can vote (see `vote_as_member`). Each eligible member counts as
+        /// a voting weight of 1 in the eligible weight of the poll.
+        pub fn set_eligible_members(
+            &mut self,
+            poll_id: PollId,
+            page: u32,
+            bits: BitmapPage,
+        ) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll.
+            if self.env().caller() != poll.owner {
+                return Err(Error::OnlyOwnerCanSetVoterWeights);
+            }
+
+            // Check if the poll has started or ended.
+            poll.ensure_status(PollStatus::NotStarted)?;
+
+            // Check if the list of eligible voters has been sealed.
+            if self.is_voter_list_sealed(poll_id) {
+                return Err(Error::VoterListIsSealed);
+            }
+
+            // Replace the page, keeping track of the total eligible weight.
+            let count = |bits: &BitmapPage| -> u128 {
+                bits.iter().map(|byte| u128::from(byte.count_ones())).sum()
+            };
+
+            let previous_bits = self
+                .eligible_members
+                .get((poll_id, page))
+                .unwrap_or_default();
+            let uploaded_weight = self
+                .uploaded_weights
+                .get(poll_id)
+                .unwrap_or_default()
+                .saturating_sub(count(&previous_bits))
+                .saturating_add(count(&bits));
+
+            self.eligible_members.insert((poll_id, page), &bits);
+            self.uploaded_weights.insert(poll_id, &uploaded_weight);
+
+            // Mark the poll as using the uploaded weights (accounts that are not on the list can
+            // only vote as members).
+            self.weighted_polls.insert(poll_id, &());
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xC4C2323C)]
+        /// Returns true if the member with the given index can vote on a poll.
+        pub fn is_eligible_member(&self, poll_id: PollId, member_index: u32) -> bool {
+            let (page, byte, mask) = bitmap_position(member_index);
+
+            self.eligible_members
+                .get((poll_id, page))
+                .is_some_and(|bits| bits[byte] & mask != 0)
+        }
+
+        #[ink(message, selector = 0xBF68F918)]
+        /// Votes on a poll as the member with the given index in the member registry, if the
+        /// caller is that member and the member is eligible (see `set_eligible_members`). Each
+        /// member can vote once, with a weight of one.
+        pub fn vote_as_member(
+            &mut self,
+            poll_id: PollId,
+            member_index: u32,
+            choice_id: ChoiceId,
+        ) -> Result<u64, Error> {
+            // Count the call against the rate limit of the caller.
+            self.consume_rate_limit()?;
+
+            // Check if the member is eligible and has not voted yet.
+            if !self.is_eligible_member(poll_id, member_index) {
+                return Err(Error::CallerHasNoVotingWeight);
+            }
+
+            if self.has_member_voted(poll_id, member_index) {
+                return Err(Error::CallerAlreadyVotedOnPoll);
+            }
+
+            // Check if the caller is the member with the given index.
+            if self.member_registry.is_none() {
+                return Err(Error::NoMemberRegistry);
+            }
+
+            let caller = self.env().caller();
+
+            if self.member_index(caller) != Some(member_index) {
+                return Err(Error::CallerIsNotMember);
+            }
+
+            // Record the vote of the caller with a weight of one.
+            let ballot_number =
+                self.record_vote_with_weight(poll_id, choice_id, caller, Some(1))?;
+            self.record_ranking(poll_id, &[choice_id], caller);
+
+            // Mark the member as having voted.
+            let (page, byte, mask) = bitmap_position(member_index);
+            let mut bits = self.member_votes.get((poll_id, page)).unwrap_or_default();
+            bits[byte] |= mask;
+            self.member_votes.insert((poll_id, page), &bits);
+
+            Ok(ballot_number)
+        }
+
+        #[ink(message, selector = 0xF190C912)]
+        /// Requires accounts to register (see `register`) before a poll that has not started yet
+        /// in order to vote on it, with the given registration deposit, or removes the
+        /// requirement (if not present).
+        pub fn set_registration(
+            &mut self,
+            poll_id: PollId,
+            deposit: Option<Balance>,
+        ) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanSetRegistration)?;
+
+            // Change the registration requirement of the poll.
+            if let Some(deposit) = deposit {
+                self.registration_deposits.insert(poll_id, &deposit);
+            } else {
+                self.registration_deposits.remove(poll_id);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xC0725381)]
+        /// Get the deposit required to register for a poll (not present if the poll does not
+        /// require registration).
+        pub fn get_registration(&self, poll_id: PollId) -> Option<Balance> {
+            self.registration_deposits.get(poll_id)
+        }
+
+        #[ink(message, payable, selector = 0x229B553F)]
+        /// Registers the caller to vote on a poll requiring registration, before the poll opens.
+        /// Only accounts eligible to vote on the poll can register, by transferring the
+        /// registration deposit (pulled from the caller for polls paid in a PSP22 token), which
+        /// can be claimed back once the poll has ended.
+        pub fn register(&mut self, poll_id: PollId) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            // Count the call against the rate limit of the caller.
+            self.consume_rate_limit()?;
+
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the poll requires registration.
+            let required_deposit = self
+                .registration_deposits
+                .get(poll_id)
+                .ok_or(Error::PollDoesNotRequireRegistration)?;
+
+            // Check if the registration window of the poll is open.
+            if !matches!(poll.status, PollStatus::NotStarted | PollStatus::Scheduled)
+                || self.env().block_timestamp() >= poll.start_at
+            {
+                return Err(Error::RegistrationIsClosed);
+            }
+
+            // Check if the caller has already registered.
+            if self.registrations.contains((poll_id, caller)) {
+                return Err(Error::CallerAlreadyRegistered);
+            }
+
+            // Check if the caller is eligible to vote on the poll.
+            let weight = self.voting_weight(poll_id, caller);
+
+            if weight == 0 {
+                return Err(Error::CallerHasNoVotingWeight);
+            }
+
+            // Collect the registration deposit.
+            let deposit = self.collect_deposit(poll_id, required_deposit)?;
+
+            // Register the caller.
+            self.registrations.insert((poll_id, caller), &deposit);
+
+            let registered_count = self.registered_counts.get(poll_id).unwrap_or_default();
+            self.registered_counts
+                .insert(poll_id, &(registered_count + 1));
+
+            let registered_weight = self.registered_weights.get(poll_id).unwrap_or_default();
+            self.registered_weights
+                .insert(poll_id, &registered_weight.saturating_add(weight));
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xB385FCE3)]
+        /// Returns true if the account has registered to vote on a poll.
+        pub fn is_registered(&self, poll_id: PollId, account: AccountId) -> bool {
+            self.registrations.contains((poll_id, account))
+        }
+
+        #[ink(message, selector = 0x3D251946)]
+        /// Returns the registration deposit of a poll to the caller once the poll has ended.
+        pub fn claim_registration_deposit(&mut self, poll_id: PollId) -> Result<(), Error> {
+            // Prevent reentrant calls while value is transferred.
+            let _guard = ReentrancyGuard::enter()?;
+
+            let caller = self.env().caller();
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the poll has ended.
+            if poll.status != PollStatus::Ended {
+                return Err(Error::PollHasNotEnded);
+            }
+
+            // Get the deposit of the caller, keeping the registration.
+            let deposit = self
+                .registrations
+                .get((poll_id, caller))
+                .filter(|deposit| *deposit > 0)
+                .ok_or(Error::NothingToClaim)?;
+
+            self.registrations.insert((poll_id, caller), &0);
+
+            // Transfer the deposit to the caller.
+            self.pay(poll_id, caller, deposit)?;
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xA66FFAC1)]
+        /// Sets the quorum of a poll that has not started yet: the share (in basis points) of the
+        /// eligible weight (see `get_quorum`) that must be cast for the poll to have a winner
+        /// (`0` removes the quorum).
+        pub fn set_quorum_weight_bps(
+            &mut self,
+            poll_id: PollId,
+            quorum_weight_bps: u16,
+        ) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanSetQuorum)?;
+
+            // Check if the quorum is at most the whole eligible weight.
+            if quorum_weight_bps > 10_000 {
+                return Err(Error::InvalidQuorum);
+            }
+
+            // Change the quorum of the poll.
+            if quorum_weight_bps == 0 {
+                self.quorum_weight_bps.remove(poll_id);
+            } else {
+                self.quorum_weight_bps.insert(poll_id, &quorum_weight_bps);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x434AA7E3)]
+        /// Records the total eligible weight of a poll that has not ended yet, as attested by its
+        /// owner (e.g. from a snapshot of the token balances). It takes precedence over the
+        /// weights uploaded with `set_voter_weights` and the weights of the registered accounts.
+        pub fn record_eligible_weight(
+            &mut self,
+            poll_id: PollId,
+            eligible_weight: u128,
+        ) -> Result<(), Error> {
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Get the poll and return error if it does not exist.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            // Check if the caller is the owner of the poll.
+            if self.env().caller() != poll.owner {
+                return Err(Error::OnlyOwnerCanSetQuorum);
+            }
+
+            // Check if the poll has ended.
+            poll.ensure_not_closed()?;
+
+            // Record the eligible weight of the poll.
+            self.eligible_weights.insert(poll_id, &eligible_weight);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xA4829457)]
+        /// Get the quorum report of a poll.
+        pub fn get_quorum(&self, poll_id: PollId) -> Result<QuorumReport, Error> {
+            self.quorum_report(poll_id)
+        }
+
+        #[ink(message, selector = 0xB63FC9AE)]
+        /// Enables or disables private voting for a poll that has not started yet. Private polls
+        /// only accept votes signed by the voting keys registered before the poll starts.
+        pub fn set_private_voting(&mut self, poll_id: PollId, enabled: bool) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanSetPrivateVoting)?;
+
+            // Change the voting mode of the poll.
+            if enabled {
+                self.private_polls.insert(poll_id, &());
+            } else {
+                self.private_polls.remove(poll_id);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x2CE3E2C5)]
+        /// Returns true if a poll only accepts private votes.
+        pub fn is_private_voting(&self, poll_id: PollId) -> bool {
+            self.private_polls.contains(poll_id)
+        }
+
+        #[ink(message, selector = 0x15E143EB)]
+        /// Registers a one-time ECDSA voting key (compressed public key) of the caller for a
+        /// private poll that has not started yet. The key votes with the weight of the caller.
+        pub fn register_voting_key(
+            &mut self,
+            poll_id: PollId,
+            public_key: [u8; 33],
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            // Check if the contract is paused.
+            if self.paused {
+                return Err(Error::ContractIsPaused);
+            }
+
+            // Check if the poll is in its registration phase.
+            let poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            if !self.private_polls.contains(poll_id) {
+                return Err(Error::PollIsNotPrivate);
+            }
+
+            poll.ensure_status(PollStatus::NotStarted)?;
+
+            // Check if the caller is eligible to vote.
+            let weight = self.voting_weight(poll_id, caller);
+
+            if weight == 0 {
+                return Err(Error::CallerHasNoVotingWeight);
+            }
+
+            // Check if the caller has accepted the terms and registered (the key votes on their
+            // behalf).
+            self.ensure_terms_accepted(caller)?;
+            self.ensure_registered(poll_id, caller)?;
+
+            // Check if the caller or the key has already been registered.
+            let key_account = self.key_account(&public_key);
+
+            if self.key_registrations.contains((poll_id, caller))
+                || self.voting_keys.contains((poll_id, key_account))
+            {
+                return Err(Error::VotingKeyAlreadyRegistered);
+            }
+
+            // Register the key.
+            self.key_registrations.insert((poll_id, caller), &());
+            self.voting_keys.insert((poll_id, key_account), &weight);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xD14D8C48)]
+        /// Get the message hash to sign with a voting key to vote on a choice of a private poll.
+        pub fn private_vote_message(&self, poll_id: PollId, choice_id: ChoiceId) -> [u8; 32] {
+            self.env().hash_encoded::<ink::env::hash::Blake2x256, _>(&(
+                self.env().account_id(),
+                poll_id,
+                choice_id,
+            ))
+        }
+
+        #[ink(message, selector = 0xBB3C870E)]
+        /// Votes on a private poll with a signature (`r || s || v`) of `private_vote_message` by a
+        /// registered voting key. Any account (e.g. a relayer) can submit the vote, so the ballot
+        /// is not linked to the account submitting it. The account derived from the key acts as
+        /// the nullifier of the ballot, preventing the key from voting twice.
+        ///
+        /// Note that the registration of keys is public: fully unlinkable ballots require ring
+        /// signatures, which are not available to contracts.
+        pub fn vote_private(
+            &mut self,
+            poll_id: PollId,
+            choice_id: ChoiceId,
+            signature: [u8; 65],
+        ) -> Result<(), Error> {
+            // Count the call against the rate limit of the caller (e.g. a relayer).
+            self.consume_rate_limit()?;
+
+            // Check if the poll accepts private votes.
+            if !self.private_polls.contains(poll_id) {
+                return Err(Error::PollIsNotPrivate);
+            }
+
+            // Recover the voting key from the signature.
+            let message = self.private_vote_message(poll_id, choice_id);
+            let public_key = self
+                .env()
+                .ecdsa_recover(&signature, &message)
+                .map_err(|_| Error::InvalidSignature)?;
+
+            // Check if the key is registered for the poll.
+            let key_account = self.key_account(&public_key);
+
+            if !self.voting_keys.contains((poll_id, key_account)) {
+                return Err(Error::VotingKeyNotRegistered);
+            }
+
+            // Record the vote of the key.
+            self.record_vote(poll_id, choice_id, key_account)?;
+            self.record_ranking(poll_id, &[choice_id], key_account);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x3A567AA9)]
+        /// Sets the key used by the membership verifier to check the proofs of anonymous votes
+        /// on a private poll that has not started yet.
+        pub fn set_verifier_key(
+            &mut self,
+            poll_id: PollId,
+            verifier_key: Vec<u8>,
+        ) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanSetVerifierKey)?;
+
+            // Check if the poll only accepts private votes.
+            if !self.private_polls.contains(poll_id) {
+                return Err(Error::PollIsNotPrivate);
+            }
+
+            // Change the verifier key of the poll.
+            self.verifier_keys.insert(poll_id, &verifier_key);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xB9E18BDD)]
+        /// Get the verifier key of a poll accepting anonymous votes.
+        pub fn get_verifier_key(&self, poll_id: PollId) -> Option<Vec<u8>> {
+            self.verifier_keys.get(poll_id)
+        }
+
+        #[ink(message, selector = 0xF575DF76)]
+        /// Makes a poll that has not started yet accept only ballots encrypted to the given public
+        /// key. The ballots are decrypted off-chain by the committee once the poll ends and the
+        /// tally is posted with `post_decrypted_tally`, so no interim results are available.
+        /// Encrypted polls are tallied by plurality.
+        pub fn set_ballot_encryption(
+            &mut self,
+            poll_id: PollId,
+            public_key: Vec<u8>,
+            committee: Vec<AccountId>,
+            threshold: u32,
+        ) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            self.require_mutable(poll_id, Error::OnlyOwnerCanSetBallotEncryption)?;
+
+            // Check if the threshold can be reached by the committee.
+            if threshold == 0 || threshold as usize > committee.len() {
+                return Err(Error::InvalidThreshold);
+            }
+
+            // Change the encryption configuration of the poll.
+            self.ballot_encryption.insert(
+                poll_id,
+                &BallotEncryption {
+                    public_key,
+                    committee,
+                    threshold,
+                },
+            );
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xF7D2174F)]
+        /// Get the encryption configuration of a poll with encrypted ballots.
+        pub fn get_ballot_encryption(&self, poll_id: PollId) -> Option<BallotEncryption> {
+            self.ballot_encryption.get(poll_id)
+        }
+
+        #[ink(message, selector = 0xEE55F612)]
+        /// Votes on a poll with encrypted ballots.
+        pub fn vote_encrypted(
+            &mut self,
+            poll_id: PollId,
+            ciphertext: Vec<u8>,
+        ) -> Result<(), Error> {
+            // Count the call against the rate limit of the caller.
+            self.consume_rate_limit()?;
+
+            let caller = self.env().caller();
+
+            // Check if the poll uses encrypted ballots.
+            if !self.ballot_encryption.contains(poll_id) {
+                return Err(Error::PollDoesNotUseEncryptedBallots);
+            }
+
+            // Check if the vote is allowed and get its weight.
+            let weight = self.check_vote(poll_id, None, caller)?;
+
+            // Insert the encrypted ballot into storage.
+            let index = self
+                .encrypted_ballot_counts
+                .get(poll_id)
+                .unwrap_or_default();
+            self.encrypted_ballots
+                .insert((poll_id, index), &EncryptedBallot { ciphertext, weight });
+            self.encrypted_ballot_counts
+                .insert(poll_id, &index.saturating_add(1));
+
+            // Insert the voter into storage.
+            self.voted_by.insert((poll_id, caller), &true);
+
+            // Count the ballot.
+            self.count_ballot(poll_id, caller);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x81398D3E)]
+        /// Get the number of encrypted ballots of a poll.
+        pub fn get_encrypted_ballot_count(&self, poll_id: PollId) -> u32 {
+            self.encrypted_ballot_counts
+                .get(poll_id)
+                .unwrap_or_default()
+        }
+
+        #[ink(message, selector = 0x29B6C80A)]
+        /// Get an encrypted ballot of a poll by its index (none if the caller cannot view the
+        /// poll).
+        pub fn get_encrypted_ballot(&self, poll_id: PollId, index: u32) -> Option<EncryptedBallot> {
+            if !self.can_view(poll_id) {
+                return None;
+            }
+
+            self.encrypted_ballots.get((poll_id, index))
+        }
+
+        #[ink(message, selector = 0x55F0D784)]
+        /// Submits the decryption share of a committee member for an ended poll with encrypted
+        /// ballots.
+        pub fn submit_decryption_share(
+            &mut self,
+            poll_id: PollId,
+            share: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let encryption = self
+                .ballot_encryption
+                .get(poll_id)
+                .ok_or(Error::PollDoesNotUseEncryptedBallots)?;
+
+            // Check if the poll has ended.
+            self.polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?
+                .ensure_status(PollStatus::Ended)?;
+
+            // Check if the caller is a member of the committee without a submitted share.
+            if !encryption.committee.contains(&caller) {
+                return Err(Error::CallerIsNotInDecryptionCommittee);
+            }
+
+            if self.decryption_shares.contains((poll_id, caller)) {
+                return Err(Error::DecryptionShareAlreadySubmitted);
+            }
+
+            // Insert the share into storage.
+            self.decryption_shares.insert((poll_id, caller), &share);
+
+            let share_count = self
+                .decryption_share_counts
+                .get(poll_id)
+                .unwrap_or_default();
+            self.decryption_share_counts
+                .insert(poll_id, &share_count.saturating_add(1));
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xEE03B0FC)]
+        /// Get the decryption share submitted by a committee member for a poll.
+        pub fn get_decryption_share(&self, poll_id: PollId, member: AccountId) -> Option<Vec<u8>> {
+            self.decryption_shares.get((poll_id, member))
+        }
+
+        #[ink(message, selector = 0xA0DAE6FD)]
+        /// Posts the decrypted tally (the votes of each choice) of an ended poll with encrypted
+        /// ballots once enough decryption shares are submitted, along with a hash committing to the
+        /// decryption for verification off-chain. Can be called by the owner or a committee member.
+        pub fn post_decrypted_tally(
+            &mut self,
+            poll_id: PollId,
+            tally: Vec<(ChoiceId, u128)>,
+            verification_hash: [u8; 32],
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let encryption = self
+                .ballot_encryption
+                .get(poll_id)
+                .ok_or(Error::PollDoesNotUseEncryptedBallots)?;
+
+            // Check if the poll has ended.
+            let mut poll = self
+                .polls
+                .get(poll_id)
+                .ok_or(Error::PollWithIdDoesNotExist)?;
+
+            poll.ensure_status(PollStatus::Ended)?;
+
+            // Check if the caller can post the tally.
+            if caller != poll.owner && !encryption.committee.contains(&caller) {
+                return Err(Error::CallerCannotPostTally);
+            }
+
+            if self.tally_hashes.contains(poll_id) {
+                return Err(Error::TallyAlreadyPosted);
+            }
+
+            if self
+                .decryption_share_counts
+                .get(poll_id)
+                .unwrap_or_default()
+                < encryption.threshold
+            {
+                return Err(Error::NotEnoughDecryptionShares);
+            }
+
+            // Check if the tally only counts existing choices and at most the encrypted ballots.
+            let total_weight = (0..self.get_encrypted_ballot_count(poll_id))
+                .filter_map(|index| self.encrypted_ballots.get((poll_id, index)))
+                .fold(0u128, |total, ballot| total.saturating_add(ballot.weight));
+
+            let total_votes = tally
+                .iter()
+                .try_fold(0u128, |total, (_, votes)| total.checked_add(*votes))
+                .ok_or(Error::InvalidTally)?;
+
+            if total_votes > total_weight
+                || tally
+                    .iter()
+                    .any(|(choice_id, _)| !self.choices.contains((poll_id, *choice_id)))
+            {
+                return Err(Error::InvalidTally);
+            }
+
+            // Insert the tally into storage.
+            for (choice_id, votes) in tally {
+                self.vote_counts.insert((poll_id, choice_id), &votes);
+            }
+
+            self.tally_hashes.insert(poll_id, &verification_hash);
+
+            // Change the winner of the poll.
+            let winner = self.plurality_winner(poll_id);
+            poll.winner = winner;
+            self.polls.insert(poll_id, &poll);
+
+            // Emit the event.
+            self.env().emit_event(TallyPosted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                winner,
+                verification_hash,
+            });
+            self.emit_lifecycle_event(poll_id, PollStage::TallyPosted);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x4C7E4EB6)]
+        /// Get the verification hash of the posted tally of a poll with encrypted ballots.
+        pub fn get_tally_hash(&self, poll_id: PollId) -> Option<[u8; 32]> {
+            self.tally_hashes.get(poll_id)
+        }
+
+        #[ink(message, selector = 0xB1B63414)]
+        /// Votes anonymously on a private poll with a zero-knowledge proof of membership in the
+        /// set of eligible voters. The proof is bound to the vote through its signal
+        /// (`private_vote_message`) and the nullifier prevents the same member from voting twice.
+        /// Anonymous votes have a weight of one.
+        pub fn vote_anonymous(
+            &mut self,
+            poll_id: PollId,
+            choice_id: ChoiceId,
+            proof: Vec<u8>,
+            nullifier: [u8; 32],
+        ) -> Result<(), Error> {
+            // Count the call against the rate limit of the caller.
+            self.consume_rate_limit()?;
+
+            // Check if the poll accepts anonymous votes.
+            let verifier_key = self
+                .verifier_keys
+                .get(poll_id)
+                .ok_or(Error::PollDoesNotAcceptAnonymousVotes)?;
+
+            let verifier = self
+                .membership_verifier
+                .ok_or(Error::NoMembershipVerifier)?;
+
+            // Check if the nullifier has already been used (before paying for the verification).
+            let nullifier_account = AccountId::from(nullifier);
+
+            if self.voting_keys.contains((poll_id, nullifier_account)) {
+                return Err(Error::NullifierAlreadyUsed);
+            }
+
+            // Verify the membership proof.
+            let signal = self.private_vote_message(poll_id, choice_id);
+
+            let result = build_call::<Environment>()
+                .call(verifier)
+                .ref_time_limit(VERIFIER_GAS_LIMIT)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "MembershipVerifier::verify_membership"
+                    )))
+                    .push_arg(verifier_key)
+                    .push_arg(proof)
+                    .push_arg(nullifier)
+                    .push_arg(signal),
+                )
+                .returns::<bool>()
+                .try_invoke();
+
+            if !matches!(result, Ok(Ok(true))) {
+                return Err(Error::InvalidProof);
+            }
+
+            // Record the vote of the nullifier as a voting key of weight one.
+            self.voting_keys.insert((poll_id, nullifier_account), &1);
+            self.record_vote(poll_id, choice_id, nullifier_account)?;
+            self.record_ranking(poll_id, &[choice_id], nullifier_account);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x1EEE3120)]
+        /// Get the compile-time limits and versions of the contract, so that clients can adapt
+        /// their validation to the deployed build.
+        pub fn constants(&self) -> Constants {
+            Constants {
+                max_choices: u64::from(ChoiceId::MAX) + 1,
+                max_description_length: Some(MAX_DESCRIPTION_LENGTH),
+                summary_description_length: SUMMARY_DESCRIPTION_LENGTH as u32,
+                max_choice_batch_size: MAX_CHOICE_BATCH_SIZE,
+                max_voter_weight_batch_size: MAX_VOTER_WEIGHT_BATCH_SIZE,
+                max_eligible_voter_chunk_size: MAX_ELIGIBLE_VOTER_CHUNK_SIZE,
+                timeline_bucket_duration: TIMELINE_BUCKET_DURATION,
+                max_export_chunk_size: MAX_EXPORT_CHUNK_SIZE,
+                max_summary_batch_size: MAX_SUMMARY_BATCH_SIZE,
+                max_admin_log_page_size: MAX_ADMIN_LOG_PAGE_SIZE,
+                max_fee_bps: MAX_FEE_BPS,
+                poll_storage_version: POLL_STORAGE_VERSION,
+                event_schema_version: EVENT_SCHEMA_VERSION,
+                topic_scheme_version: TOPIC_SCHEME_VERSION,
+                tally_strategies: SUPPORTED_TALLY_STRATEGIES,
+            }
+        }
+
+        #[ink(message, selector = 0x55719F5A)]
+        /// Get the version of the schema of the events emitted by the contract.
+        pub fn get_event_schema_version(&self) -> u8 {
+            EVENT_SCHEMA_VERSION
+        }
+
+        #[ink(message, selector = 0x92004F6E)]
+        /// Get the topic of the `PollLifecycle` events of a poll.
+        pub fn get_poll_topic(&self, poll_id: PollId) -> Hash {
+            Hash::from(self.poll_topic(poll_id))
+        }
+
+        #[ink(message, selector = 0x60DDB40E)]
+        /// Get the scheme used to derive the topics of the `PollLifecycle` events.
+        pub fn get_topic_scheme(&self) -> TopicScheme {
+            TopicScheme {
+                version: TOPIC_SCHEME_VERSION,
+                domain: String::from(POLL_TOPIC_DOMAIN),
+                namespace_domain: String::from(NAMESPACE_TOPIC_DOMAIN),
+            }
+        }
+
+        #[ink(message, selector = 0x40819FC7)]
+        /// Sets the opaque seed from which the namespace of a poll that has not started yet is
+        /// derived. The namespace is a topic of every later event of the poll, so that indexers
+        /// can follow all the polls of an organization sharing the contract with a single filter.
+        pub fn set_topic_seed(&mut self, poll_id: PollId, seed: [u8; 32]) -> Result<(), Error> {
+            // Check if the poll can still be changed.
+            let poll = self.require_mutable(poll_id, Error::OnlyOwnerCanSetTopicSeed)?;
+
+            // Derive the namespace from the owner as well, so that other accounts cannot emit
+            // events of their polls under it.
+            let namespace = self.env().hash_encoded::<ink::env::hash::Blake2x256, _>(&(
+                NAMESPACE_TOPIC_DOMAIN,
+                poll.owner,
+                seed,
+            ));
+            self.topic_namespaces.insert(poll_id, &namespace);
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x19AE8791)]
+        /// Get the namespace of the events of a poll (see `set_topic_seed`).
+        pub fn get_topic_namespace(&self, poll_id: PollId) -> Option<[u8; 32]> {
+            self.topic_namespaces.get(poll_id)
+        }
+
+        #[ink(message, selector = 0xDC042A54)]
+        /// Get the total number of polls created.
+        pub fn poll_count(&self) -> u64 {
+            self.poll_count
+        }
+
+        #[ink(message, selector = 0x5853C882)]
+        /// Returns true if a poll with the given id exists.
+        pub fn poll_exists(&self, poll_id: PollId) -> bool {
+            self.polls.contains(poll_id)
+        }
+
+        #[ink(message, selector = 0x6499AAEB)]
+        /// Returns true if a choice with the given id exists in the poll.
+        pub fn choice_exists(&self, poll_id: PollId, choice_id: ChoiceId) -> bool {
+            self.choices.contains((poll_id, choice_id))
+        }
+
+        #[ink(message, selector = 0xBFA6B7DD)]
+        /// Get all the choices for a poll (none if the caller cannot view the poll).
+        pub fn get_choices(&self, poll_id: PollId) -> Vec<(ChoiceId, Choice)> {
+            self.visible_choices(poll_id)
+        }
+
+        #[ink(message, selector = 0xEB9F6096)]
+        /// Get the report for a poll. The per-choice counts of a running poll that hides its
+        /// results until the end are reported as zero to everyone but the owner.
+        pub fn get_report(&self, poll_id: PollId) -> Result<PollReport, Error> {
+            self.poll_report(poll_id)
+        }
+
+        #[ink(message, selector = 0x03301A4B)]
+        /// Get the report for a poll with the owner and (for polls with public ballots) the voters
+        /// resolved to their display names by the identity registry. The voters are listed a page
+        /// at a time: those in the `limit` voter slots (at most `MAX_IDENTITY_PAGE_SIZE`) starting
+        /// at `offset`, in order of voting.
+        pub fn get_report_with_identities(
+            &self,
+            poll_id: PollId,
+            offset: u32,
+            limit: u32,
+        ) -> Result<IdentityReport, Error> {
+            self.identity_report(poll_id, offset, limit)
+        }
+
+        #[ink(message, selector = 0x911C75EA)]
+        /// Get the report for a poll as presented to an account. If the poll shuffles its choices,
+        /// they are shuffled deterministically with a seed derived from the poll id and the account
+        /// (included in the report).
+        pub fn get_report_for(
+            &self,
+            poll_id: PollId,
+            account: AccountId,
+        ) -> Result<PollReport, Error> {
+            self.report_for_account(poll_id, account)
+        }
+
+        #[ink(message, selector = 0x1264450B)]
+        /// Get the weight of the voters preferring choice `a` to choice `b` in a poll with the
+        /// Condorcet tally strategy.
+        pub fn get_pairwise_preference(&self, poll_id: PollId, a: ChoiceId, b: ChoiceId) -> u128 {
+            self.pairwise_preferences
+                .get((poll_id, a, b))
+                .unwrap_or_default()
+        }
+
+        #[ink(message, selector = 0x34C6175B)]
+        /// Get the Smith set of an ended poll with the Condorcet tally strategy: the smallest set
+        /// of choices that beat every choice outside the set. It contains only the winner if there
+        /// is a Condorcet winner.
+        pub fn get_smith_set(&self, poll_id: PollId) -> Vec<ChoiceId> {
+            self.smith_sets.get(poll_id).unwrap_or_default()
+        }
+
+        #[ink(message, selector = 0xC51514C9)]
+        /// Get the elected choices (in order of election) of an ended poll with the STV tally
+        /// strategy.
+        pub fn get_winners(&self, poll_id: PollId) -> Vec<ChoiceId> {
+            self.winners.get(poll_id).unwrap_or_default()
+        }
+
+        #[ink(message, selector = 0x2C9DA8BE)]
+        /// Get the round-by-round record of the count of an ended poll with the STV tally
+        /// strategy.
+        pub fn get_count_rounds(&self, poll_id: PollId) -> Vec<CountRound> {
+            self.count_rounds.get(poll_id).unwrap_or_default()
+        }
+
+        #[ink(message, selector = 0x1EA63CE3)]
+        /// Get the number of votes (or their weight) of a choice of a poll (`0` if the caller
+        /// cannot view the poll or its results are hidden).
+        pub fn get_vote_count(&self, poll_id: PollId, choice_id: ChoiceId) -> u128 {
+            match self.polls.get(poll_id) {
+                Some(poll) if self.can_view(poll_id) && !self.hides_counts(poll_id, &poll) => self
+                    .vote_counts
+                    .get((poll_id, choice_id))
+                    .unwrap_or_default(),
+                _ => 0,
+            }
+        }
+
+        #[ink(message, selector = 0x8A07AF70)]
+        /// Get the number of votes (or their weight) of each choice of a poll (empty if the caller
+        /// cannot view the poll, `0` for every choice if its results are hidden).
+        pub fn get_vote_counts(&self, poll_id: PollId) -> Vec<(ChoiceId, u128)> {
+            self.visible_vote_counts(poll_id)
+        }
+
+        #[ink(message, selector = 0x70E83E24)]
+        /// Get the number of the ballot of an account on a poll (not present if the account has
+        /// not voted on the poll).
+        pub fn get_ballot_number(&self, poll_id: PollId, account: AccountId) -> Option<u64> {
+            self.ballot_numbers.get((poll_id, account))
+        }
+
+        #[ink(message, selector = 0xDB94E389)]
+        /// Get the vote timeline of a poll: the number of ballots cast during each hour in which
+        /// ballots were cast, in chronological order.
+        pub fn get_vote_timeline(&self, poll_id: PollId) -> Vec<TimelineBucket> {
+            self.vote_timelines.get(poll_id).unwrap_or_default()
+        }
+
+        #[ink(message, selector = 0x80375DFD)]
+        /// Get the turnout report for a poll.
+        pub fn get_turnout(&self, poll_id: PollId) -> Result<TurnoutReport, Error> {
+            self.turnout_report(poll_id)
+        }
+
+        #[ink(message, selector = 0x36D673C2)]
+        /// Get the summary of a poll.
+        pub fn get_summary(&self, poll_id: PollId) -> Result<PollSummary, Error> {
+            self.poll_summary(poll_id)
+        }
+
+        #[ink(message, selector = 0x28C49261)]
+        /// Get the summaries of multiple polls (`None` for polls that do not exist).
+        pub fn get_summaries(
+            &self,
+            poll_ids: Vec<PollId>,
+        ) -> Result<Vec<Option<PollSummary>>, Error> {
+            self.poll_summaries(poll_ids)
+        }
+
+        #[ink(message, selector = 0xF15EAB7A)]
+        /// Get the dashboard of the polls created by an account (oldest first), with their
+        /// status, turnout and the actions pending on them.
+        pub fn get_owner_dashboard(&self, owner: AccountId) -> OwnerDashboard {
+            self.owner_dashboard(owner)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ink::env::test::EmittedEvent;
+
+        use crate::{contract::migration::*, tally::STV_PRECISION};
+
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+
+        /// One day in milliseconds.
+        const ONE_DAY: Timestamp = 24 * 60 * 60 * 1000;
+
+        /// Decodes an emitted event if it has the signature topic of the given type of event.
+        fn decode_event<E: ink::env::Event + scale::Decode>(event: &EmittedEvent) -> Option<E> {
+            let signature_topic = E::SIGNATURE_TOPIC.expect("the events are not anonymous");
+
+            (event.topics.first() == Some(&signature_topic.to_vec())).then(|| {
+                E::decode(&mut &event.data[..])
+                    .expect("encountered invalid contract event data buffer")
+            })
+        }
+
+        /// Returns the recorded events, without the `PollLifecycle` events emitted alongside them.
+        fn recorded_poll_events() -> Vec<EmittedEvent> {
+            ink::env::test::recorded_events()
+                .filter(|event| decode_event::<PollLifecycle>(event).is_none())
+                .collect()
+        }
+
+        fn assert_poll_created_event(
+            event: &EmittedEvent,
+            expected_poll_id: PollId,
+            expected_description: &str,
+            expected_owner: AccountId,
+        ) {
+            let decoded_event = decode_event::<PollCreated>(event);
+
+            if let Some(PollCreated {
+                schema_version,
+                poll_id,
+                description,
+                owner,
+            }) = decoded_event
+            {
+                assert_eq!(schema_version, EVENT_SCHEMA_VERSION);
+                assert_eq!(poll_id, expected_poll_id);
+                assert_eq!(description, expected_description);
+                assert_eq!(owner, expected_owner);
+            } else {
+                panic!("encountered unexpected contract event kind: expected `PollCreated`")
+            }
+        }
+
+        fn assert_add_choice_event(
+            event: &EmittedEvent,
+            expected_poll_id: PollId,
+            expected_choice_id: ChoiceId,
+            expected_description: &str,
+        ) {
+            let decoded_event = decode_event::<ChoiceAdded>(event);
+
+            if let Some(ChoiceAdded {
+                schema_version,
+                poll_id,
+                namespace: _,
+                choice_id,
+                description,
+            }) = decoded_event
+            {
+                assert_eq!(schema_version, EVENT_SCHEMA_VERSION);
+                assert_eq!(poll_id, expected_poll_id);
+                assert_eq!(choice_id, expected_choice_id);
+                assert_eq!(description, expected_description);
+            } else {
+                panic!("encountered unexpected contract event kind: expected `ChoiceAdded`")
+            }
+        }
+
+        fn assert_start_poll_event(event: &EmittedEvent, expected_poll_id: PollId) {
+            let decoded_event = decode_event::<PollStarted>(event);
+
+            if let Some(PollStarted {
+                schema_version,
+                poll_id,
+                namespace: _,
+            }) = decoded_event
+            {
+                assert_eq!(schema_version, EVENT_SCHEMA_VERSION);
+                assert_eq!(poll_id, expected_poll_id);
+            } else {
+                panic!("encountered unexpected contract event kind: expected `PollStarted`")
+            }
+        }
+
+        fn assert_vote_cast_event(
+            event: &EmittedEvent,
+            expected_poll_id: PollId,
+            expected_voter: AccountId,
+            expected_ballot_number: u64,
+        ) {
+            let decoded_event = decode_event::<VoteCast>(event);
+
+            if let Some(VoteCast {
+                schema_version,
+                poll_id,
+                namespace: _,
+                voter,
+                ballot_number,
+            }) = decoded_event
+            {
+                assert_eq!(schema_version, EVENT_SCHEMA_VERSION);
+                assert_eq!(poll_id, expected_poll_id);
+                assert_eq!(voter, expected_voter);
+                assert_eq!(ballot_number, expected_ballot_number);
+            } else {
+                panic!("encountered unexpected contract event kind: expected `VoteCast`")
+            }
+        }
+
+        fn assert_end_poll_event(
+            event: &EmittedEvent,
+            expected_poll_id: PollId,
+            expected_winner: Option<ChoiceId>,
+            expected_total_ballots: u64,
+        ) {
+            let decoded_event = decode_event::<PollEnded>(event);
+
+            if let Some(PollEnded {
+                schema_version,
+                poll_id,
+                namespace: _,
+                winner,
+                total_ballots,
+                winner_topic,
+                ..
+            }) = decoded_event
+            {
+                assert_eq!(schema_version, EVENT_SCHEMA_VERSION);
+                assert_eq!(poll_id, expected_poll_id);
+                assert_eq!(winner, expected_winner);
+                assert_eq!(total_ballots, expected_total_ballots);
+                assert_eq!(
+                    winner_topic,
+                    expected_winner.map_or(NO_WINNER_TOPIC, u64::from)
+                );
+
+                // The winner topic is the last topic of the event.
+                let mut expected_topic = [0; 32];
+                expected_topic[..8].copy_from_slice(&winner_topic.to_le_bytes());
+                assert_eq!(event.topics.last(), Some(&expected_topic.to_vec()));
+            } else {
+                panic!("encountered unexpected contract event kind: expected `PollEnded`")
+            }
+        }
+
+        #[ink::test]
+        /// Tests that `default` constructor sets `admin` properly.
+        fn test_contract_admin() {
+            let voting_contract = VotingContract::default();
+
+            assert_eq!(
+                voting_contract.admin,
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().alice
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `pause` works properly.
+        fn test_contract_pause_success() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(voting_contract.paused, false);
+            assert!(voting_contract.pause().is_ok());
+            assert_eq!(voting_contract.paused, true);
+        }
+
+        #[ink::test]
+        /// Tests that `pause` fails if the caller is not the admin.
+        fn test_contract_pause_failure_not_admin() {
+            let mut voting_contract = VotingContract::default();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().bob,
+            );
+            assert_eq!(voting_contract.pause(), Err(Error::CallerIsNotAdmin));
+            assert_eq!(voting_contract.paused, false);
+        }
+
+        #[ink::test]
+        /// Tests that `unpause` works properly.
+        fn test_contract_unpause_success() {
+            let mut voting_contract = VotingContract::default();
+
+            assert!(voting_contract.pause().is_ok());
+
+            assert!(voting_contract.unpause().is_ok());
+            assert_eq!(voting_contract.paused, false);
+        }
+
+        #[ink::test]
+        /// Tests that `unpause` fails if the caller is not the admin.
+        fn test_contract_unpause_failure_not_admin() {
+            let mut voting_contract = VotingContract::default();
+
+            assert!(voting_contract.pause().is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().bob,
+            );
+            assert_eq!(voting_contract.unpause(), Err(Error::CallerIsNotAdmin));
+            assert_eq!(voting_contract.paused, true);
+        }
+
+        #[ink::test]
+        /// Tests that `migrate` rewrites a poll stored with `u8` choice ids.
+        fn test_migrate_choice_ids() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Write a poll using the storage layout of version 0.
+            let mut v0 = StorageV0::new();
+            v0.polls.insert(
+                1,
+                &PollV0 {
+                    description: "test".to_string().try_into().unwrap(),
+                    status: PollStatus::Ended,
+                    owner: default_accounts.bob,
+                    winner: Some(2),
+                    start_at: 0,
+                    end_at: ONE_DAY,
+                },
+            );
+            v0.choice_ids.insert(1, &vec![1u8, 2u8]);
+            v0.next_choice_ids.insert(1, &3u8);
+            v0.choices.insert(
+                (1, 1),
+                &Choice {
+                    description: "test1".to_string().try_into().unwrap(),
+                },
+            );
+            v0.choices.insert(
+                (1, 2),
+                &Choice {
+                    description: "test2".to_string().try_into().unwrap(),
+                },
+            );
+            v0.vote_counts.insert((1, 1), &1u64);
+            v0.vote_counts.insert((1, 2), &3u64);
+
+            assert_eq!(voting_contract.migrate(1), Ok(()));
+
+            let report = voting_contract.get_report(1).unwrap();
+            assert_eq!(report.owner, default_accounts.bob);
+            assert_eq!(report.status, PollStatus::Ended);
+            assert_eq!(report.winner, Some(2));
+            assert_eq!(report.description, "test".to_string());
+            assert_eq!(report.choices.len(), 2);
+            assert_eq!(report.choices[0].id, 1);
+            assert_eq!(report.choices[0].description, "test1".to_string());
+            assert_eq!(report.choices[0].vote_count, 1);
+            assert_eq!(report.choices[1].id, 2);
+            assert_eq!(report.choices[1].description, "test2".to_string());
+            assert_eq!(report.choices[1].vote_count, 3);
+            assert_eq!(voting_contract.next_choice_ids.get(1), Some(3));
+            assert_eq!(voting_contract.choice_count.get(1), Some(2));
+            assert!(voting_contract.has_choice(1, 1));
+            assert!(voting_contract.has_choice(1, 2));
+            assert!(!voting_contract.has_choice(1, 3));
+
+            // Entries of version 0 are removed.
+            assert!(!v0.choices.contains((1, 1)));
+            assert!(!v0.vote_counts.contains((1, 2)));
+
+            assert_eq!(voting_contract.migrate(1), Err(Error::PollAlreadyMigrated));
+        }
+
+        #[ink::test]
+        /// Tests that `migrate` rewrites the vote counts of a poll stored with `u64` vote counts.
+        fn test_migrate_vote_counts() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+
+            // Write the poll, its list of choices and the vote counts using the storage layouts of
+            // versions 1 to 4.
+            let poll = voting_contract.polls.get(1).unwrap();
+            let mut v3 = StorageV3::new();
+            v3.polls.insert(
+                1,
+                &PollV3 {
+                    description: voting_contract.poll_descriptions.take(1).unwrap(),
+                    status: poll.status,
+                    owner: poll.owner,
+                    winner: poll.winner,
+                    start_at: poll.start_at,
+                    end_at: poll.end_at,
+                },
+            );
+            let mut v4 = StorageV4::new();
+            v4.choice_ids.insert(1, &voting_contract.choice_id_list(1));
+            let mut v1 = StorageV1::new();
+            v1.vote_counts.insert((1, 1), &u64::MAX);
+            voting_contract.poll_versions.insert(1, &1);
+
+            assert_eq!(voting_contract.migrate(1), Ok(()));
+
+            let report = voting_contract.get_report(1).unwrap();
+            assert_eq!(report.description, "test".to_string());
+            assert_eq!(report.choices[0].vote_count, u128::from(u64::MAX));
+            assert_eq!(
+                voting_contract.poll_versions.get(1),
+                Some(POLL_STORAGE_VERSION)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `migrate` fails for polls created by the current version of the contract.
+        fn test_migrate_failure_already_migrated() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.migrate(1), Err(Error::PollAlreadyMigrated));
+        }
+
+        #[ink::test]
+        /// Tests that `set_code` refuses older code versions and that `migrate` verifies and
+        /// records the version of the running code.
+        fn test_code_version() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(voting_contract.get_code_version(), CODE_VERSION);
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            assert_eq!(
+                voting_contract.set_code([0; 32], CODE_VERSION - 1),
+                Err(Error::CodeVersionTooLow)
+            );
+
+            // The running code is not the one announced by `set_code`.
+            voting_contract.pending_code_version = Some(CODE_VERSION + 1);
+            assert_eq!(voting_contract.migrate(1), Err(Error::CodeVersionMismatch));
+
+            // The running code is the one announced by `set_code`: its version is recorded even
+            // though the poll is already migrated.
+            voting_contract.code_version = CODE_VERSION - 1;
+            voting_contract.pending_code_version = Some(CODE_VERSION);
+            assert_eq!(voting_contract.migrate(1), Ok(()));
+            assert_eq!(voting_contract.get_code_version(), CODE_VERSION);
+            assert_eq!(voting_contract.pending_code_version, None);
+            assert_eq!(voting_contract.migrate(1), Err(Error::PollAlreadyMigrated));
+
+            // The running code is older than the code which last migrated the storage.
+            voting_contract.code_version = CODE_VERSION + 1;
+            assert_eq!(voting_contract.migrate(1), Err(Error::CodeVersionTooLow));
+        }
+
+        #[ink::test]
+        /// Tests that `migrate` fails if the caller is not the admin.
+        fn test_migrate_failure_not_admin() {
+            let mut voting_contract = VotingContract::default();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().bob,
+            );
+            assert_eq!(voting_contract.migrate(1), Err(Error::CallerIsNotAdmin));
+        }
+
+        #[ink::test]
+        /// Tests that `enable_proxy` fails if the caller is not the admin.
+        fn test_enable_proxy_failure_not_admin() {
+            let mut voting_contract = VotingContract::default();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().bob,
+            );
+            assert_eq!(
+                voting_contract.enable_proxy([0; 32]),
+                Err(Error::CallerIsNotAdmin)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that circuit breakers pause only the selected operations.
+        fn test_circuit_breakers() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            let circuit_breakers = CircuitBreakers {
+                pause_voting: true,
+                pause_poll_creation: true,
+                pause_upgrades: true,
+            };
+            assert_eq!(
+                voting_contract.set_circuit_breakers(circuit_breakers.clone()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.get_circuit_breakers(), circuit_breakers);
+
+            assert_eq!(voting_contract.vote(1, 1), Err(Error::VotingIsPaused));
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY),
+                Err(Error::PollCreationIsPaused)
+            );
+            assert_eq!(
+                voting_contract.set_code([0; 32], CODE_VERSION),
+                Err(Error::UpgradesArePaused)
+            );
+            assert_eq!(
+                voting_contract.enable_proxy([0; 32]),
+                Err(Error::UpgradesArePaused)
+            );
+
+            // Administrative and read-side operations are still allowed.
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert!(voting_contract.get_report(1).is_ok());
+
+            assert_eq!(
+                voting_contract.set_circuit_breakers(CircuitBreakers::default()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `set_circuit_breakers` fails if the caller is not the admin.
+        fn test_set_circuit_breakers_failure_not_admin() {
+            let mut voting_contract = VotingContract::default();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().bob,
+            );
+            assert_eq!(
+                voting_contract.set_circuit_breakers(CircuitBreakers::default()),
+                Err(Error::CallerIsNotAdmin)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that administrative actions are recorded in the admin log.
+        fn test_admin_log() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(42);
+            assert!(voting_contract.pause().is_ok());
+            assert!(voting_contract.unpause().is_ok());
+            assert!(voting_contract.change_admin(default_accounts.bob).is_ok());
+
+            let log = voting_contract.get_admin_log(0, 10).unwrap();
+            assert_eq!(log.len(), 3);
+            assert_eq!(log[0].index, 0);
+            assert_eq!(log[0].action, AdminAction::Pause);
+            assert_eq!(log[0].actor, default_accounts.alice);
+            assert_eq!(log[0].timestamp, 42);
+            assert_eq!(log[1].action, AdminAction::Unpause);
+            assert_eq!(log[2].action, AdminAction::ChangeAdmin);
+
+            let mut expected_payload_hash = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+                &default_accounts.bob,
+                &mut expected_payload_hash,
+            );
+            assert_eq!(log[2].payload_hash, expected_payload_hash);
+
+            let log = voting_contract.get_admin_log(1, 1).unwrap();
+            assert_eq!(log.len(), 1);
+            assert_eq!(log[0].action, AdminAction::Unpause);
+
+            assert_eq!(
+                voting_contract.get_admin_log(0, MAX_ADMIN_LOG_PAGE_SIZE + 1),
+                Err(Error::BatchTooLarge)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the admin log retains only the most recent `ADMIN_LOG_CAPACITY` entries.
+        fn test_admin_log_overwrites_oldest_entries() {
+            let mut voting_contract = VotingContract::default();
+
+            for _ in 0..ADMIN_LOG_CAPACITY + 2 {
+                assert!(voting_contract.pause().is_ok());
+            }
+
+            let log = voting_contract.get_admin_log(0, 2).unwrap();
+            assert_eq!(log[0].index, 2);
+            assert_eq!(log[1].index, 3);
+
+            let log = voting_contract
+                .get_admin_log(ADMIN_LOG_CAPACITY - 1, 2)
+                .unwrap();
+            assert_eq!(log.len(), 1);
+            assert_eq!(log[0].index, ADMIN_LOG_CAPACITY + 1);
+        }
+
+        #[ink::test]
+        /// Tests that only the allowed poll status transitions are accepted.
+        fn test_poll_status_transitions() {
+            use PollStatus::*;
+
+            let statuses = [
+                NotStarted,
+                Started,
+                Ended,
+                Scheduled,
+                PendingApproval,
+                Cancelled,
+            ];
+            let allowed = [
+                (PendingApproval, NotStarted),
+                (PendingApproval, Cancelled),
+                (NotStarted, Scheduled),
+                (NotStarted, Started),
+                (NotStarted, Cancelled),
+                (Scheduled, Started),
+                (Scheduled, Cancelled),
+                (Started, Ended),
+            ];
+
+            for from in statuses {
+                for to in statuses {
+                    assert_eq!(
+                        from.can_transition_to(to),
+                        allowed.contains(&(from, to)),
+                        "{from:?} -> {to:?}"
+                    );
+                }
+            }
+
+            // Statuses of existing polls keep their encoding.
+            assert_eq!(scale::Encode::encode(&NotStarted), [0]);
+            assert_eq!(scale::Encode::encode(&Started), [1]);
+            assert_eq!(scale::Encode::encode(&Ended), [2]);
+        }
+
+        #[ink::test]
+        /// Tests that descriptions containing a denied term are rejected.
+        fn test_description_denylist() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let term_hash = voting_contract.hash_term("Spam".to_string());
+            assert_eq!(voting_contract.add_denied_term(term_hash), Ok(()));
+            assert!(voting_contract.is_term_denied(term_hash));
+
+            assert_eq!(
+                voting_contract.create_poll(1, "Buy SPAM now".to_string(), 0, ONE_DAY),
+                Err(Error::DescriptionContainsDeniedTerm)
+            );
+            assert_eq!(
+                voting_contract.create_poll(1, "Spammers welcome".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "spam!".to_string()),
+                Err(Error::DescriptionContainsDeniedTerm)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.remove_denied_term(term_hash),
+                Err(Error::CallerIsNotAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.remove_denied_term(term_hash), Ok(()));
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "spam!".to_string()),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `create_poll` works properly in success scenario.
+        fn test_create_poll_success() {
+            let mut voting_contract = VotingContract::default();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            // Check if the poll has been created.
+            let poll = voting_contract.polls.get(&1).unwrap();
+            assert_eq!(
+                voting_contract.poll_descriptions.get(1).unwrap(),
+                "test".to_string()
+            );
+            assert_eq!(poll.status, PollStatus::NotStarted);
+            assert_eq!(
+                poll.owner,
+                ink::env::caller::<ink::env::DefaultEnvironment>()
+            );
+
+            let emitted_events = recorded_poll_events();
+            assert_poll_created_event(
+                &emitted_events[0],
+                1,
+                "test",
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().alice,
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `create_poll` works properly in failure scenario (contract paused).
+        fn test_create_poll_failure_contract_paused() {
+            let mut voting_contract = VotingContract::default();
+
+            assert!(voting_contract.pause().is_ok());
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Err(Error::ContractIsPaused)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `create_poll` works properly in failure scenario (duplicate poll id).
+        fn test_create_poll_failure() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.create_poll(1, "test1".to_string(), 0, ONE_DAY),
+                Err(Error::PollWithIdAlreadyExists)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `create_poll` works properly in failure scenario (end before start).
+        fn test_create_poll_failure_end_before_start() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), ONE_DAY, 0),
+                Err(Error::InvalidPollDuration)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `create_poll` works properly in failure scenario (duration too short).
+        fn test_create_poll_failure_duration_too_short() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, DEFAULT_MIN_DURATION - 1),
+                Err(Error::InvalidPollDuration)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `create_poll` works properly in failure scenario (duration too long).
+        fn test_create_poll_failure_duration_too_long() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, DEFAULT_MAX_DURATION + 1),
+                Err(Error::InvalidPollDuration)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `create_poll` and `add_choice` work properly in failure scenario
+        /// (description too long).
+        fn test_create_poll_failure_description_too_long() {
+            let mut voting_contract = VotingContract::default();
+
+            let description = "a".repeat(MAX_DESCRIPTION_LENGTH as usize + 1);
+
+            assert_eq!(
+                voting_contract.create_poll(1, description.clone(), 0, ONE_DAY),
+                Err(Error::DescriptionTooLong)
+            );
+            assert_eq!(
+                voting_contract.create_poll(1, description[1..].to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, description),
+                Err(Error::DescriptionTooLong)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `set_duration_bounds` works properly.
+        fn test_set_duration_bounds_success() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(voting_contract.set_duration_bounds(10, ONE_DAY), Ok(()));
+            assert_eq!(voting_contract.get_duration_bounds(), (10, ONE_DAY));
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, 10),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY + 1),
+                Err(Error::InvalidPollDuration)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `set_duration_bounds` fails if the bounds are invalid.
+        fn test_set_duration_bounds_failure_invalid_bounds() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.set_duration_bounds(ONE_DAY, 10),
+                Err(Error::InvalidPollDuration)
+            );
+            assert_eq!(
+                voting_contract.set_duration_bounds(0, ONE_DAY),
+                Err(Error::InvalidPollDuration)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `set_duration_bounds` fails if the caller is not the admin.
+        fn test_set_duration_bounds_failure_not_admin() {
+            let mut voting_contract = VotingContract::default();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().bob,
+            );
+            assert_eq!(
+                voting_contract.set_duration_bounds(10, ONE_DAY),
+                Err(Error::CallerIsNotAdmin)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `create_poll` fails if the transferred value is less than the poll deposit.
+        fn test_create_poll_failure_insufficient_deposit() {
+            let mut voting_contract = VotingContract::default();
+
+            assert_eq!(voting_contract.set_poll_deposit(100), Ok(()));
+            assert_eq!(voting_contract.get_poll_deposit(), 100);
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(99);
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Err(Error::InsufficientDeposit)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the deposit of a poll can be claimed by its owner once the poll has ended.
+        fn test_claim_deposit_success() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(voting_contract.set_poll_deposit(100), Ok(()));
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                contract_id,
+                1_000_000,
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            assert_eq!(voting_contract.get_deposit(1), 100);
+
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.claim_deposit(1),
+                Err(Error::PollHasNotEnded)
+            );
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(
+                voting_contract.claim_deposit(1),
+                Err(Error::OnlyOwnerCanClaimDeposit)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            let balance_before =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.bob,
+                )
+                .unwrap();
+            assert_eq!(voting_contract.claim_deposit(1), Ok(()));
+            let balance_after =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.bob,
+                )
+                .unwrap();
+
+            assert_eq!(balance_after - balance_before, 100);
+            assert_eq!(
+                voting_contract.claim_deposit(1),
+                Err(Error::NoDepositToClaim)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the messages transferring value cannot be re-entered (as a malicious callee
+        /// would do while the guard of the calling message is held) and release the guard.
+        fn test_reentrancy_guard() {
+            let mut voting_contract = VotingContract::default();
+
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(voting_contract.set_poll_deposit(100), Ok(()));
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                contract_id,
+                1_000_000,
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            // Reentrant calls are rejected while the guard is held.
+            let guard = ReentrancyGuard::enter().unwrap();
+            assert_eq!(voting_contract.claim_deposit(1), Err(Error::ReentrantCall));
+            assert_eq!(voting_contract.claim_winnings(1), Err(Error::ReentrantCall));
+            assert_eq!(
+                voting_contract.claim_registration_deposit(1),
+                Err(Error::ReentrantCall)
+            );
+            assert_eq!(
+                voting_contract.finalize_if_expired(1),
+                Err(Error::ReentrantCall)
+            );
+            assert_eq!(
+                voting_contract.resolve_challenge(1, ChallengeOutcome::Uphold),
+                Err(Error::ReentrantCall)
+            );
+            drop(guard);
+
+            // The guard is released once a message returns.
+            assert_eq!(voting_contract.claim_deposit(1), Ok(()));
+            assert_eq!(
+                voting_contract.claim_deposit(1),
+                Err(Error::NoDepositToClaim)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that an account cannot submit more votes per block than the rate limit.
+        fn test_rate_limit() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            for poll_id in 1..=3 {
+                assert_eq!(
+                    voting_contract.create_poll(poll_id, "test".to_string(), 0, ONE_DAY),
+                    Ok(())
+                );
+                assert_eq!(
+                    voting_contract.add_choice(poll_id, 1, "test1".to_string()),
+                    Ok(())
+                );
+                assert_eq!(voting_contract.start_poll(poll_id), Ok(()));
+            }
+
+            // Only the admin can set the rate limit.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_rate_limit(Some(2)),
+                Err(Error::CallerIsNotAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.set_rate_limit(Some(2)), Ok(()));
+            assert_eq!(voting_contract.get_rate_limit(), Some(2));
+
+            // The third vote of the block is rejected.
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            assert_eq!(voting_contract.vote(2, 1), Ok(1));
+            assert_eq!(voting_contract.vote(3, 1), Err(Error::TooManyRequests));
+
+            // Other accounts have their own counter.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(3, 1), Ok(1));
+
+            // The counter is reset in the next block.
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.vote(3, 1), Ok(2));
+
+            // The rate limit can be removed.
+            assert_eq!(voting_contract.set_rate_limit(None), Ok(()));
+            assert_eq!(voting_contract.get_rate_limit(), None);
+        }
+
+        #[ink::test]
+        /// Tests that milestones are reported when a choice passes a multiple of the vote
+        /// interval or takes the lead of the poll.
+        fn test_milestones() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let milestones = MilestoneConfig {
+                vote_interval: 2,
+                lead_changes: true,
+            };
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Ok(())
+            );
+
+            // Only the owner can set the milestones.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_milestones(1, Some(milestones.clone())),
+                Err(Error::OnlyOwnerCanSetMilestones)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.set_milestones(1, Some(milestones.clone())),
+                Ok(())
+            );
+            assert_eq!(voting_contract.get_milestones(1), Some(milestones));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            for (ballot_number, (voter, choice_id)) in (1..).zip([
+                (default_accounts.alice, 1),
+                (default_accounts.bob, 2),
+                (default_accounts.charlie, 2),
+                (default_accounts.django, 1),
+            ]) {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                assert_eq!(voting_contract.vote(1, choice_id), Ok(ballot_number));
+            }
+
+            let milestones: Vec<(ChoiceId, MilestoneKind)> = recorded_poll_events()
+                .iter()
+                .filter_map(|event| match decode_event::<MilestoneReached>(event) {
+                    Some(MilestoneReached {
+                        poll_id: 1,
+                        choice_id,
+                        kind,
+                        ..
+                    }) => Some((choice_id, kind)),
+                    _ => None,
+                })
+                .collect();
+
+            // A tie does not change the leader.
+            assert_eq!(
+                milestones,
+                vec![
+                    (1, MilestoneKind::TookLead),
+                    (2, MilestoneKind::VoteCount { count: 2 }),
+                    (2, MilestoneKind::TookLead),
+                    (1, MilestoneKind::VoteCount { count: 2 }),
+                ]
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the attestation of the result of a poll records its winner, counts, end
+        /// block and the root of the Merkle tree of its ballots.
+        fn test_result_attestation() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            assert_eq!(
+                voting_contract.get_result_attestation(1),
+                Err(Error::PollHasNotEnded)
+            );
+
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 2), Ok(2));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            let attestation = voting_contract.get_result_attestation(1).unwrap();
+            let attestation =
+                <ResultAttestation as scale::Decode>::decode(&mut &attestation[..]).unwrap();
+
+            let leaf = |voter: AccountId, choice_id: ChoiceId| {
+                let mut output = [0; 32];
+                ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+                    &(voter, choice_id, 1u128),
+                    &mut output,
+                );
+                output
+            };
+
+            let mut ballot_tree = MerkleTree::default();
+            ballot_tree.push(leaf(default_accounts.alice, 1));
+            ballot_tree.push(leaf(default_accounts.bob, 2));
+
+            assert_eq!(attestation.poll_id, 1);
+            assert_eq!(
+                attestation.winner,
+                voting_contract.polls.get(1).unwrap().winner
+            );
+            assert_eq!(attestation.counts, vec![(1, 1), (2, 1)]);
+            assert_eq!(
+                attestation.end_block,
+                ink::env::block_number::<ink::env::DefaultEnvironment>()
+            );
+            assert_eq!(attestation.ballot_root, ballot_tree.root());
+
+            // The configuration hash commits to the configuration of the poll.
+            let poll = voting_contract.polls.get(1).unwrap();
+            voting_contract
+                .poll_descriptions
+                .insert(1, &Description::new("changed".to_string()).unwrap());
+            assert_ne!(
+                attestation.config_hash,
+                voting_contract.config_hash(1, &poll)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that an approved operator can vote on behalf of its principal.
+        fn test_vote_for() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            // The principal (alice) approves an operator (bob).
+            assert_eq!(
+                voting_contract.approve_operator(default_accounts.alice),
+                Err(Error::CannotApproveSelf)
+            );
+            assert_eq!(
+                voting_contract.approve_operator(default_accounts.bob),
+                Ok(())
+            );
+            assert!(voting_contract.is_operator(default_accounts.alice, default_accounts.bob));
+
+            // Other accounts cannot vote on behalf of the principal.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(
+                voting_contract.vote_for(default_accounts.alice, 1, 1),
+                Err(Error::CallerIsNotOperator)
+            );
+
+            // The ballot is recorded against the principal.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.vote_for(default_accounts.alice, 1, 1),
+                Ok(1)
+            );
+            assert_eq!(
+                voting_contract.ballots.get((1, default_accounts.alice)),
+                Some(1)
+            );
+            assert!(!voting_contract.voted_by.contains((1, default_accounts.bob)));
+            assert_eq!(
+                voting_contract.vote_for(default_accounts.alice, 1, 1),
+                Err(Error::CallerAlreadyVotedOnPoll)
+            );
+
+            // The principal can revoke the operator.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.revoke_operator(default_accounts.bob),
+                Ok(())
+            );
+            assert!(!voting_contract.is_operator(default_accounts.alice, default_accounts.bob));
+        }
+
+        #[ink::test]
+        /// Tests that the dashboard of an owner lists their polls with the pending actions.
+        fn test_owner_dashboard() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            for poll_id in 1..=4 {
+                assert_eq!(
+                    voting_contract.create_poll(poll_id, "test".to_string(), 0, ONE_DAY),
+                    Ok(())
+                );
+            }
+
+            for poll_id in 2..=4 {
+                assert_eq!(
+                    voting_contract.add_choice(poll_id, 1, "test1".to_string()),
+                    Ok(())
+                );
+            }
+
+            assert_eq!(voting_contract.start_poll(3), Ok(()));
+            assert_eq!(voting_contract.start_poll(4), Ok(()));
+            assert_eq!(voting_contract.vote(3, 1), Ok(1));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(ONE_DAY);
+
+            let entry = |id, status, total_ballots, pending_actions| OwnerPollEntry {
+                id,
+                status,
+                total_ballots,
+                registered_voters: 0,
+                pending_actions,
+            };
+
+            assert_eq!(
+                voting_contract.get_owner_dashboard(default_accounts.alice),
+                OwnerDashboard {
+                    owner: default_accounts.alice,
+                    polls: vec![
+                        entry(
+                            1,
+                            PollStatus::NotStarted,
+                            0,
+                            vec![PendingAction::AddChoices]
+                        ),
+                        entry(2, PollStatus::NotStarted, 0, vec![PendingAction::Start]),
+                        entry(3, PollStatus::Started, 1, vec![PendingAction::Finalize]),
+                        entry(4, PollStatus::Started, 0, vec![PendingAction::Finalize]),
+                    ],
+                }
+            );
+
+            // Ended polls without a deposit to claim have no pending actions.
+            assert_eq!(voting_contract.end_poll(4), Ok(()));
+            assert_eq!(
+                voting_contract
+                    .get_owner_dashboard(default_accounts.alice)
+                    .polls[3],
+                entry(4, PollStatus::Ended, 0, vec![])
+            );
+
+            assert_eq!(
+                voting_contract.get_owner_dashboard(default_accounts.bob),
+                OwnerDashboard {
+                    owner: default_accounts.bob,
+                    polls: vec![],
+                }
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the constants report the limits and versions of the contract.
+        fn test_constants() {
+            let voting_contract = VotingContract::default();
+
+            let constants = voting_contract.constants();
+
+            assert_eq!(constants.max_choices, 1 << 32);
+            assert_eq!(constants.max_choice_batch_size, MAX_CHOICE_BATCH_SIZE);
+            assert_eq!(
+                constants.max_description_length,
+                Some(MAX_DESCRIPTION_LENGTH)
+            );
+            assert_eq!(
+                constants.max_eligible_voter_chunk_size,
+                MAX_ELIGIBLE_VOTER_CHUNK_SIZE
+            );
+            assert_eq!(constants.poll_storage_version, POLL_STORAGE_VERSION);
+            assert_eq!(constants.event_schema_version, EVENT_SCHEMA_VERSION);
+            assert_eq!(constants.tally_strategies, 0b11111);
+        }
+
+        #[ink::test]
+        /// Tests that new polls inherit the default configuration set by the admin.
+        fn test_default_poll_config() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let config = PollConfig {
+                visibility: PollVisibility::Private,
+                tally_strategy: TallyStrategy::Borda,
+                hide_results_until_end: true,
+                challenge_period: ONE_DAY,
+            };
+
+            assert_eq!(
+                voting_contract.get_default_poll_config(),
+                PollConfig::default()
+            );
+
+            // Only the admin can set the default configuration.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_default_poll_config(config.clone()),
+                Err(Error::CallerIsNotAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.set_default_poll_config(PollConfig {
+                    tally_strategy: TallyStrategy::Stv { seats: 0 },
+                    ..config.clone()
+                }),
+                Err(Error::InvalidSeatCount)
+            );
+            assert_eq!(
+                voting_contract.set_default_poll_config(config.clone()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.get_default_poll_config(), config);
+
+            // New polls inherit the default configuration.
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.get_poll_visibility(1),
+                PollVisibility::Private
+            );
+            assert_eq!(voting_contract.get_tally_strategy(1), TallyStrategy::Borda);
+            assert!(voting_contract.hides_results_until_end(1));
+            assert_eq!(voting_contract.get_challenge_period(1), ONE_DAY);
+
+            // The owner can override it before the poll starts.
+            assert_eq!(
+                voting_contract.set_tally_strategy(1, TallyStrategy::Plurality),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.get_tally_strategy(1),
+                TallyStrategy::Plurality
+            );
+        }
+
+        #[ink::test]
+        /// Tests that accepted ballots are numbered sequentially within each poll.
+        fn test_ballot_numbers() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            for poll_id in 1..=2 {
+                assert_eq!(
+                    voting_contract.create_poll(poll_id, "test".to_string(), 0, ONE_DAY),
+                    Ok(())
+                );
+                assert_eq!(
+                    voting_contract.add_choice(poll_id, 1, "test1".to_string()),
+                    Ok(())
+                );
+                assert_eq!(voting_contract.start_poll(poll_id), Ok(()));
+            }
+
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            assert_eq!(voting_contract.vote(2, 1), Ok(1));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 1), Ok(2));
+
+            // Rejected ballots are not numbered.
+            assert_eq!(
+                voting_contract.vote(1, 1),
+                Err(Error::CallerAlreadyVotedOnPoll)
+            );
+
+            assert_eq!(
+                voting_contract.get_ballot_number(1, default_accounts.alice),
+                Some(1)
+            );
+            assert_eq!(
+                voting_contract.get_ballot_number(1, default_accounts.bob),
+                Some(2)
+            );
+            assert_eq!(
+                voting_contract.get_ballot_number(2, default_accounts.bob),
+                None
+            );
+
+            let emitted_events = recorded_poll_events();
+            assert_vote_cast_event(emitted_events.last().unwrap(), 1, default_accounts.bob, 2);
+        }
+
+        #[ink::test]
+        /// Tests that the keeper bounty funded by the owner is paid to the account finalizing the
+        /// poll with `finalize_if_expired`, and reclaimed by the owner otherwise.
+        fn test_keeper_bounty() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+
+            for poll_id in 1..=2 {
+                assert_eq!(
+                    voting_contract.create_poll(poll_id, "test".to_string(), 0, ONE_DAY),
+                    Ok(())
+                );
+                assert_eq!(
+                    voting_contract.add_choice(poll_id, 1, "test1".to_string()),
+                    Ok(())
+                );
+                assert_eq!(voting_contract.start_poll(poll_id), Ok(()));
+            }
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                contract_id,
+                1_000_000,
+            );
+
+            // Only the owner of the poll can fund the keeper bounty.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(40);
+            assert_eq!(
+                voting_contract.fund_keeper_bounty(1),
+                Err(Error::OnlyOwnerCanFundKeeperBounty)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.fund_keeper_bounty(1), Ok(()));
+            assert_eq!(voting_contract.fund_keeper_bounty(2), Ok(()));
+            assert_eq!(voting_contract.get_keeper_bounty(1), 40);
+            assert_eq!(voting_contract.get_keeper_claim(1), None);
+
+            // The bounty cannot be reclaimed before the poll ends.
+            assert_eq!(
+                voting_contract.reclaim_keeper_bounty(2),
+                Err(Error::PollHasNotEnded)
+            );
+            assert_eq!(voting_contract.end_poll(2), Ok(()));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(ONE_DAY);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            let balance_before =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.bob,
+                )
+                .unwrap();
+            assert_eq!(voting_contract.finalize_if_expired(1), Ok(()));
+            let balance_after =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.bob,
+                )
+                .unwrap();
+
+            assert_eq!(balance_after - balance_before, 40);
+            assert_eq!(voting_contract.get_keeper_bounty(1), 0);
+            assert_eq!(
+                voting_contract.get_keeper_claim(1),
+                Some(KeeperClaim {
+                    keeper: default_accounts.bob,
+                    amount: 40,
+                })
+            );
+
+            // The bounty of the poll ended by its owner is returned to the owner.
+            assert_eq!(
+                voting_contract.reclaim_keeper_bounty(2),
+                Err(Error::OnlyOwnerCanFundKeeperBounty)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.reclaim_keeper_bounty(2), Ok(()));
+            assert_eq!(voting_contract.get_keeper_bounty(2), 0);
+            assert_eq!(
+                voting_contract.reclaim_keeper_bounty(2),
+                Err(Error::NothingToClaim)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the list of eligible voters of a poll can be uploaded in chunks and must be
+        /// sealed before the poll starts.
+        fn test_eligible_voters_chunk() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+
+            let chunk =
+                vec![AccountId::from([0x10; 32]); MAX_ELIGIBLE_VOTER_CHUNK_SIZE as usize + 1];
+            assert_eq!(
+                voting_contract.add_eligible_voters_chunk(1, chunk),
+                Err(Error::BatchTooLarge)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.add_eligible_voters_chunk(1, vec![default_accounts.bob]),
+                Err(Error::OnlyOwnerCanSetVoterWeights)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+
+            // Accounts already on the list are not counted twice.
+            assert_eq!(
+                voting_contract.add_eligible_voters_chunk(
+                    1,
+                    vec![default_accounts.bob, default_accounts.charlie]
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_eligible_voters_chunk(
+                    1,
+                    vec![default_accounts.charlie, default_accounts.django]
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.get_voter_list_upload(1),
+                Some(VoterListUpload {
+                    count: 3,
+                    sealed: false,
+                })
+            );
+            assert_eq!(voting_contract.get_voter_weight(1, default_accounts.bob), 1);
+            assert_eq!(voting_contract.get_voter_weight(1, default_accounts.eve), 0);
+
+            // The poll cannot start until the list is sealed.
+            assert_eq!(
+                voting_contract.start_poll(1),
+                Err(Error::VoterListIsNotSealed)
+            );
+            assert_eq!(voting_contract.seal_voter_list(1), Ok(()));
+            assert_eq!(
+                voting_contract.seal_voter_list(1),
+                Err(Error::VoterListIsSealed)
+            );
+            assert_eq!(
+                voting_contract.add_eligible_voters_chunk(1, vec![default_accounts.eve]),
+                Err(Error::VoterListIsSealed)
+            );
+            assert_eq!(
+                voting_contract.set_voter_weights(1, vec![(default_accounts.eve, 1)]),
+                Err(Error::VoterListIsSealed)
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            // Only the accounts on the list can vote.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.eve);
+            assert_eq!(
+                voting_contract.vote(1, 1),
+                Err(Error::CallerHasNoVotingWeight)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the eligibility of the members of a poll is stored as a paged bitmap.
+        fn test_eligible_members() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+
+            // Members 0, 9 and 256 are eligible.
+            let mut bits = [0; 32];
+            bits[0] = 0b1;
+            bits[1] = 0b10;
+            assert_eq!(voting_contract.set_eligible_members(1, 0, bits), Ok(()));
+            assert_eq!(
+                voting_contract.set_eligible_members(1, 1, [0xFF; 32]),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.set_eligible_members(1, 1, [0b1; 32]),
+                Ok(())
+            );
+
+            assert!(voting_contract.is_eligible_member(1, 0));
+            assert!(!voting_contract.is_eligible_member(1, 1));
+            assert!(voting_contract.is_eligible_member(1, 9));
+            assert!(voting_contract.is_eligible_member(1, 256));
+            assert!(!voting_contract.is_eligible_member(1, 257));
+            assert!(voting_contract.is_eligible_member(1, 264));
+            assert!(!voting_contract.is_eligible_member(1, 512));
+            assert!(!voting_contract.is_eligible_member(2, 0));
+
+            // Each eligible member counts towards the eligible weight.
+            assert_eq!(voting_contract.set_quorum_weight_bps(1, 10_000), Ok(()));
+            assert_eq!(voting_contract.get_quorum(1).unwrap().eligible_weight, 34);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_eligible_members(1, 0, bits),
+                Err(Error::OnlyOwnerCanSetVoterWeights)
+            );
+            assert_eq!(
+                voting_contract.set_member_registry(Some(default_accounts.charlie)),
+                Err(Error::CallerIsNotAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.set_eligible_members(1, 0, bits),
+                Err(Error::PollHasStarted)
+            );
+
+            // Only eligible members can vote, through the member registry.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.vote_as_member(1, 1, 1),
+                Err(Error::CallerHasNoVotingWeight)
+            );
+            assert_eq!(
+                voting_contract.vote_as_member(1, 0, 1),
+                Err(Error::NoMemberRegistry)
+            );
+            assert_eq!(
+                voting_contract.vote(1, 1),
+                Err(Error::CallerHasNoVotingWeight)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.set_member_registry(Some(default_accounts.charlie)),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.get_member_registry(),
+                Some(default_accounts.charlie)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the ballots cast on a poll are counted in hourly buckets.
+        fn test_vote_timeline() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.get_vote_timeline(1), vec![]);
+
+            let voters = [
+                (default_accounts.alice, 10),
+                (default_accounts.bob, TIMELINE_BUCKET_DURATION - 1),
+                (default_accounts.charlie, 3 * TIMELINE_BUCKET_DURATION + 5),
+            ];
+
+            for (voter, timestamp) in voters {
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(timestamp);
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                assert!(voting_contract.vote(1, 1).is_ok());
+            }
+
+            // Rejected ballots are not counted.
+            assert_eq!(
+                voting_contract.vote(1, 1),
+                Err(Error::CallerAlreadyVotedOnPoll)
+            );
+
+            assert_eq!(
+                voting_contract.get_vote_timeline(1),
+                vec![
+                    TimelineBucket {
+                        start_at: 0,
+                        ballots: 2,
+                    },
+                    TimelineBucket {
+                        start_at: 3 * TIMELINE_BUCKET_DURATION,
+                        ballots: 1,
+                    },
+                ]
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the state of a poll is exported in chunks.
+        fn test_export_state() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            let choices = (1..=MAX_EXPORT_CHUNK_SIZE)
+                .map(|choice_id| (choice_id, format!("test{choice_id}")))
+                .collect();
+            assert_eq!(voting_contract.add_choices(1, choices), Ok(()));
+            assert_eq!(voting_contract.set_public_ballots(1, true), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert!(voting_contract.vote(1, 2).is_ok());
+
+            let decode = |chunk: Vec<u8>| -> Vec<ExportRecord> {
+                scale::Decode::decode(&mut &chunk[..]).unwrap()
+            };
+
+            // The first chunk holds the poll and all but the last choice.
+            let (chunk, cursor) = voting_contract.export_state(1, 0).unwrap();
+            let records = decode(chunk);
+
+            assert_eq!(records.len(), MAX_EXPORT_CHUNK_SIZE as usize);
+            assert!(matches!(
+                &records[0],
+                ExportRecord::Poll { description, .. } if description == "test"
+            ));
+            assert!(matches!(
+                &records[2],
+                ExportRecord::Choice(ChoiceReport {
+                    id: 2,
+                    vote_count: 1,
+                    ..
+                })
+            ));
+            assert_eq!(cursor, Some(MAX_EXPORT_CHUNK_SIZE));
+
+            // The second chunk holds the last choice and the public ballot.
+            let (chunk, cursor) = voting_contract.export_state(1, cursor.unwrap()).unwrap();
+            let records = decode(chunk);
+
+            assert_eq!(records.len(), 2);
+            assert!(matches!(
+                &records[0],
+                ExportRecord::Choice(ChoiceReport { id, .. }) if *id == MAX_EXPORT_CHUNK_SIZE
+            ));
+            assert_eq!(
+                records[1],
+                ExportRecord::Ballot {
+                    voter: default_accounts.alice,
+                    choice_id: 2,
+                }
+            );
+            assert_eq!(cursor, None);
+
+            // Cursors past the end return an empty chunk.
+            let (chunk, cursor) = voting_contract.export_state(1, 100).unwrap();
+            assert!(decode(chunk).is_empty());
+            assert_eq!(cursor, None);
+
+            assert_eq!(
+                voting_contract.export_state(2, 0),
+                Err(Error::PollWithIdDoesNotExist)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that historical polls can only be imported while migration mode is active, and
+        /// that migration mode can only be entered once.
+        fn test_import_poll() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let poll = || Poll {
+                status: PollStatus::Ended,
+                owner: default_accounts.bob,
+                winner: Some(2),
+                start_at: 0,
+                end_at: ONE_DAY,
+            };
+            let choices = || vec![(1, "test1".to_string()), (2, "test2".to_string())];
+            let counts = || vec![(1, 3), (2, 5)];
+
+            assert_eq!(
+                voting_contract.import_poll(1, poll(), "imported".to_string(), choices(), counts()),
+                Err(Error::MigrationModeIsNotActive)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.enter_migration_mode(),
+                Err(Error::CallerIsNotAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.enter_migration_mode(), Ok(()));
+            assert_eq!(voting_contract.get_migration_mode(), MigrationMode::Active);
+
+            // Counts must refer to imported choices.
+            assert_eq!(
+                voting_contract.import_poll(
+                    1,
+                    poll(),
+                    "imported".to_string(),
+                    choices(),
+                    vec![(3, 1)]
+                ),
+                Err(Error::ChoiceWithIdDoesNotExist)
+            );
+            assert_eq!(
+                voting_contract.import_poll(1, poll(), "imported".to_string(), choices(), counts()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.import_poll(1, poll(), "imported".to_string(), choices(), counts()),
+                Err(Error::PollWithIdAlreadyExists)
+            );
+
+            let report = voting_contract.get_report(1).unwrap();
+            assert_eq!(report.owner, default_accounts.bob);
+            assert_eq!(report.status, PollStatus::Ended);
+            assert_eq!(report.winner, Some(2));
+            assert_eq!(report.choices[1].vote_count, 5);
+            assert_eq!(
+                voting_contract
+                    .get_owner_dashboard(default_accounts.bob)
+                    .polls
+                    .len(),
+                1
+            );
+
+            let emitted_events = recorded_poll_events();
+            let mut data_hash = [0; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+                &(&poll(), "imported", &choices(), &counts()),
+                &mut data_hash,
+            );
+            assert!(matches!(
+                decode_event::<PollImported>(emitted_events.last().unwrap()),
+                Some(PollImported {
+                    poll_id: 1,
+                    owner,
+                    data_hash: event_hash,
+                    ..
+                }) if owner == default_accounts.bob && event_hash == data_hash
+            ));
+
+            // Migration mode cannot be entered again once exited.
+            assert_eq!(voting_contract.exit_migration_mode(), Ok(()));
+            assert_eq!(voting_contract.get_migration_mode(), MigrationMode::Closed);
+            assert_eq!(
+                voting_contract.import_poll(2, poll(), "imported".to_string(), choices(), counts()),
+                Err(Error::MigrationModeIsNotActive)
+            );
+            assert_eq!(
+                voting_contract.enter_migration_mode(),
+                Err(Error::MigrationModeUnavailable)
+            );
+            assert_eq!(
+                voting_contract.exit_migration_mode(),
+                Err(Error::MigrationModeIsNotActive)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `change_admin` rejects the account of the contract, the zero account and the
+        /// accounts of the denylist of admins.
+        fn test_change_admin_failure_invalid_target() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(voting_contract.is_admin(default_accounts.alice));
+            assert!(!voting_contract.is_admin(default_accounts.bob));
+
+            let contract = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            assert_eq!(
+                voting_contract.change_admin(contract),
+                Err(Error::InvalidAdmin)
+            );
+            assert_eq!(
+                voting_contract.change_admin(AccountId::from([0; 32])),
+                Err(Error::InvalidAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.add_denied_admin(default_accounts.bob),
+                Err(Error::CallerIsNotAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.add_denied_admin(default_accounts.bob),
+                Ok(())
+            );
+            assert!(voting_contract.is_admin_denied(default_accounts.bob));
+            assert_eq!(
+                voting_contract.change_admin(default_accounts.bob),
+                Err(Error::InvalidAdmin)
+            );
+
+            assert_eq!(
+                voting_contract.remove_denied_admin(default_accounts.bob),
+                Ok(())
+            );
+            assert!(!voting_contract.is_admin_denied(default_accounts.bob));
+            assert_eq!(voting_contract.change_admin(default_accounts.bob), Ok(()));
+            assert!(voting_contract.is_admin(default_accounts.bob));
+            assert!(!voting_contract.is_admin(default_accounts.alice));
+        }
+
+        #[ink::test]
+        /// Tests that the finalizer appointed by the owner of a poll can end the poll.
+        fn test_set_finalizer() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_finalizer(1, Some(default_accounts.bob)),
+                Err(Error::OnlyOwnerCanSetFinalizer)
+            );
+            assert_eq!(voting_contract.end_poll(1), Err(Error::OnlyOwnerCanEndPoll));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.set_finalizer(1, Some(default_accounts.bob)),
+                Ok(())
+            );
+            assert_eq!(voting_contract.get_finalizer(1), Some(default_accounts.bob));
+
+            let emitted_events = recorded_poll_events();
+            assert!(matches!(
+                decode_event::<FinalizerChanged>(emitted_events.last().unwrap()),
+                Some(FinalizerChanged {
+                    poll_id: 1,
+                    finalizer: Some(finalizer),
+                    ..
+                }) if finalizer == default_accounts.bob
+            ));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            // The finalizer of an ended poll can no longer be changed.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.set_finalizer(1, None),
+                Err(Error::PollHasEnded)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the tenure schedule can only be set by the admin and boosts weights by full
+        /// years of membership up to its maximum bonus.
+        fn test_tenure_schedule() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let schedule = TenureSchedule {
+                registry: default_accounts.django,
+                bonus_bps_per_year: 1_000,
+                max_bonus_bps: 3_000,
+            };
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_tenure_schedule(Some(schedule.clone())),
+                Err(Error::CallerIsNotAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert!(voting_contract.get_tenure_schedule().is_none());
+            assert_eq!(
+                voting_contract.set_tenure_schedule(Some(schedule.clone())),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.get_tenure_schedule(),
+                Some(schedule.clone())
+            );
+
+            assert_eq!(schedule.boost(100, 0), 100);
+            assert_eq!(schedule.boost(100, TENURE_YEAR - 1), 100);
+            assert_eq!(schedule.boost(100, TENURE_YEAR), 110);
+            assert_eq!(schedule.boost(100, 5 * TENURE_YEAR / 2), 120);
+            assert_eq!(schedule.boost(100, 10 * TENURE_YEAR), 130);
+            assert_eq!(schedule.boost(u128::MAX, 10 * TENURE_YEAR), u128::MAX);
+
+            assert_eq!(voting_contract.set_tenure_schedule(None), Ok(()));
+            assert!(voting_contract.get_tenure_schedule().is_none());
+        }
+
+        #[ink::test]
+        /// Tests that polls can't be started once the maximum number of active polls is reached,
+        /// until an active poll ends.
+        fn test_max_active_polls() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            for poll_id in 1..=3 {
+                assert_eq!(
+                    voting_contract.create_poll(poll_id, "test".to_string(), 0, ONE_DAY),
+                    Ok(())
+                );
+                assert_eq!(
+                    voting_contract.add_choice(poll_id, 1, "test1".to_string()),
+                    Ok(())
+                );
+            }
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_max_active_polls(Some(1)),
+                Err(Error::CallerIsNotAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.set_max_active_polls(Some(1)), Ok(()));
+            assert_eq!(voting_contract.get_max_active_polls(), Some(1));
+
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.get_active_poll_count(), 1);
+            assert_eq!(
+                voting_contract.start_poll(2),
+                Err(Error::TooManyActivePolls)
+            );
+
+            // Ending an active poll frees its slot.
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(voting_contract.get_active_poll_count(), 0);
+            assert_eq!(voting_contract.start_poll(2), Ok(()));
+
+            // Removing an active poll frees its slot as well.
+            assert_eq!(
+                voting_contract.add_moderator(default_accounts.alice),
+                Ok(())
+            );
+            assert_eq!(voting_contract.remove_poll(2), Ok(()));
+            assert_eq!(voting_contract.get_active_poll_count(), 0);
+
+            assert_eq!(voting_contract.set_max_active_polls(None), Ok(()));
+            assert_eq!(voting_contract.start_poll(3), Ok(()));
+            assert_eq!(voting_contract.get_active_poll_count(), 1);
+        }
+
+        #[ink::test]
+        /// Tests that archiving an ended poll prunes its choices and keeps a record of its result.
+        fn test_archive_poll() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract
+                    .add_choices(1, vec![(1, "test1".to_string()), (2, "test2".to_string())]),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            assert_eq!(voting_contract.archive_poll(1), Err(Error::PollHasNotEnded));
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            let counts = voting_contract.get_vote_counts(1);
+            let winner = voting_contract.get_report(1).unwrap().winner;
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.archive_poll(1),
+                Err(Error::OnlyOwnerCanArchivePoll)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.get_result_record(1), None);
+            assert_eq!(voting_contract.archive_poll(1), Ok(()));
+            assert_eq!(voting_contract.archive_poll(1), Err(Error::PollIsArchived));
+
+            let mut per_choice_totals_hash = [0; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+                &counts,
+                &mut per_choice_totals_hash,
+            );
+            assert_eq!(
+                voting_contract.get_result_record(1),
+                Some(ResultRecord {
+                    winner,
+                    total_votes: 1,
+                    per_choice_totals_hash,
+                    ended_at: 0,
+                })
+            );
+
+            let report = voting_contract.get_report(1).unwrap();
+            assert_eq!(report.status, PollStatus::Ended);
+            assert!(report.choices.is_empty());
+            assert!(!voting_contract.has_choice(1, 1));
+        }
+
+        #[ink::test]
+        /// Tests that the owner of a poll can delete it until a choice is added or it starts,
+        /// and that the id of a deleted poll can't be reused.
+        fn test_delete_poll() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "tset".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(2, 1, "test1".to_string()),
+                Ok(())
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.delete_poll(1),
+                Err(Error::OnlyOwnerCanDeletePoll)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.delete_poll(2), Err(Error::PollIsInUse));
+            assert_eq!(voting_contract.delete_poll(1), Ok(()));
+
+            assert!(!voting_contract.polls.contains(1));
+            assert!(!voting_contract.poll_descriptions.contains(1));
+            assert!(!voting_contract.poll_versions.contains(1));
+            assert_eq!(voting_contract.poll_count, 1);
+            assert_eq!(
+                voting_contract
+                    .get_owner_dashboard(default_accounts.alice)
+                    .polls
+                    .len(),
+                1
+            );
+
+            let emitted_events = recorded_poll_events();
+            assert!(matches!(
+                decode_event::<PollDeleted>(emitted_events.last().unwrap()),
+                Some(PollDeleted {
+                    poll_id: 1,
+                    owner,
+                    ..
+                }) if owner == default_accounts.alice
+            ));
+
+            assert_eq!(
+                voting_contract.delete_poll(1),
+                Err(Error::PollWithIdDoesNotExist)
+            );
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Err(Error::PollWithIdAlreadyExists)
+            );
+
+            // Started polls can't be deleted.
+            assert_eq!(voting_contract.start_poll(2), Ok(()));
+            assert_eq!(voting_contract.delete_poll(2), Err(Error::PollHasStarted));
+        }
+
+        #[ink::test]
+        /// Tests that the choices of a poll with a choice capacity can't receive more votes than
+        /// their capacity, and that the report exposes their remaining capacity.
+        fn test_choice_capacity() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Ok(())
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_choice_capacity(1, Some(1)),
+                Err(Error::OnlyOwnerCanSetChoiceCapacity)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.set_choice_capacity(1, Some(1)), Ok(()));
+            assert_eq!(voting_contract.get_choice_capacity(1), Some(1));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.set_choice_capacity(1, None),
+                Err(Error::PollHasStarted)
+            );
+
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.vote(1, 1),
+                Err(Error::ChoiceCapacityReached)
+            );
+            assert_eq!(voting_contract.vote(1, 2), Ok(2));
+
+            let report = voting_contract.get_report(1).unwrap();
+            assert_eq!(report.choices[0].remaining_capacity, Some(0));
+            assert_eq!(report.choices[1].remaining_capacity, Some(0));
+        }
+
+        #[ink::test]
+        /// Tests that voters can wait for a seat on a full choice, and that the first waitlisted
+        /// voter is voted in when a vote for the choice is retracted.
+        fn test_waitlist() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_choice_capacity(1, Some(1)), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            assert_eq!(voting_contract.vote_or_wait(1, 1), Ok(Some(1)));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.retract_vote(1),
+                Err(Error::CallerHasNotVotedOnPoll)
+            );
+            assert_eq!(voting_contract.vote_or_wait(1, 1), Ok(None));
+            assert_eq!(
+                voting_contract.vote_or_wait(1, 1),
+                Err(Error::CallerAlreadyWaitlisted)
+            );
+            assert_eq!(
+                voting_contract.get_waitlist(1, 1),
+                vec![default_accounts.bob]
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(voting_contract.vote_or_wait(1, 2), Ok(Some(2)));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.retract_vote(1), Ok(()));
+
+            // The seat freed by the retracted vote is given to the waitlisted voter.
+            assert_eq!(voting_contract.get_waitlist(1, 1), vec![]);
+            assert_eq!(
+                voting_contract.ballots.get((1, default_accounts.bob)),
+                Some(1)
+            );
+            assert!(!voting_contract
+                .voted_by
+                .contains((1, default_accounts.alice)));
+            assert_eq!(voting_contract.vote_counts.get((1, 1)), Some(1));
+
+            let emitted_events = recorded_poll_events();
+            assert!(matches!(
+                decode_event::<VoterPromoted>(emitted_events.last().unwrap()),
+                Some(VoterPromoted {
+                    poll_id: 1,
+                    voter,
+                    choice_id: 1,
+                    ballot_number: 3,
+                    ..
+                }) if voter == default_accounts.bob
+            ));
+
+            // The choice is full again.
+            assert_eq!(
+                voting_contract.vote(1, 1),
+                Err(Error::ChoiceCapacityReached)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the questions of a poll with multiple questions are answered in a single
+        /// ballot and tallied separately.
+        fn test_vote_ballot() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_question(
+                    1,
+                    1,
+                    "question1".to_string(),
+                    vec![(1, "test1".to_string()), (2, "test2".to_string())]
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_question(
+                    1,
+                    2,
+                    "question2".to_string(),
+                    vec![(3, "test3".to_string()), (4, "test4".to_string())]
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_question(1, 2, "question3".to_string(), vec![]),
+                Err(Error::QuestionWithIdAlreadyExists)
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            assert_eq!(voting_contract.vote(1, 1), Err(Error::PollRequiresBallot));
+            assert_eq!(
+                voting_contract.vote_ballot(1, vec![(1, Answer::Choice(3))]),
+                Err(Error::InvalidAnswers)
+            );
+            assert_eq!(
+                voting_contract
+                    .vote_ballot(1, vec![(1, Answer::Choice(1)), (1, Answer::Choice(2))]),
+                Err(Error::InvalidAnswers)
+            );
+            assert_eq!(
+                voting_contract
+                    .vote_ballot(1, vec![(1, Answer::Choice(1)), (2, Answer::Choice(4))]),
+                Ok(1)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.vote_ballot(1, vec![(2, Answer::Choice(4))]),
+                Ok(2)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(
+                voting_contract
+                    .vote_ballot(1, vec![(1, Answer::Choice(2)), (2, Answer::Choice(3))]),
+                Ok(3)
+            );
+            assert_eq!(
+                voting_contract.vote_ballot(1, vec![(1, Answer::Choice(2))]),
+                Err(Error::CallerAlreadyVotedOnPoll)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            let report = voting_contract.get_report(1).unwrap();
+            assert_eq!(report.questions.len(), 2);
+            assert_eq!(report.questions[0].choice_ids, vec![1, 2]);
+            assert_eq!(report.questions[0].winner, None);
+            assert_eq!(report.questions[1].choice_ids, vec![3, 4]);
+            assert_eq!(report.questions[1].winner, Some(4));
+            assert_eq!(voting_contract.get_vote_count(1, 4), 2);
+
+            // Polls without questions do not accept ballots.
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(2, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(2), Ok(()));
+            assert_eq!(
+                voting_contract.vote_ballot(2, vec![(1, Answer::Choice(1))]),
+                Err(Error::PollDoesNotAcceptBallots)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that numeric questions accept values within their range and report the
+        /// statistics of the answers.
+        fn test_numeric_question() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_numeric_question(1, 1, "budget".to_string(), 100, 0),
+                Err(Error::InvalidNumericRange)
+            );
+            assert_eq!(
+                voting_contract.add_numeric_question(1, 1, "budget".to_string(), 0, 100),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_numeric_question(1, 2, "fee".to_string(), 0, 999),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            assert_eq!(
+                voting_contract.vote_ballot(1, vec![(1, Answer::Value(101))]),
+                Err(Error::InvalidAnswers)
+            );
+            assert_eq!(
+                voting_contract.vote_ballot(1, vec![(1, Answer::Choice(0))]),
+                Err(Error::InvalidAnswers)
+            );
+            assert_eq!(
+                voting_contract
+                    .vote_ballot(1, vec![(1, Answer::Value(10)), (2, Answer::Value(13))]),
+                Ok(1)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.vote_ballot(1, vec![(1, Answer::Value(40))]),
+                Ok(2)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(
+                voting_contract.vote_ballot(1, vec![(1, Answer::Value(100))]),
+                Ok(3)
+            );
+
+            let report = voting_contract.get_report(1).unwrap();
+            assert_eq!(
+                report.questions[0].statistics,
+                Some(NumericStatistics {
+                    count: 3,
+                    sum: 150,
+                    mean: Some(50),
+                    median: Some(40),
+                    weighted_median: None,
+                })
+            );
+
+            // The answers to questions with wide ranges are counted in buckets of 8 values.
+            assert_eq!(
+                report.questions[1].statistics,
+                Some(NumericStatistics {
+                    count: 1,
+                    sum: 13,
+                    mean: Some(13),
+                    median: Some(8),
+                    weighted_median: None,
+                })
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the numeric questions of polls tallied by weighted median resolve to the
+        /// median of their answers weighted by the weight of the voters.
+        fn test_weighted_median_tally() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_numeric_question(1, 1, "budget".to_string(), 0, 100),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.set_tally_strategy(1, TallyStrategy::WeightedMedian),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.set_voter_weights(
+                    1,
+                    vec![
+                        (default_accounts.alice, 1),
+                        (default_accounts.bob, 2),
+                        (default_accounts.charlie, 5),
+                    ]
+                ),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            assert_eq!(
+                voting_contract.vote_ballot(1, vec![(1, Answer::Value(10))]),
+                Ok(1)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.vote_ballot(1, vec![(1, Answer::Value(20))]),
+                Ok(2)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(
+                voting_contract.vote_ballot(1, vec![(1, Answer::Value(90))]),
+                Ok(3)
+            );
+
+            // The heaviest voter outweighs the two others, while the unweighted median is
+            // unaffected by the weights.
+            let report = voting_contract.get_report(1).unwrap();
+            assert_eq!(
+                report.questions[0].statistics,
+                Some(NumericStatistics {
+                    count: 3,
+                    sum: 120,
+                    mean: Some(40),
+                    median: Some(20),
+                    weighted_median: Some(90),
+                })
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the result of a poll can be challenged during its challenge period and
+        /// overturned by a resolver.
+        fn test_challenge_result() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(voting_contract.set_challenge_deposit(100), Ok(()));
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_challenge_period(1, ONE_DAY), Ok(()));
+            assert_eq!(voting_contract.get_challenge_period(1), ONE_DAY);
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.get_result_status(1),
+                Err(Error::PollHasNotEnded)
+            );
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.get_result_status(1),
+                Ok(ResultStatus::Provisional)
+            );
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                contract_id,
+                1_000_000,
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(99);
+            assert_eq!(
+                voting_contract.challenge_result(1),
+                Err(Error::InsufficientDeposit)
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(voting_contract.challenge_result(1), Ok(()));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            assert_eq!(
+                voting_contract.get_result_status(1),
+                Ok(ResultStatus::Challenged)
+            );
+            assert_eq!(
+                voting_contract.get_challenge(1),
+                Some(Challenge {
+                    challenger: default_accounts.bob,
+                    deposit: 100,
+                })
+            );
+            assert_eq!(
+                voting_contract.challenge_result(1),
+                Err(Error::ResultIsNotProvisional)
+            );
+            assert_eq!(
+                voting_contract.resolve_challenge(1, ChallengeOutcome::Uphold),
+                Err(Error::CallerIsNotResolver)
+            );
+
+            // The challenge stays pending after the end of the challenge period.
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(ONE_DAY);
+            assert_eq!(
+                voting_contract.get_result_status(1),
+                Ok(ResultStatus::Challenged)
+            );
+
+            let balance_before =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.bob,
+                )
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.add_resolver(default_accounts.charlie),
+                Ok(())
+            );
+            assert!(voting_contract.is_resolver(default_accounts.charlie));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(
+                voting_contract.resolve_challenge(1, ChallengeOutcome::Overturn { winner: None }),
+                Ok(())
+            );
+            let balance_after =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.bob,
+                )
+                .unwrap();
+            assert_eq!(balance_after - balance_before, 100);
+            assert_eq!(
+                voting_contract.get_result_status(1),
+                Ok(ResultStatus::Final)
+            );
+            assert_eq!(voting_contract.polls.get(1).unwrap().winner, None);
+            assert_eq!(
+                voting_contract.resolve_challenge(1, ChallengeOutcome::Uphold),
+                Err(Error::ResultIsNotChallenged)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that resolving a challenge slashes the deposit if the result is upheld and
+        /// refunds it if the poll is voided.
+        fn test_resolve_challenge_uphold_and_void() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(voting_contract.set_challenge_deposit(100), Ok(()));
+            assert_eq!(voting_contract.add_resolver(default_accounts.alice), Ok(()));
+
+            for poll_id in [1, 2] {
+                assert_eq!(
+                    voting_contract.create_poll(poll_id, "test".to_string(), 0, ONE_DAY),
+                    Ok(())
+                );
+                assert_eq!(
+                    voting_contract.add_choice(poll_id, 1, "test1".to_string()),
+                    Ok(())
+                );
+                assert_eq!(
+                    voting_contract.set_challenge_period(poll_id, ONE_DAY),
+                    Ok(())
+                );
+                assert_eq!(voting_contract.start_poll(poll_id), Ok(()));
+                assert_eq!(voting_contract.vote(poll_id, 1), Ok(1));
+                assert_eq!(voting_contract.end_poll(poll_id), Ok(()));
+            }
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                contract_id,
+                1_000_000,
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(voting_contract.challenge_result(1), Ok(()));
+            assert_eq!(voting_contract.challenge_result(2), Ok(()));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            let bob_balance = || {
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.bob,
+                )
+                .unwrap()
+            };
+            let balance_before = bob_balance();
+            let winner = voting_contract.polls.get(1).unwrap().winner;
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.resolve_challenge(1, ChallengeOutcome::Uphold),
+                Ok(())
+            );
+            assert_eq!(bob_balance(), balance_before);
+            assert_eq!(
+                voting_contract.get_result_status(1),
+                Ok(ResultStatus::Final)
+            );
+            assert_eq!(voting_contract.polls.get(1).unwrap().winner, winner);
+
+            assert_eq!(
+                voting_contract.resolve_challenge(2, ChallengeOutcome::Void),
+                Ok(())
+            );
+            assert_eq!(bob_balance() - balance_before, 100);
+            assert_eq!(
+                voting_contract.get_result_status(2),
+                Ok(ResultStatus::Voided)
+            );
+            assert_eq!(voting_contract.polls.get(2).unwrap().winner, None);
+        }
+
+        #[ink::test]
+        /// Tests that the result of a poll becomes final at the end of its challenge period.
+        fn test_challenge_period_expiry() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_challenge_period(1, ONE_DAY), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.get_result_status(1),
+                Ok(ResultStatus::Provisional)
+            );
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(ONE_DAY);
+            assert_eq!(
+                voting_contract.get_result_status(1),
+                Ok(ResultStatus::Final)
+            );
+            assert_eq!(
+                voting_contract.challenge_result(1),
+                Err(Error::ResultIsNotProvisional)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that a moderator can remove a poll and slash its deposit.
+        fn test_remove_poll_slashes_deposit() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(voting_contract.set_poll_deposit(100), Ok(()));
+            assert_eq!(
+                voting_contract.add_moderator(default_accounts.charlie),
+                Ok(())
+            );
+            assert!(voting_contract.is_moderator(default_accounts.charlie));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(
+                voting_contract.create_poll(1, "spam".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            assert_eq!(
+                voting_contract.remove_poll(1),
+                Err(Error::CallerIsNotModerator)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(voting_contract.remove_poll(1), Ok(()));
+            assert_eq!(voting_contract.get_deposit(1), 0);
+            assert_eq!(
+                voting_contract.get_report(1).unwrap().status,
+                PollStatus::Ended
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.claim_deposit(1),
+                Err(Error::NoDepositToClaim)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.remove_moderator(default_accounts.charlie),
+                Ok(())
+            );
+            assert!(!voting_contract.is_moderator(default_accounts.charlie));
+        }
+
+        #[ink::test]
+        /// Tests that only accounts on the creator allow-list can create polls when it is enabled.
+        fn test_create_poll_creator_allow_list() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(voting_contract.set_creator_allow_list(true), Ok(()));
+            assert!(voting_contract.is_creator_allow_list_enabled());
+            assert_eq!(voting_contract.add_creator(default_accounts.bob), Ok(()));
+            assert!(voting_contract.is_creator(default_accounts.bob));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Err(Error::CallerIsNotAllowedToCreatePoll)
+            );
+            assert_eq!(
+                voting_contract.add_creator(default_accounts.charlie),
+                Err(Error::CallerIsNotAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.remove_creator(default_accounts.bob), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY),
+                Err(Error::CallerIsNotAllowedToCreatePoll)
+            );
+
+            // Disabling the allow-list opens poll creation to everyone again.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.set_creator_allow_list(false), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        /// Tests that recurring polls can be created from a template and listed as a series.
+        fn test_poll_templates() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll_template(
+                    "weekly".to_string(),
+                    vec!["yes".to_string(), "no".to_string()],
+                    ONE_DAY,
+                    TallyStrategy::Borda,
+                    ONE_DAY,
+                ),
+                Ok(1)
+            );
+            assert_eq!(
+                voting_contract.instantiate_from_template(2, 1, 0),
+                Err(Error::TemplateWithIdDoesNotExist)
+            );
+            assert_eq!(voting_contract.instantiate_from_template(1, 1, 0), Ok(()));
+            assert_eq!(
+                voting_contract.instantiate_from_template(1, 1, 0),
+                Err(Error::PollWithIdAlreadyExists)
+            );
+            assert_eq!(
+                voting_contract.instantiate_from_template(1, 2, ONE_DAY),
+                Ok(())
+            );
+
+            // The polls have the choices and configuration of the template.
+            for poll_id in [1, 2] {
+                assert_eq!(
+                    voting_contract.get_choices(poll_id),
+                    vec![
+                        (
+                            1,
+                            Choice {
+                                description: "yes".to_string().try_into().unwrap()
+                            }
+                        ),
+                        (
+                            2,
+                            Choice {
+                                description: "no".to_string().try_into().unwrap()
+                            }
+                        ),
+                    ]
+                );
+                assert_eq!(
+                    voting_contract.get_tally_strategy(poll_id),
+                    TallyStrategy::Borda
+                );
+                assert_eq!(voting_contract.get_challenge_period(poll_id), ONE_DAY);
+                assert_eq!(voting_contract.get_template_of(poll_id), Some(1));
+            }
+            assert_eq!(voting_contract.polls.get(2).unwrap().end_at, 2 * ONE_DAY);
+
+            let series = voting_contract.get_series(1);
+            assert_eq!(series.len(), 2);
+            assert_eq!(series[0].id, 1);
+            assert_eq!(series[1].id, 2);
+            assert_eq!(series[1].short_description, "weekly");
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.instantiate_from_template(1, 3, 0),
+                Err(Error::OnlyOwnerCanInstantiateTemplate)
+            );
+            assert_eq!(voting_contract.get_template_of(3), None);
+        }
+
+        #[ink::test]
+        /// Tests that `poll_count`, `poll_exists` and `choice_exists` reflect the created polls and choices.
+        fn test_poll_count_and_existence_checks() {
+            let mut voting_contract = VotingContract::default();
+
+            assert_eq!(voting_contract.poll_count(), 0);
+            assert!(!voting_contract.poll_exists(1));
+            assert!(!voting_contract.choice_exists(1, 1));
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY),
+                Err(Error::PollWithIdAlreadyExists)
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+
+            assert_eq!(voting_contract.poll_count(), 2);
+            assert!(voting_contract.poll_exists(1));
+            assert!(voting_contract.poll_exists(2));
+            assert!(!voting_contract.poll_exists(3));
+            assert!(voting_contract.choice_exists(1, 1));
+            assert!(!voting_contract.choice_exists(1, 2));
+            assert!(!voting_contract.choice_exists(2, 1));
+        }
+
+        #[ink::test]
+        /// Tests that the protocol fee is taken from the value transferred to the contract.
+        fn test_protocol_fee() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.set_fee_config(FeeConfig {
+                    bps: 1_001,
+                    recipient: default_accounts.django,
+                }),
+                Err(Error::FeeTooHigh)
+            );
+            assert_eq!(
+                voting_contract.set_fee_config(FeeConfig {
+                    bps: 250,
+                    recipient: default_accounts.django,
+                }),
+                Ok(())
+            );
+            assert_eq!(voting_contract.get_fee_config().bps, 250);
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                contract_id,
+                1_000_000,
+            );
+            let balance_before =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.django,
+                )
+                .unwrap();
+
+            // 2.5% of 100 is taken from the deposit.
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.get_deposit(1), 98);
+
+            // 2.5% of 39 rounds down to 0.
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(39);
+            assert_eq!(voting_contract.fund_fee_pool(1), Ok(()));
+            assert_eq!(voting_contract.get_fee_pool(1), 39);
+
+            let balance_after =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.django,
+                )
+                .unwrap();
+            assert_eq!(balance_after - balance_before, 2);
+        }
+
+        #[ink::test]
+        /// Tests that only the admin can set the results publisher.
+        fn test_set_results_publisher() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(voting_contract.get_results_publisher(), None);
+            assert_eq!(
+                voting_contract.set_results_publisher(Some(default_accounts.django)),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.get_results_publisher(),
+                Some(default_accounts.django)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_results_publisher(None),
+                Err(Error::CallerIsNotAdmin)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that only the admin can set the stats contract.
+        fn test_set_stats_contract() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(voting_contract.get_stats_contract(), None);
+            assert_eq!(
+                voting_contract.set_stats_contract(Some(default_accounts.django)),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.get_stats_contract(),
+                Some(default_accounts.django)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_stats_contract(None),
+                Err(Error::CallerIsNotAdmin)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `estimate_poll_deposit` accounts for the poll and its choices.
+        fn test_estimate_poll_deposit() {
+            let mut voting_contract = VotingContract::default();
+
+            assert_eq!(voting_contract.estimate_poll_deposit(10, 2, 5), 0);
+            assert_eq!(voting_contract.set_storage_deposit_rates(1, 0), Ok(()));
+            assert_eq!(voting_contract.get_storage_deposit_rates(), (1, 0));
+
+            // Poll: 1 (status) + 32 (owner) + 1 (winner) + 16 (window), description: 1 + 10,
+            // version: 1, choices: 2 * (1 + 5), choice slots: 2 * 4, choice count: 4, next choice
+            // id: 4.
+            assert_eq!(
+                voting_contract.estimate_poll_deposit(10, 2, 5),
+                50 + 11 + 1 + 12 + 8 + 4 + 4
+            );
+
+            // Poll, description, version, next choice id, choice count, two choice slots and two
+            // choices.
+            assert_eq!(voting_contract.set_storage_deposit_rates(0, 10), Ok(()));
+            assert_eq!(voting_contract.estimate_poll_deposit(10, 2, 5), 90);
+        }
+
+        #[ink::test]
+        /// Tests that `set_fee_config` fails if the caller is not the admin.
+        fn test_set_fee_config_failure_not_admin() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_fee_config(FeeConfig {
+                    bps: 100,
+                    recipient: default_accounts.bob,
+                }),
+                Err(Error::CallerIsNotAdmin)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that polls keep the payment token configured when they were created.
+        fn test_payment_token() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "native".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.set_payment_token(Some(default_accounts.django)),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.get_payment_token(),
+                Some(default_accounts.django)
+            );
+            assert_eq!(
+                voting_contract.create_poll(2, "token".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            assert_eq!(voting_contract.get_poll_token(1), None);
+            assert_eq!(
+                voting_contract.get_poll_token(2),
+                Some(default_accounts.django)
+            );
+
+            // Tokens can't be offered to polls paid in the native currency.
+            assert_eq!(
+                voting_contract.fund_fee_pool_with_tokens(1, 10),
+                Err(Error::PollDoesNotUseTokens)
+            );
+
+            // Native value can't be transferred to polls paid in a PSP22 token.
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(10);
+            assert_eq!(
+                voting_contract.fund_fee_pool(2),
+                Err(Error::NativeValueNotAccepted)
+            );
+            assert_eq!(
+                voting_contract.create_poll(3, "token".to_string(), 0, ONE_DAY),
+                Err(Error::NativeValueNotAccepted)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_payment_token(None),
+                Err(Error::CallerIsNotAdmin)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `add_choice` works properly in success scenario.
+        fn test_add_choice_success() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+
+            // Check if the choice has been added to the choice list.
+            assert_eq!(voting_contract.choice_count.get(1), Some(1));
+
+            // Check if the choice has been added to choices.
+            let choice = voting_contract.choices.get((1, 1)).unwrap();
+            assert_eq!(choice.description, "test".to_string());
+
+            // Add one more choice
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test1".to_string()),
+                Ok(())
+            );
+
+            // Check if the choice has been added to the choice list.
+            assert_eq!(voting_contract.choice_count.get(1), Some(2));
+
+            // Check if the choice has been added to choices.
+            let choice = voting_contract.choices.get((1, 2)).unwrap();
+            assert_eq!(choice.description, "test1".to_string());
+
+            let emitted_events = recorded_poll_events();
+            assert_add_choice_event(&emitted_events[1], 1, 1, "test");
+            assert_add_choice_event(&emitted_events[2], 1, 2, "test1");
+        }
+
+        #[ink::test]
+        /// Tests that `add_choice` works properly in failure scenario (contract paused).
+        fn test_add_choice_failure_contract_paused() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            assert!(voting_contract.pause().is_ok());
+
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test".to_string()),
+                Err(Error::ContractIsPaused)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `add_choice` works properly in failure scenario (duplicate choice id).
+        fn test_add_choice_failure_duplicate_id() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Err(Error::ChoiceWithIdAlreadyExists)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `add_choice` works properly in failure scenario (poll does not exist).
+        fn test_add_choice_failure_poll_does_not_exist() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test".to_string()),
+                Err(Error::PollWithIdDoesNotExist)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `add_choice` works properly in failure scenario (poll has started).
+        fn test_add_choice_failure_poll_has_started() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Err(Error::PollHasStarted)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `add_choice` works properly in failure scenario (poll has ended).
+        fn test_add_choice_failure_poll_has_ended() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Err(Error::PollHasEnded)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `add_choice` works properly in failure scenario (caller is not owner).
+        fn test_add_choice_failure_caller_is_not_owner() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test".to_string()),
+                Err(Error::OnlyOwnerCanAddChoice)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `auto_add_choice` assigns increasing ids in insertion order.
+        fn test_auto_add_choice_success() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.auto_add_choice(1, "test0".to_string()),
+                Ok(0)
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 5, "test5".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.auto_add_choice(1, "test6".to_string()),
+                Ok(6)
+            );
+
+            let choices = voting_contract.get_choices(1);
+            assert_eq!(choices.len(), 3);
+            assert_eq!(choices[0].0, 0);
+            assert_eq!(choices[1].0, 5);
+            assert_eq!(choices[2].0, 6);
+
+            let emitted_events = recorded_poll_events();
+            assert_add_choice_event(&emitted_events[1], 1, 0, "test0");
+            assert_add_choice_event(&emitted_events[3], 1, 6, "test6");
+        }
+
+        #[ink::test]
+        /// Tests that `auto_add_choice` works properly in failure scenario (choice ids exhausted).
+        fn test_auto_add_choice_failure_choice_ids_exhausted() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, ChoiceId::MAX, "test".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.auto_add_choice(1, "test1".to_string()),
+                Err(Error::ChoiceIdsExhausted)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `auto_add_choice` works properly in failure scenario (caller is not owner).
+        fn test_auto_add_choice_failure_caller_is_not_owner() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.auto_add_choice(1, "test".to_string()),
+                Err(Error::OnlyOwnerCanAddChoice)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `set_choice_order` only accepts permutations of the choices of the poll.
+        fn test_set_choice_order() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            for choice_id in 1..=3 {
+                assert_eq!(
+                    voting_contract.add_choice(1, choice_id, format!("test{choice_id}")),
+                    Ok(())
+                );
+            }
+
+            assert_eq!(
+                voting_contract.set_choice_order(1, vec![3, 1]),
+                Err(Error::InvalidChoiceOrder)
+            );
+            assert_eq!(
+                voting_contract.set_choice_order(1, vec![3, 1, 1]),
+                Err(Error::InvalidChoiceOrder)
+            );
+            assert_eq!(voting_contract.set_choice_order(1, vec![3, 1, 2]), Ok(()));
+
+            let report = voting_contract.get_report(1).unwrap();
+            let order: Vec<ChoiceId> = report.choices.iter().map(|choice| choice.id).collect();
+            assert_eq!(order, vec![3, 1, 2]);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_choice_order(1, vec![1, 2, 3]),
+                Err(Error::OnlyOwnerCanOrderChoices)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `get_report_for` shuffles the choices deterministically per account.
+        fn test_get_report_for_shuffles_choices() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            for choice_id in 1..=5 {
+                assert_eq!(
+                    voting_contract.add_choice(1, choice_id, format!("test{choice_id}")),
+                    Ok(())
+                );
+            }
+
+            // Without shuffling, the report for an account is the regular report.
+            let report = voting_contract
+                .get_report_for(1, default_accounts.bob)
+                .unwrap();
+            assert_eq!(report, voting_contract.get_report(1).unwrap());
+
+            assert_eq!(voting_contract.set_choice_shuffle(1, true), Ok(()));
+
+            let report = voting_contract
+                .get_report_for(1, default_accounts.bob)
+                .unwrap();
+            assert!(report.shuffle_seed.is_some());
+            assert_eq!(
+                report,
+                voting_contract
+                    .get_report_for(1, default_accounts.bob)
+                    .unwrap()
+            );
+
+            let mut order: Vec<ChoiceId> = report.choices.iter().map(|choice| choice.id).collect();
+            order.sort_unstable();
+            assert_eq!(order, vec![1, 2, 3, 4, 5]);
+        }
+
+        #[ink::test]
+        /// Tests that choices can be added in batches, updated and removed before the poll starts.
+        fn test_edit_choices() {
+            let mut voting_contract = VotingContract::default();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract
+                    .add_choices(1, vec![(1, "test1".to_string()), (2, "test2".to_string())]),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.update_choice(1, 2, "updated".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.remove_choice(1, 1), Ok(()));
+            assert_eq!(
+                voting_contract.remove_choice(1, 1),
+                Err(Error::ChoiceWithIdDoesNotExist)
+            );
+            assert!(!voting_contract.has_choice(1, 1));
+            assert!(voting_contract.has_choice(1, 2));
+            assert_eq!(voting_contract.choice_count.get(1), Some(1));
+            assert_eq!(voting_contract.choice_slots.get((1, 1)), None);
+
+            let choices = voting_contract.get_choices(1);
+            assert_eq!(choices.len(), 1);
+            assert_eq!(choices[0].0, 2);
+            assert_eq!(choices[0].1.description, "updated");
+
+            // A poll whose choices have all been removed can't be started.
+            assert_eq!(voting_contract.remove_choice(1, 2), Ok(()));
+            assert_eq!(
+                voting_contract.start_poll(1),
+                Err(Error::CannotStartPollWithNoChoices)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the choice set of a poll is locked once the poll has started.
+        fn test_choices_are_locked_after_start() {
+            let mut voting_contract = VotingContract::default();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Err(Error::PollHasStarted)
+            );
+            assert_eq!(
+                voting_contract.add_choices(1, vec![(2, "test2".to_string())]),
+                Err(Error::PollHasStarted)
+            );
+            assert_eq!(
+                voting_contract.auto_add_choice(1, "test2".to_string()),
+                Err(Error::PollHasStarted)
+            );
+            assert_eq!(
+                voting_contract.update_choice(1, 1, "updated".to_string()),
+                Err(Error::PollHasStarted)
+            );
+            assert_eq!(
+                voting_contract.remove_choice(1, 1),
+                Err(Error::PollHasStarted)
+            );
+            assert_eq!(
+                voting_contract.set_choice_order(1, vec![1]),
+                Err(Error::PollHasStarted)
+            );
+            assert_eq!(
+                voting_contract.set_choice_shuffle(1, true),
+                Err(Error::PollHasStarted)
+            );
+        }
+
+        /// Creates a started poll with the given tally strategy and number of choices (with ids
+        /// starting at 1) and casts the given ranked ballots from different accounts.
+        fn setup_ranked_poll(
+            strategy: TallyStrategy,
+            num_choices: ChoiceId,
+            ballots: &[Vec<ChoiceId>],
+        ) -> VotingContract {
+            let mut voting_contract = VotingContract::default();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            for choice_id in 1..=num_choices {
+                assert_eq!(
+                    voting_contract.add_choice(1, choice_id, format!("test{choice_id}")),
+                    Ok(())
+                );
+            }
+            assert_eq!(voting_contract.set_tally_strategy(1, strategy), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            for (index, ranking) in ballots.iter().enumerate() {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(AccountId::from(
+                    [index as u8 + 100; 32],
+                ));
+                assert_eq!(
+                    voting_contract.vote_ranked(1, ranking.clone()),
+                    Ok(index as u64 + 1)
+                );
+            }
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().alice,
+            );
+
+            voting_contract
+        }
+
+        #[ink::test]
+        /// Tests that the Condorcet winner beats every other choice in pairwise comparisons.
+        fn test_condorcet_winner() {
+            // Choice 2 has the fewest first preferences but beats both other choices.
+            let mut voting_contract = setup_ranked_poll(
+                TallyStrategy::Condorcet,
+                3,
+                &[
+                    vec![1, 2, 3],
+                    vec![1, 2, 3],
+                    vec![3, 2, 1],
+                    vec![3, 2, 1],
+                    vec![2, 1, 3],
+                ],
+            );
+
+            assert_eq!(voting_contract.get_pairwise_preference(1, 2, 1), 3);
+            assert_eq!(voting_contract.get_pairwise_preference(1, 1, 2), 2);
+
+            assert_eq!(
+                voting_contract.vote_ranked(1, vec![1, 1]),
+                Err(Error::InvalidRanking)
+            );
+
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(voting_contract.get_report(1).unwrap().winner, Some(2));
+            assert_eq!(voting_contract.get_smith_set(1), vec![2]);
+        }
+
+        #[ink::test]
+        /// Tests that the Smith set is reported if the pairwise preferences form a cycle.
+        fn test_condorcet_smith_set() {
+            // Choices 1, 2 and 3 form a cycle and all of them beat choice 4 (which is never ranked).
+            let mut voting_contract = setup_ranked_poll(
+                TallyStrategy::Condorcet,
+                4,
+                &[vec![1, 2, 3], vec![2, 3, 1], vec![3, 1, 2]],
+            );
+
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(voting_contract.get_report(1).unwrap().winner, None);
+            assert_eq!(voting_contract.get_smith_set(1), vec![1, 2, 3]);
+        }
+
+        #[ink::test]
+        /// Tests that the Borda winner is the choice with the most points and that the report
+        /// includes both the points and the first-preference counts.
+        fn test_borda_winner() {
+            // Choice 1 has the most first preferences but choice 2 has the most points.
+            let mut voting_contract = setup_ranked_poll(
+                TallyStrategy::Borda,
+                3,
+                &[vec![1, 2, 3], vec![1, 2, 3], vec![2, 3, 1], vec![3, 2]],
+            );
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            let report = voting_contract.get_report(1).unwrap();
+            assert_eq!(report.winner, Some(2));
+
+            let tally: Vec<(u128, u128)> = report
+                .choices
+                .iter()
+                .map(|choice| (choice.vote_count, choice.points))
+                .collect();
+            assert_eq!(tally, vec![(2, 4), (1, 5), (1, 3)]);
+        }
+
+        #[ink::test]
+        /// Tests that STV transfers the surplus of an elected choice to the next preferences.
+        fn test_stv_surplus_transfer() {
+            // Choice 1 reaches the quota in the first round and its surplus elects choice 3 over
+            // choice 2.
+            let mut ballots = vec![vec![1, 3]; 4];
+            ballots.extend([vec![2], vec![2], vec![3]]);
+            let mut voting_contract =
+                setup_ranked_poll(TallyStrategy::Stv { seats: 2 }, 3, &ballots);
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            assert_eq!(voting_contract.get_report(1).unwrap().winner, None);
+            assert_eq!(voting_contract.get_winners(1), vec![1, 3]);
+
+            let rounds = voting_contract.get_count_rounds(1);
+            assert_eq!(rounds.len(), 2);
+            assert_eq!(rounds[0].elected, vec![1]);
+            assert_eq!(rounds[1].elected, vec![3]);
+            assert_eq!(
+                rounds[1].tallies,
+                vec![(2, 2 * STV_PRECISION), (3, 2_666_666_666)]
+            );
+        }
+
+        #[ink::test]
+        /// Tests that STV eliminates the choice with the fewest votes if no choice reaches the
+        /// quota.
+        fn test_stv_elimination() {
+            let mut voting_contract = setup_ranked_poll(
+                TallyStrategy::Stv { seats: 1 },
+                3,
+                &[vec![1], vec![1], vec![2], vec![2], vec![3, 2]],
+            );
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            assert_eq!(voting_contract.get_report(1).unwrap().winner, Some(2));
+
+            let rounds = voting_contract.get_count_rounds(1);
+            assert_eq!(rounds.len(), 2);
+            assert_eq!(rounds[0].eliminated, Some(3));
+            assert_eq!(rounds[1].elected, vec![2]);
+        }
+
+        #[ink::test]
+        /// Tests that anonymous votes are only accepted by private polls with a verifier key and a
+        /// configured membership verifier.
+        fn test_vote_anonymous_failure() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.set_verifier_key(1, vec![1, 2, 3]),
+                Err(Error::PollIsNotPrivate)
+            );
+            assert_eq!(voting_contract.set_private_voting(1, true), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_verifier_key(1, vec![1, 2, 3]),
+                Err(Error::OnlyOwnerCanSetVerifierKey)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.vote_anonymous(1, 1, vec![], [1; 32]),
+                Err(Error::PollDoesNotAcceptAnonymousVotes)
+            );
+            assert_eq!(voting_contract.get_verifier_key(1), None);
+
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(2, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_private_voting(2, true), Ok(()));
+            assert_eq!(voting_contract.set_verifier_key(2, vec![1, 2, 3]), Ok(()));
+            assert_eq!(voting_contract.start_poll(2), Ok(()));
+            assert_eq!(
+                voting_contract.vote_anonymous(2, 1, vec![], [1; 32]),
+                Err(Error::NoMembershipVerifier)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that encrypted ballots are tallied once enough decryption shares are submitted.
+        fn test_encrypted_ballots() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let committee = vec![default_accounts.django, default_accounts.eve];
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract
+                    .add_choices(1, vec![(1, "test1".to_string()), (2, "test2".to_string())]),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.set_ballot_encryption(1, vec![7; 32], committee.clone(), 3),
+                Err(Error::InvalidThreshold)
+            );
+            assert_eq!(
+                voting_contract.set_ballot_encryption(1, vec![7; 32], committee, 2),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            assert_eq!(
+                voting_contract.vote(1, 1),
+                Err(Error::PollRequiresEncryptedVote)
+            );
+            for voter in [default_accounts.bob, default_accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                assert_eq!(voting_contract.vote_encrypted(1, vec![1, 2, 3]), Ok(()));
+            }
+            assert_eq!(
+                voting_contract.vote_encrypted(1, vec![1, 2, 3]),
+                Err(Error::CallerAlreadyVotedOnPoll)
+            );
+            assert_eq!(voting_contract.get_encrypted_ballot_count(1), 2);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.submit_decryption_share(1, vec![1]),
+                Err(Error::CallerIsNotInDecryptionCommittee)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.django);
+            assert_eq!(voting_contract.submit_decryption_share(1, vec![1]), Ok(()));
+            assert_eq!(
+                voting_contract.submit_decryption_share(1, vec![1]),
+                Err(Error::DecryptionShareAlreadySubmitted)
+            );
+            assert_eq!(
+                voting_contract.post_decrypted_tally(1, vec![(1, 2)], [9; 32]),
+                Err(Error::NotEnoughDecryptionShares)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.eve);
+            assert_eq!(voting_contract.submit_decryption_share(1, vec![2]), Ok(()));
+            assert_eq!(
+                voting_contract.post_decrypted_tally(1, vec![(1, 2), (2, 1)], [9; 32]),
+                Err(Error::InvalidTally)
+            );
+            assert_eq!(
+                voting_contract.post_decrypted_tally(1, vec![(1, 2)], [9; 32]),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.post_decrypted_tally(1, vec![(1, 2)], [9; 32]),
+                Err(Error::TallyAlreadyPosted)
+            );
+
+            assert_eq!(voting_contract.get_tally_hash(1), Some([9; 32]));
+            assert_eq!(
+                voting_contract.get_report(1).unwrap().choices[0].vote_count,
+                2
+            );
+        }
+
+        #[ink::test]
+        /// Tests that hidden results are only reported to the owner until the poll ends.
+        fn test_hide_results_until_end() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_hide_results_until_end(1, true), Ok(()));
+            assert!(voting_contract.hides_results_until_end(1));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            assert_eq!(
+                voting_contract.get_report(1).unwrap().choices[0].vote_count,
+                0
+            );
+            assert_eq!(voting_contract.get_vote_count(1, 1), 0);
+            assert_eq!(voting_contract.get_vote_counts(1), vec![(1, 0)]);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.get_report(1).unwrap().choices[0].vote_count,
+                1
+            );
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.get_report(1).unwrap().choices[0].vote_count,
+                1
+            );
+            assert_eq!(voting_contract.get_vote_count(1, 1), 1);
+        }
+
+        #[ink::test]
+        /// Tests that the owner of a poll cannot vote on it if the poll does not allow it.
+        fn test_owner_may_vote() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert!(voting_contract.owner_may_vote(1));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_owner_may_vote(1, false),
+                Err(Error::OnlyOwnerCanSetOwnerMayVote)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.set_owner_may_vote(1, false), Ok(()));
+            assert!(!voting_contract.owner_may_vote(1));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            assert_eq!(voting_contract.vote(1, 1), Err(Error::OwnerCannotVote));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+        }
+
+        #[ink::test]
+        /// Tests that recused accounts cannot vote and that the recusals are reported.
+        fn test_recusal() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_moderator(default_accounts.charlie),
+                Ok(())
+            );
+            assert_eq!(voting_contract.recuse(1, default_accounts.bob), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.django);
+            assert_eq!(
+                voting_contract.recuse(1, default_accounts.eve),
+                Err(Error::OnlyOwnerOrModeratorCanRecuse)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(voting_contract.recuse(1, default_accounts.eve), Ok(()));
+            assert_eq!(voting_contract.recuse(1, default_accounts.eve), Ok(()));
+            assert!(voting_contract.is_recused(1, default_accounts.eve));
+            assert!(!voting_contract.is_recused(1, default_accounts.django));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.recuse(1, default_accounts.django),
+                Err(Error::PollHasStarted)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 1), Err(Error::AccountIsRecused));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.django);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+
+            assert_eq!(voting_contract.get_report(1).unwrap().recusal_count, 2);
+        }
+
+        #[ink::test]
+        /// Tests that private polls can only be viewed by the owner, the moderators and the
+        /// accounts on the eligibility list.
+        fn test_private_poll_visibility() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.set_poll_visibility(1, PollVisibility::Private),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.set_voter_weights(1, vec![(default_accounts.bob, 1)]),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_moderator(default_accounts.charlie),
+                Ok(())
+            );
+
+            for viewer in [
+                default_accounts.alice,
+                default_accounts.bob,
+                default_accounts.charlie,
+            ] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(viewer);
+                assert!(voting_contract.get_report(1).is_ok());
+                assert_eq!(voting_contract.get_choices(1).len(), 1);
+            }
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.django);
+            assert_eq!(
+                voting_contract.get_report(1),
+                Err(Error::CallerCannotViewPoll)
+            );
+            assert!(voting_contract.get_choices(1).is_empty());
+        }
+
+        #[ink::test]
+        /// Tests that the report with identities lists the voters of polls with public ballots.
+        fn test_get_report_with_identities() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_choice_capacity(1, Some(10)), Ok(()));
+            assert_eq!(voting_contract.set_public_ballots(1, true), Ok(()));
+            assert!(voting_contract.has_public_ballots(1));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            for (ballot_number, voter) in [
+                default_accounts.bob,
+                default_accounts.charlie,
+                default_accounts.django,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                assert_eq!(voting_contract.vote(1, 1), Ok(ballot_number as u64 + 1));
+            }
+
+            let voter_identity = |account| VoterIdentity {
+                account,
+                display_name: None,
+                choice_id: 1,
+            };
+
+            // The voters are listed a page at a time, in order of voting.
+            let report = voting_contract.get_report_with_identities(1, 0, 2).unwrap();
+            assert_eq!(report.owner_name, None);
+            assert_eq!(
+                report.voters,
+                vec![
+                    voter_identity(default_accounts.bob),
+                    voter_identity(default_accounts.charlie)
+                ]
+            );
+            assert_eq!(report.next_offset, Some(2));
+
+            let report = voting_contract.get_report_with_identities(1, 2, 2).unwrap();
+            assert_eq!(report.voters, vec![voter_identity(default_accounts.django)]);
+            assert_eq!(report.next_offset, None);
+
+            // Retracted votes leave their slot empty.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(voting_contract.retract_vote(1), Ok(()));
+
+            let report = voting_contract.get_report_with_identities(1, 0, 2).unwrap();
+            assert_eq!(report.voters, vec![voter_identity(default_accounts.bob)]);
+            assert_eq!(report.next_offset, Some(2));
+
+            assert_eq!(
+                voting_contract.get_report_with_identities(1, 0, MAX_IDENTITY_PAGE_SIZE + 1),
+                Err(Error::BatchTooLarge)
+            );
+        }
+
+        /// Signs the message of a private vote with the voting key derived from the given seed and
+        /// returns the public key and the signature.
+        fn sign_private_vote(
+            voting_contract: &VotingContract,
+            seed: u8,
+            poll_id: PollId,
+            choice_id: ChoiceId,
+        ) -> ([u8; 33], [u8; 65]) {
+            let secret_key = secp256k1::SecretKey::from_slice(&[seed; 32]).unwrap();
+            let message = secp256k1::Message::from_slice(
+                &voting_contract.private_vote_message(poll_id, choice_id),
+            )
+            .unwrap();
+
+            let (recovery_id, compact) = secp256k1::SECP256K1
+                .sign_ecdsa_recoverable(&message, &secret_key)
+                .serialize_compact();
+
+            let mut signature = [0; 65];
+            signature[..64].copy_from_slice(&compact);
+            signature[64] = recovery_id.to_i32() as u8;
+
+            let public_key =
+                secp256k1::PublicKey::from_secret_key(secp256k1::SECP256K1, &secret_key);
+
+            (public_key.serialize(), signature)
+        }
+
+        #[ink::test]
+        /// Tests that private polls only accept votes signed by registered voting keys.
+        fn test_vote_private() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_private_voting(1, true), Ok(()));
+            assert!(voting_contract.is_private_voting(1));
+
+            let (public_key, signature) = sign_private_vote(&voting_contract, 1, 1, 1);
+            let (unregistered_key, forged_signature) = sign_private_vote(&voting_contract, 2, 1, 1);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.register_voting_key(1, public_key), Ok(()));
+            assert_eq!(
+                voting_contract.register_voting_key(1, unregistered_key),
+                Err(Error::VotingKeyAlreadyRegistered)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.register_voting_key(1, unregistered_key),
+                Err(Error::PollHasStarted)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.vote(1, 1),
+                Err(Error::PollRequiresPrivateVote)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(
+                voting_contract.vote_private(1, 1, forged_signature),
+                Err(Error::VotingKeyNotRegistered)
+            );
+            assert_eq!(voting_contract.vote_private(1, 1, signature), Ok(()));
+            assert_eq!(
+                voting_contract.vote_private(1, 1, signature),
+                Err(Error::CallerAlreadyVotedOnPoll)
+            );
+
+            assert_eq!(
+                voting_contract.get_report(1).unwrap().choices[0].vote_count,
+                1
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `start_poll` works properly in success scenario.
+        fn test_start_poll_success() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            // Check if the poll has been started.
+            let poll = voting_contract.polls.get(1).unwrap();
+            assert_eq!(poll.status, PollStatus::Started);
+
+            let emitted_events = recorded_poll_events();
+            assert_start_poll_event(&emitted_events[2], 1);
+        }
+
+        #[ink::test]
+        /// Tests that the `PollLifecycle` events of a poll are emitted under the topic of the poll.
+        fn test_poll_lifecycle_events() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            let poll_topic = voting_contract.get_poll_topic(1);
+            assert_ne!(poll_topic, voting_contract.get_poll_topic(2));
+
+            let stages = ink::env::test::recorded_events()
+                .filter(|event| event.topics.contains(&poll_topic.as_ref().to_vec()))
+                .map(|event| match decode_event::<PollLifecycle>(&event) {
+                    Some(PollLifecycle { poll_id, stage, .. }) => {
+                        assert_eq!(poll_id, 1);
+                        stage
+                    }
+                    _ => panic!(
+                        "encountered unexpected contract event kind: expected `PollLifecycle`"
+                    ),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                stages,
+                vec![
+                    PollStage::Created,
+                    PollStage::ChoiceAdded,
+                    PollStage::Started,
+                    PollStage::Ended,
+                ]
+            );
+
+            assert_eq!(
+                voting_contract.get_topic_scheme(),
+                TopicScheme {
+                    version: TOPIC_SCHEME_VERSION,
+                    domain: "poll".to_string(),
+                    namespace_domain: "namespace".to_string(),
+                }
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the events of a poll with a topic seed carry the namespace derived from the
+        /// seed and the owner of the poll.
+        fn test_topic_seed() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+            assert_eq!(voting_contract.get_topic_namespace(1), None);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_topic_seed(1, [7; 32]),
+                Err(Error::OnlyOwnerCanSetTopicSeed)
+            );
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_topic_seed(2, [7; 32]), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.set_topic_seed(1, [7; 32]), Ok(()));
+
+            // The same seed gives different namespaces to the polls of different owners.
+            let namespace = voting_contract.get_topic_namespace(1).unwrap();
+            assert_ne!(voting_contract.get_topic_namespace(2), Some(namespace));
+
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            assert_eq!(
+                voting_contract.set_topic_seed(1, [8; 32]),
+                Err(Error::PollHasStarted)
+            );
+
+            let emitted_events = recorded_poll_events();
+
+            match decode_event::<PollStarted>(&emitted_events[3]) {
+                Some(event) => assert_eq!(event.namespace, Some(namespace)),
+                _ => panic!("encountered unexpected contract event kind: expected `PollStarted`"),
+            }
+
+            match decode_event::<VoteCast>(&emitted_events[4]) {
+                Some(event) => assert_eq!(event.namespace, Some(namespace)),
+                _ => panic!("encountered unexpected contract event kind: expected `VoteCast`"),
+            }
+        }
+
+        #[ink::test]
+        /// Tests that `start_poll` works properly in failure scenario (contract paused).
+        fn test_start_poll_failure_contract_paused() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            assert!(voting_contract.pause().is_ok());
+
+            assert_eq!(voting_contract.start_poll(1), Err(Error::ContractIsPaused));
+        }
+
+        #[ink::test]
+        /// Tests that `start_poll` works properly in failure scenario (poll does not exist).
+        fn test_start_poll_failure_poll_does_not_exist() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.start_poll(1),
+                Err(Error::PollWithIdDoesNotExist)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `start_poll` works properly in failure scenario (poll has started).
+        fn test_start_poll_failure_poll_has_started() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.start_poll(1),
+                Err(Error::InvalidStatusTransition {
+                    from: PollStatus::Started,
+                    to: PollStatus::Started
+                })
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `start_poll` works properly in failure scenario (poll has ended).
+        fn test_start_poll_failure_poll_has_ended() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.start_poll(1),
+                Err(Error::InvalidStatusTransition {
+                    from: PollStatus::Ended,
+                    to: PollStatus::Started
+                })
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `start_poll` works properly in failure scenario (caller is not owner).
+        fn test_start_poll_failure_caller_is_not_owner() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.start_poll(1),
+                Err(Error::OnlyOwnerCanStartPoll)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `end_poll` works properly in success scenario.
+        fn test_end_poll_success() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            // Check if the poll has been ended.
+            let poll = voting_contract.polls.get(1).unwrap();
+            assert_eq!(poll.status, PollStatus::Ended);
+
+            let emitted_events = recorded_poll_events();
+            assert_end_poll_event(&emitted_events[4], 1, None, 0);
+        }
+
+        #[ink::test]
+        /// Tests that `end_poll` works properly in failure scenario (contract paused).
+        fn test_end_poll_failure_contract_paused() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            assert!(voting_contract.pause().is_ok());
+
+            assert_eq!(voting_contract.end_poll(1), Err(Error::ContractIsPaused));
+        }
+
+        #[ink::test]
+        /// Tests that `end_poll` works properly in failure scenario (poll does not exist).
+        fn test_end_poll_failure_poll_does_not_exist() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.end_poll(1),
+                Err(Error::PollWithIdDoesNotExist)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `end_poll` works properly in failure scenario (poll has ended).
+        fn test_end_poll_failure_poll_has_ended() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.end_poll(1),
+                Err(Error::InvalidStatusTransition {
+                    from: PollStatus::Ended,
+                    to: PollStatus::Ended
+                })
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `end_poll` works properly in failure scenario (poll has not started).
+        fn test_end_poll_failure_poll_has_not_started() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.end_poll(1),
+                Err(Error::InvalidStatusTransition {
+                    from: PollStatus::NotStarted,
+                    to: PollStatus::Ended
+                })
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `end_poll` works properly in failure scenario (caller is not owner).
+        fn test_end_poll_failure_caller_is_not_owner() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.end_poll(1), Err(Error::OnlyOwnerCanEndPoll));
+        }
+
+        #[ink::test]
+        /// Tests that only the owner of a poll can set its observer before it ends.
+        fn test_set_poll_observer() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(voting_contract.get_poll_observer(1), None);
+
+            assert_eq!(
+                voting_contract.set_poll_observer(1, Some(default_accounts.django)),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.get_poll_observer(1),
+                Some(default_accounts.django)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_poll_observer(1, None),
+                Err(Error::OnlyOwnerCanSetObserver)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.set_poll_observer(1, None), Ok(()));
+            assert_eq!(voting_contract.get_poll_observer(1), None);
+            assert!(!voting_contract.observer_notification_failed(1));
+        }
+
+        #[ink::test]
+        /// Tests that `finalize_if_expired` works properly in success scenario.
+        fn test_finalize_if_expired_success() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(ONE_DAY);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.finalize_if_expired(1), Ok(()));
+
+            // Check if the poll has been ended.
+            let poll = voting_contract.polls.get(1).unwrap();
+            assert_eq!(poll.status, PollStatus::Ended);
+
+            let emitted_events = recorded_poll_events();
+            assert_end_poll_event(&emitted_events[3], 1, Some(1), 0);
+        }
+
+        #[ink::test]
+        /// Tests that `finalize_if_expired` pays the bounty from the poll's fee pool.
+        fn test_finalize_if_expired_pays_bounty() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(voting_contract.set_finalization_bounty(100), Ok(()));
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                contract_id,
+                1_000_000,
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(60);
+            assert_eq!(voting_contract.fund_fee_pool(1), Ok(()));
+            assert_eq!(voting_contract.get_fee_pool(1), 60);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(ONE_DAY);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+
+            let balance_before =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.bob,
+                )
+                .unwrap();
+            assert_eq!(voting_contract.finalize_if_expired(1), Ok(()));
+            let balance_after =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.bob,
+                )
+                .unwrap();
+
+            // The bounty is capped by the balance of the fee pool.
+            assert_eq!(balance_after - balance_before, 60);
+            assert_eq!(voting_contract.get_fee_pool(1), 0);
+        }
+
+        #[ink::test]
+        /// Tests that `finalize_if_expired` works properly in failure scenario (poll has not expired).
+        fn test_finalize_if_expired_failure_poll_has_not_expired() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(ONE_DAY - 1);
+            assert_eq!(
+                voting_contract.finalize_if_expired(1),
+                Err(Error::PollHasNotExpired)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `finalize_if_expired` works properly in failure scenario (poll has not started).
+        fn test_finalize_if_expired_failure_poll_has_not_started() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(ONE_DAY);
+            assert_eq!(
+                voting_contract.finalize_if_expired(1),
+                Err(Error::InvalidStatusTransition {
+                    from: PollStatus::NotStarted,
+                    to: PollStatus::Ended
+                })
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `set_finalization_bounty` fails if the caller is not the admin.
+        fn test_set_finalization_bounty_failure_not_admin() {
+            let mut voting_contract = VotingContract::default();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().bob,
+            );
+            assert_eq!(
+                voting_contract.set_finalization_bounty(100),
+                Err(Error::CallerIsNotAdmin)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `vote` works properly in success scenario.
+        fn test_vote_success() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+
+            // Check if the vote has been registered.
+            let vote_count = voting_contract.vote_counts.get((1, 1)).unwrap();
+            assert_eq!(vote_count, 1);
+            assert!(voting_contract
+                .voted_by
+                .contains(&(1, ink::env::caller::<ink::env::DefaultEnvironment>())));
+        }
+
+        #[ink::test]
+        /// Tests that voting requires accepting the current terms once they are set.
+        fn test_vote_terms_acceptance() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(voting_contract.get_terms(), None);
+            assert_eq!(
+                voting_contract.accept_terms([1; 32]),
+                Err(Error::NoTermsToAccept)
+            );
+            assert_eq!(voting_contract.set_terms(Some([1; 32])), Ok(()));
+            assert_eq!(voting_contract.get_terms(), Some(([1; 32], 1)));
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_terms(None),
+                Err(Error::CallerIsNotAdmin)
+            );
+            assert_eq!(voting_contract.vote(1, 1), Err(Error::TermsNotAccepted));
+            assert_eq!(
+                voting_contract.accept_terms([2; 32]),
+                Err(Error::TermsHashMismatch)
+            );
+            assert_eq!(voting_contract.accept_terms([1; 32]), Ok(()));
+            assert!(voting_contract.has_accepted(default_accounts.bob));
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+
+            // New terms have to be accepted again.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.set_terms(Some([2; 32])), Ok(()));
+            assert_eq!(voting_contract.get_terms(), Some(([2; 32], 2)));
+            assert!(!voting_contract.has_accepted(default_accounts.bob));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(voting_contract.vote(1, 1), Err(Error::TermsNotAccepted));
+
+            // Disabling the requirement opens voting to everyone again.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.set_terms(None), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(voting_contract.vote(1, 1), Ok(2));
+        }
+
+        #[ink::test]
+        /// Tests that value can only be attached to votes on polls in prediction mode.
+        fn test_vote_failure_poll_does_not_accept_stakes() {
+            let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.set_prediction_mode(1, true),
+                Err(Error::PollHasStarted)
+            );
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(10);
+            assert_eq!(
+                voting_contract.vote(1, 1),
+                Err(Error::PollDoesNotAcceptStakes)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `claim_winnings` refunds the stakes if the poll ends without a winner.
+        fn test_claim_winnings_refunds_stakes_without_winner() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_prediction_mode(1, true), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                contract_id,
+                1_000_000,
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(30);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(70);
+            assert_eq!(voting_contract.vote(1, 2), Ok(2));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            assert_eq!(voting_contract.get_prediction_pot(1), 100);
+            assert_eq!(
+                voting_contract.claim_winnings(1),
+                Err(Error::PollHasNotEnded)
+            );
+
+            // The poll ends in a tie.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.claim_winnings(1), Ok(30));
+            assert_eq!(
+                voting_contract.claim_winnings(1),
+                Err(Error::NothingToClaim)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(voting_contract.claim_winnings(1), Ok(70));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.django);
+            assert_eq!(
+                voting_contract.claim_winnings(1),
+                Err(Error::NothingToClaim)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `claim_winnings` splits the pot pro-rata between the backers of the winner.
+        fn test_claim_winnings_splits_pot_pro_rata() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_prediction_mode(1, true), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                contract_id,
+                1_000_000,
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2);
+            assert_eq!(voting_contract.vote(1, 1), Ok(2));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.django);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(97);
+            assert_eq!(voting_contract.vote(1, 2), Ok(3));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            // The pot of 100 is split 1:2 between the backers of the winner, rounding down.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.claim_winnings(1), Ok(33));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(voting_contract.claim_winnings(1), Ok(66));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.django);
+            assert_eq!(
+                voting_contract.claim_winnings(1),
+                Err(Error::NothingToClaim)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `claim_winnings` refunds the stakes if nobody staked on the winner.
+        fn test_claim_winnings_refunds_stakes_without_winning_stake() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract
+                    .add_choices(1, vec![(1, "test1".to_string()), (2, "test2".to_string())]),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_prediction_mode(1, true), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                contract_id,
+                1_000_000,
+            );
+
+            // The winner is only backed by votes without stakes.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.django);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.eve);
+            assert_eq!(voting_contract.vote(1, 1), Ok(2));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(30);
+            assert_eq!(voting_contract.vote(1, 2), Ok(3));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            // Make the unstaked choice the winner regardless of the tally.
+            let mut poll = voting_contract.polls.get(1).unwrap();
+            poll.winner = Some(1);
+            voting_contract.polls.insert(1, &poll);
+
+            // The staker on the losing choice gets their stake back.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.claim_winnings(1), Ok(30));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.django);
+            assert_eq!(
+                voting_contract.claim_winnings(1),
+                Err(Error::NothingToClaim)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that votes on polls with uploaded weights are tallied with those weights.
+        fn test_vote_with_uploaded_voter_weights() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.get_voter_weight(1, default_accounts.bob), 1);
+
+            assert_eq!(
+                voting_contract.set_voter_weights(
+                    1,
+                    vec![(default_accounts.bob, 40), (default_accounts.charlie, 60)]
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.set_voter_weights(1, vec![(default_accounts.charlie, 0)]),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.set_voter_weights(
+                    1,
+                    vec![(default_accounts.django, 1); MAX_VOTER_WEIGHT_BATCH_SIZE as usize + 1]
+                ),
+                Err(Error::BatchTooLarge)
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(
+                voting_contract.vote(1, 1),
+                Err(Error::CallerHasNoVotingWeight)
+            );
+
+            assert_eq!(
+                voting_contract.get_report(1).unwrap().choices[0].vote_count,
+                40
+            );
+        }
+
+        #[ink::test]
+        /// Tests that `get_voting_power` evaluates the weighting rules of each poll without
+        /// voting.
+        fn test_get_voting_power() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            for poll_id in 1..=3 {
+                assert_eq!(
+                    voting_contract.create_poll(poll_id, "test".to_string(), 0, ONE_DAY),
+                    Ok(())
+                );
+                assert_eq!(
+                    voting_contract.add_choice(poll_id, 1, "test1".to_string()),
+                    Ok(())
+                );
+            }
+
+            // Votes on unweighted polls count with the weight source (not boosted without a
+            // tenure schedule).
+            assert!(voting_contract.get_tenure_schedule().is_none());
+            assert_eq!(voting_contract.get_voting_power(1, default_accounts.bob), 1);
+
+            // Votes on weighted polls count with the uploaded weights.
+            assert_eq!(
+                voting_contract.set_voter_weights(2, vec![(default_accounts.bob, 40)]),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.get_voting_power(2, default_accounts.bob),
+                40
+            );
+            assert_eq!(
+                voting_contract.get_voting_power(2, default_accounts.charlie),
+                0
+            );
+
+            // Votes signed by voting keys count with the weight of the account which registered
+            // the key.
+            let public_key = [2; 33];
+            let key_account = voting_contract.key_account(&public_key);
+
+            assert_eq!(
+                voting_contract.set_voter_weights(3, vec![(default_accounts.alice, 7)]),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_private_voting(3, true), Ok(()));
+            assert_eq!(voting_contract.get_voting_power(3, key_account), 0);
+            assert_eq!(voting_contract.register_voting_key(3, public_key), Ok(()));
+            assert_eq!(voting_contract.get_voting_power(3, key_account), 7);
+
+            // The preview does not vote.
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.get_voting_power(1, default_accounts.alice),
+                1
+            );
+            assert_eq!(
+                voting_contract.get_report(1).unwrap().choices[0].vote_count,
+                0
+            );
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+        }
+
+        #[ink::test]
+        /// Tests that votes recorded with a fixed weight (as by `vote_as_member`) go through the
+        /// checks of a regular vote and leave no voting key behind.
+        fn test_vote_with_fixed_weight() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.set_voter_weights(1, vec![(default_accounts.bob, 40)]),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            // The vote counts with the fixed weight instead of the uploaded one.
+            assert_eq!(
+                voting_contract.record_vote_with_weight(1, 1, default_accounts.bob, Some(1)),
+                Ok(1)
+            );
+            assert!(!voting_contract
+                .voting_keys
+                .contains((1, default_accounts.bob)));
+            assert_eq!(
+                voting_contract.get_report(1).unwrap().choices[0].vote_count,
+                1
+            );
+
+            // The checks of a regular vote still apply.
+            assert_eq!(
+                voting_contract.record_vote_with_weight(1, 1, default_accounts.bob, Some(1)),
+                Err(Error::CallerAlreadyVotedOnPoll)
+            );
+            assert_eq!(
+                voting_contract.record_vote_with_weight(1, 2, default_accounts.charlie, Some(1)),
+                Err(Error::ChoiceWithIdDoesNotExist)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the number of voters of a choice is counted separately from its weight.
+        fn test_voter_count() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_choice_capacity(1, Some(10)), Ok(()));
+            assert_eq!(
+                voting_contract.set_voter_weights(
+                    1,
+                    vec![(default_accounts.bob, 40), (default_accounts.charlie, 60)]
+                ),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(voting_contract.vote(1, 1), Ok(2));
+
+            let report = voting_contract.get_report(1).unwrap();
+            assert_eq!(report.choices[0].vote_count, 100);
+            assert_eq!(report.choices[0].voter_count, 2);
+
+            // A retracted vote is no longer counted.
+            assert_eq!(voting_contract.retract_vote(1), Ok(()));
+
+            let report = voting_contract.get_report(1).unwrap();
+            assert_eq!(report.choices[0].vote_count, 40);
+            assert_eq!(report.choices[0].voter_count, 1);
+        }
+
+        #[ink::test]
+        /// Tests that the ballots cast again after being retracted are counted as changed.
+        fn test_changed_ballots() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract
+                    .add_choices(1, vec![(1, "test1".to_string()), (2, "test2".to_string())]),
+                Ok(())
+            );
+            assert_eq!(voting_contract.set_choice_capacity(1, Some(10)), Ok(()));
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            assert_eq!(voting_contract.retract_vote(1), Ok(()));
+            assert_eq!(voting_contract.get_turnout(1).unwrap().changed_ballots, 0);
+            assert_eq!(voting_contract.vote(1, 2), Ok(2));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 1), Ok(3));
+
+            assert_eq!(voting_contract.get_turnout(1).unwrap().changed_ballots, 1);
+        }
+
+        #[ink::test]
+        /// Tests that only the admin can set the metadata of the instance.
+        fn test_instance_metadata() {
+            let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(voting_contract.get_instance_metadata(), None);
+            assert_eq!(
+                voting_contract.set_instance_metadata(
+                    "a".repeat(MAX_INSTANCE_METADATA_LENGTH as usize + 1),
+                    "https://example.com".to_string()
+                ),
+                Err(Error::InstanceMetadataTooLong)
+            );
+            assert_eq!(
+                voting_contract
+                    .set_instance_metadata("Grants".to_string(), "https://example.com".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.get_instance_metadata(),
+                Some(InstanceMetadata {
+                    name: "Grants".to_string(),
+                    uri: "https://example.com".to_string(),
+                })
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.set_instance_metadata("Other".to_string(), String::new()),
+                Err(Error::CallerIsNotAdmin)
+            );
+        }
+
+        #[ink::test]
+        /// Tests that the discovery feed lists the recently started polls and the polls ending
+        /// soon.
+        fn test_active_feed() {
+            let mut voting_contract = VotingContract::default();
+
+            for (poll_id, end_at) in [(1, 3 * ONE_DAY), (2, ONE_DAY), (3, 2 * ONE_DAY)] {
+                assert_eq!(
+                    voting_contract.create_poll(poll_id, "test".to_string(), 0, end_at),
+                    Ok(())
+                );
+                assert_eq!(
+                    voting_contract.add_choice(poll_id, 1, "test1".to_string()),
+                    Ok(())
+                );
+                assert_eq!(voting_contract.start_poll(poll_id), Ok(()));
+            }
+
+            let feed = voting_contract.get_active_feed();
+            assert_eq!(feed.recently_started, vec![3, 2, 1]);
+            assert_eq!(
+                feed.ending_soon,
+                vec![(2, ONE_DAY), (3, 2 * ONE_DAY), (1, 3 * ONE_DAY)]
+            );
+
+            // Ended polls leave the feed.
+            assert_eq!(voting_contract.end_poll(3), Ok(()));
+
+            let feed = voting_contract.get_active_feed();
+            assert_eq!(feed.recently_started, vec![2, 1]);
+            assert_eq!(feed.ending_soon, vec![(2, ONE_DAY), (1, 3 * ONE_DAY)]);
+
+            // Polls whose voting window has passed are not listed as ending soon.
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(ONE_DAY);
+            assert_eq!(
+                voting_contract.get_active_feed().ending_soon,
+                vec![(1, 3 * ONE_DAY)]
+            );
+        }
+
+        #[ink::test]
+        /// Tests that ranked ballots are only accepted by polls with a ranked tally strategy.
+        fn test_vote_ranked_failure() {
+            let mut voting_contract = VotingContract::default();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract
+                    .add_choices(1, vec![(1, "test1".to_string()), (2, "test2".to_string())]),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.set_tally_strategy(1, TallyStrategy::Stv { seats: 0 }),
+                Err(Error::InvalidSeatCount)
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
 
             assert_eq!(
-                voting_contract.admin,
-                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().alice
+                voting_contract.vote_ranked(1, vec![1, 2]),
+                Err(Error::PollDoesNotAcceptRankedBallots)
+            );
+            assert_eq!(
+                voting_contract.vote_ranked(1, vec![]),
+                Err(Error::InvalidRanking)
+            );
+            assert_eq!(
+                voting_contract.set_tally_strategy(1, TallyStrategy::Condorcet),
+                Err(Error::PollHasStarted)
             );
         }
 
         #[ink::test]
-        /// Tests that `pause` works properly.
-        fn test_contract_pause_success() {
-            let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.paused, false);
-            assert!(voting_contract.pause().is_ok());
-            assert_eq!(voting_contract.paused, true);
+        /// Tests that the default weight source gives every account exactly one vote.
+        fn test_unit_weight_source() {
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(UnitWeight.weight_of(default_accounts.alice), 1);
+            assert_eq!(UnitWeight.weight_of(default_accounts.bob), 1);
         }
 
         #[ink::test]
-        /// Tests that `pause` fails if the caller is not the admin.
-        fn test_contract_pause_failure_not_admin() {
+        /// Tests that `can_vote` reports the same errors as `vote` without mutating state.
+        fn test_can_vote() {
             let mut voting_contract = VotingContract::default();
 
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
-                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().bob,
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.can_vote(1, 1, default_accounts.bob),
+                Err(Error::PollWithIdDoesNotExist)
+            );
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.can_vote(1, 1, default_accounts.bob),
+                Err(Error::PollHasNotStarted)
+            );
+
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.can_vote(1, 2, default_accounts.bob),
+                Err(Error::ChoiceWithIdDoesNotExist)
+            );
+            assert_eq!(voting_contract.can_vote(1, 1, default_accounts.bob), Ok(()));
+
+            // Checking a vote does not record it.
+            assert_eq!(voting_contract.can_vote(1, 1, default_accounts.bob), Ok(()));
+            assert_eq!(voting_contract.get_turnout(1).unwrap().total_ballots, 0);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            assert_eq!(
+                voting_contract.can_vote(1, 1, default_accounts.bob),
+                Err(Error::CallerAlreadyVotedOnPoll)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.pause(), Ok(()));
+            assert_eq!(
+                voting_contract.can_vote(1, 1, default_accounts.charlie),
+                Err(Error::ContractIsPaused)
             );
-            assert_eq!(voting_contract.pause(), Err(Error::CallerIsNotAdmin));
-            assert_eq!(voting_contract.paused, false);
         }
 
         #[ink::test]
-        /// Tests that `unpause` works properly.
-        fn test_contract_unpause_success() {
+        /// Tests that `vote` works properly in failure scenario (contract paused).
+        fn test_vote_failure_contract_paused() {
             let mut voting_contract = VotingContract::default();
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
 
             assert!(voting_contract.pause().is_ok());
 
-            assert!(voting_contract.unpause().is_ok());
-            assert_eq!(voting_contract.paused, false);
+            assert_eq!(voting_contract.vote(1, 1), Err(Error::ContractIsPaused));
         }
 
         #[ink::test]
-        /// Tests that `unpause` fails if the caller is not the admin.
-        fn test_contract_unpause_failure_not_admin() {
+        /// Tests that `vote` works properly in failure scenario (poll does not exist).
+        fn test_vote_failure_poll_does_not_exist() {
             let mut voting_contract = VotingContract::default();
-
-            assert!(voting_contract.pause().is_ok());
-
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
-                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().bob,
+            assert_eq!(
+                voting_contract.vote(1, 1),
+                Err(Error::PollWithIdDoesNotExist)
             );
-            assert_eq!(voting_contract.unpause(), Err(Error::CallerIsNotAdmin));
-            assert_eq!(voting_contract.paused, true);
         }
 
         #[ink::test]
-        /// Tests that `create_poll` works properly in success scenario.
-        fn test_create_poll_success() {
+        /// Tests that `vote` works properly in failure scenario (poll has ended).
+        fn test_vote_failure_poll_has_ended() {
             let mut voting_contract = VotingContract::default();
-
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-
-            // Check if the poll has been created.
-            let poll = voting_contract.polls.get(&1).unwrap();
-            assert_eq!(poll.description, "test".to_string());
-            assert_eq!(poll.status, PollStatus::NotStarted);
             assert_eq!(
-                poll.owner,
-                ink::env::caller::<ink::env::DefaultEnvironment>()
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
             );
-
-            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
-            assert_poll_created_event(
-                &emitted_events[0],
-                1,
-                "test",
-                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().alice,
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
             );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(voting_contract.vote(1, 1), Err(Error::PollHasEnded));
         }
 
         #[ink::test]
-        /// Tests that `create_poll` works properly in failure scenario (contract paused).
-        fn test_create_poll_failure_contract_paused() {
+        /// Tests that `vote` works properly in failure scenario (poll has not started).
+        fn test_vote_failure_poll_has_not_started() {
             let mut voting_contract = VotingContract::default();
-
-            assert!(voting_contract.pause().is_ok());
-
             assert_eq!(
-                voting_contract.create_poll(1, "test".to_string()),
-                Err(Error::ContractIsPaused)
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
             );
+            assert_eq!(voting_contract.vote(1, 1), Err(Error::PollHasNotStarted));
         }
 
         #[ink::test]
-        /// Tests that `create_poll` works properly in failure scenario (duplicate poll id).
-        fn test_create_poll_failure() {
+        /// Tests that `vote` works properly in failure scenario (choice does not exist).
+        fn test_vote_failure_choice_does_not_exist() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
             assert_eq!(
-                voting_contract.create_poll(1, "test1".to_string()),
-                Err(Error::PollWithIdAlreadyExists)
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.vote(1, 2),
+                Err(Error::ChoiceWithIdDoesNotExist)
             );
         }
 
         #[ink::test]
-        /// Tests that `add_choice` works properly in success scenario.
-        fn test_add_choice_success() {
+        /// Tests that `vote` works properly in failure scenario (caller has already voted).
+        fn test_vote_failure_caller_has_already_voted() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
-
-            // Check if the choice has been added to the choice list.
-            assert_eq!(voting_contract.choice_ids.get(1).unwrap().len(), 1);
-
-            // Check if the choice has been added to choices.
-            let choice = voting_contract.choices.get((1, 1)).unwrap();
-            assert_eq!(choice.description, "test".to_string());
-
-            // Add one more choice
             assert_eq!(
-                voting_contract.add_choice(1, 2, "test1".to_string()),
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
                 Ok(())
             );
-
-            // Check if the choice has been added to the choice list.
-            assert_eq!(voting_contract.choice_ids.get(1).unwrap().len(), 2);
-
-            // Check if the choice has been added to choices.
-            let choice = voting_contract.choices.get((1, 2)).unwrap();
-            assert_eq!(choice.description, "test1".to_string());
-
-            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
-            assert_add_choice_event(&emitted_events[1], 1, 1, "test");
-            assert_add_choice_event(&emitted_events[2], 1, 2, "test1");
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            assert_eq!(
+                voting_contract.vote(1, 1),
+                Err(Error::CallerAlreadyVotedOnPoll)
+            );
         }
 
         #[ink::test]
-        /// Tests that `add_choice` works properly in failure scenario (contract paused).
-        fn test_add_choice_failure_contract_paused() {
+        /// Tests that `vote` works properly in failure scenario (voting window has passed).
+        fn test_vote_failure_poll_has_expired() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-
-            assert!(voting_contract.pause().is_ok());
-
             assert_eq!(
-                voting_contract.add_choice(1, 1, "test".to_string()),
-                Err(Error::ContractIsPaused)
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
             );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(ONE_DAY);
+            assert_eq!(voting_contract.vote(1, 1), Err(Error::PollHasEnded));
         }
 
         #[ink::test]
-        /// Tests that `add_choice` works properly in failure scenario (duplicate choice id).
-        fn test_add_choice_failure_duplicate_id() {
+        /// Tests that `vote` works properly in failure scenario (voting window has not opened).
+        fn test_vote_failure_poll_window_not_open() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), ONE_DAY, 2 * ONE_DAY),
+                Ok(())
+            );
             assert_eq!(
                 voting_contract.add_choice(1, 1, "test1".to_string()),
-                Err(Error::ChoiceWithIdAlreadyExists)
+                Ok(())
             );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.vote(1, 1), Err(Error::PollHasNotStarted));
         }
 
         #[ink::test]
-        /// Tests that `add_choice` works properly in failure scenario (poll does not exist).
-        fn test_add_choice_failure_poll_does_not_exist() {
+        /// Tests that per-poll delegations take precedence over global delegations.
+        fn test_delegation_resolution_order() {
             let mut voting_contract = VotingContract::default();
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
             assert_eq!(
-                voting_contract.add_choice(1, 1, "test".to_string()),
-                Err(Error::PollWithIdDoesNotExist)
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.create_poll(2, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.eve);
+            assert_eq!(voting_contract.get_delegate(1, default_accounts.eve), None);
+
+            assert_eq!(
+                voting_contract.delegate_all(default_accounts.bob, None),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.delegate(1, default_accounts.charlie, None),
+                Ok(())
+            );
+
+            // Per-poll delegation overrides the global delegation.
+            assert_eq!(
+                voting_contract.get_delegate(1, default_accounts.eve),
+                Some(default_accounts.charlie)
+            );
+            assert_eq!(
+                voting_contract.get_delegate(2, default_accounts.eve),
+                Some(default_accounts.bob)
+            );
+
+            // Removing the per-poll delegation falls back to the global delegation.
+            assert_eq!(voting_contract.undelegate(1), Ok(()));
+            assert_eq!(
+                voting_contract.get_delegate(1, default_accounts.eve),
+                Some(default_accounts.bob)
+            );
+
+            assert_eq!(voting_contract.undelegate_all(), Ok(()));
+            assert_eq!(voting_contract.get_delegate(2, default_accounts.eve), None);
+
+            assert_eq!(
+                voting_contract.delegate_all(default_accounts.eve, None),
+                Err(Error::CannotDelegateToSelf)
             );
         }
 
         #[ink::test]
-        /// Tests that `add_choice` works properly in failure scenario (poll has started).
-        fn test_add_choice_failure_poll_has_started() {
+        /// Tests that expired delegations are ignored.
+        fn test_delegation_expiry() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
             assert_eq!(
-                voting_contract.add_choice(1, 2, "test2".to_string()),
-                Err(Error::PollHasStarted)
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.eve);
+            assert_eq!(
+                voting_contract.delegate_all(default_accounts.bob, Some(100)),
+                Err(Error::InvalidDelegationExpiry)
+            );
+            assert_eq!(
+                voting_contract.delegate_all(default_accounts.bob, Some(1_000)),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.delegate(1, default_accounts.charlie, Some(500)),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.get_delegation(default_accounts.eve),
+                Some(Delegation {
+                    delegate: default_accounts.bob,
+                    expires_at: Some(1_000),
+                })
+            );
+
+            assert_eq!(
+                voting_contract.get_delegate(1, default_accounts.eve),
+                Some(default_accounts.charlie)
+            );
+
+            // The expired per-poll delegation falls back to the global delegation.
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(500);
+            assert_eq!(
+                voting_contract.get_delegate(1, default_accounts.eve),
+                Some(default_accounts.bob)
             );
-        }
 
-        #[ink::test]
-        /// Tests that `add_choice` works properly in failure scenario (poll has ended).
-        fn test_add_choice_failure_poll_has_ended() {
-            let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.start_poll(1), Ok(()));
-            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+            assert_eq!(voting_contract.get_delegate(1, default_accounts.eve), None);
             assert_eq!(
-                voting_contract.add_choice(1, 2, "test2".to_string()),
-                Err(Error::PollHasEnded)
+                voting_contract.cast_delegated_vote(1, default_accounts.eve),
+                Err(Error::NoDelegation)
             );
         }
 
         #[ink::test]
-        /// Tests that `add_choice` works properly in failure scenario (caller is not owner).
-        fn test_add_choice_failure_caller_is_not_owner() {
+        /// Tests that `cast_delegated_vote` follows the choice of the delegate.
+        fn test_cast_delegated_vote() {
             let mut voting_contract = VotingContract::default();
 
             let default_accounts =
                 ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.eve);
+            assert_eq!(
+                voting_contract.delegate_all(default_accounts.bob, None),
+                Ok(())
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.frank);
+            assert_eq!(
+                voting_contract.delegate(1, default_accounts.bob, None),
+                Ok(())
+            );
+
+            assert_eq!(
+                voting_contract.cast_delegated_vote(1, default_accounts.eve),
+                Err(Error::DelegateHasNotVoted)
+            );
+            assert_eq!(
+                voting_contract.cast_delegated_vote(1, default_accounts.django),
+                Err(Error::NoDelegation)
+            );
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 2), Ok(1));
+
             assert_eq!(
-                voting_contract.add_choice(1, 1, "test".to_string()),
-                Err(Error::OnlyOwnerCanAddChoice)
+                voting_contract.cast_delegated_vote(1, default_accounts.eve),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.cast_delegated_vote(1, default_accounts.eve),
+                Err(Error::CallerAlreadyVotedOnPoll)
             );
+
+            // A delegator who voted directly is not overridden by the delegate.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.frank);
+            assert_eq!(voting_contract.vote(1, 1), Ok(3));
+            assert_eq!(
+                voting_contract.cast_delegated_vote(1, default_accounts.frank),
+                Err(Error::CallerAlreadyVotedOnPoll)
+            );
+
+            let report = voting_contract.get_report(1).unwrap();
+            assert_eq!(report.choices[0].vote_count, 1);
+            assert_eq!(report.choices[1].vote_count, 2);
         }
 
         #[ink::test]
-        /// Tests that `start_poll` works properly in success scenario.
-        fn test_start_poll_success() {
+        /// Tests that `get_report` returns the correct report (poll has not started).
+        fn test_get_report_poll_has_not_started() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
 
-            // Check if the poll has been started.
-            let poll = voting_contract.polls.get(1).unwrap();
-            assert_eq!(poll.status, PollStatus::Started);
+            let report = voting_contract.get_report(1).unwrap();
 
-            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
-            assert_start_poll_event(&emitted_events[2], 1);
-        }
+            assert_eq!(report.id, 1);
+            assert_eq!(report.description, "test".to_string());
+            assert_eq!(report.status, PollStatus::NotStarted);
 
-        #[ink::test]
-        /// Tests that `start_poll` works properly in failure scenario (contract paused).
-        fn test_start_poll_failure_contract_paused() {
-            let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+            assert_eq!(report.choices.len(), 1);
 
-            assert!(voting_contract.pause().is_ok());
+            assert_eq!(report.choices[0].id, 1);
+            assert_eq!(report.choices[0].description, "test1".to_string());
+            assert_eq!(report.choices[0].vote_count, 0);
 
-            assert_eq!(voting_contract.start_poll(1), Err(Error::ContractIsPaused));
+            assert_eq!(report.winner, None);
         }
 
         #[ink::test]
-        /// Tests that `start_poll` works properly in failure scenario (poll does not exist).
-        fn test_start_poll_failure_poll_does_not_exist() {
+        /// Tests that `get_report` returns the correct report (poll has started).
+        fn test_get_report_poll_has_started() {
             let mut voting_contract = VotingContract::default();
             assert_eq!(
-                voting_contract.start_poll(1),
-                Err(Error::PollWithIdDoesNotExist)
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
             );
-        }
 
-        #[ink::test]
-        /// Tests that `start_poll` works properly in failure scenario (poll has started).
-        fn test_start_poll_failure_poll_has_started() {
-            let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
             assert_eq!(voting_contract.start_poll(1), Ok(()));
-            assert_eq!(voting_contract.start_poll(1), Err(Error::PollHasStarted));
-        }
 
-        #[ink::test]
-        /// Tests that `start_poll` works properly in failure scenario (poll has ended).
-        fn test_start_poll_failure_poll_has_ended() {
-            let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.start_poll(1), Ok(()));
-            assert_eq!(voting_contract.end_poll(1), Ok(()));
-            assert_eq!(voting_contract.start_poll(1), Err(Error::PollHasEnded));
-        }
+            let report = voting_contract.get_report(1).unwrap();
 
-        #[ink::test]
-        /// Tests that `start_poll` works properly in failure scenario (caller is not owner).
-        fn test_start_poll_failure_caller_is_not_owner() {
-            let mut voting_contract = VotingContract::default();
+            assert_eq!(report.id, 1);
+            assert_eq!(report.description, "test".to_string());
+            assert_eq!(report.status, PollStatus::Started);
 
-            let default_accounts =
-                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(report.choices.len(), 1);
 
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+            assert_eq!(report.choices[0].id, 1);
+            assert_eq!(report.choices[0].description, "test1".to_string());
+            assert_eq!(report.choices[0].vote_count, 0);
 
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
-            assert_eq!(
-                voting_contract.start_poll(1),
-                Err(Error::OnlyOwnerCanStartPoll)
-            );
+            assert_eq!(report.winner, None);
         }
 
         #[ink::test]
-        /// Tests that `end_poll` works properly in success scenario.
-        fn test_end_poll_success() {
+        /// Tests that `get_report` returns the correct report (poll has started with votes).
+        fn test_get_report_poll_has_started_with_votes() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
             assert_eq!(
                 voting_contract.add_choice(1, 1, "test1".to_string()),
                 Ok(())
@@ -970,238 +11144,369 @@ mod voting_contract {
                 voting_contract.add_choice(1, 2, "test2".to_string()),
                 Ok(())
             );
+
             assert_eq!(voting_contract.start_poll(1), Ok(()));
-            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
 
-            // Check if the poll has been ended.
-            let poll = voting_contract.polls.get(1).unwrap();
-            assert_eq!(poll.status, PollStatus::Ended);
+            let report = voting_contract.get_report(1).unwrap();
 
-            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
-            assert_end_poll_event(&emitted_events[4], 1, None);
-        }
+            assert_eq!(report.id, 1);
+            assert_eq!(report.description, "test".to_string());
+            assert_eq!(report.status, PollStatus::Started);
 
-        #[ink::test]
-        /// Tests that `end_poll` works properly in failure scenario (contract paused).
-        fn test_end_poll_failure_contract_paused() {
-            let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(report.choices.len(), 2);
 
-            assert!(voting_contract.pause().is_ok());
+            assert_eq!(report.choices[0].id, 1);
+            assert_eq!(report.choices[0].description, "test1".to_string());
+            assert_eq!(report.choices[0].vote_count, 1);
 
-            assert_eq!(voting_contract.end_poll(1), Err(Error::ContractIsPaused));
+            assert_eq!(report.choices[1].id, 2);
+            assert_eq!(report.choices[1].description, "test2".to_string());
+            assert_eq!(report.choices[1].vote_count, 0);
+
+            assert_eq!(report.winner, None);
         }
 
         #[ink::test]
-        /// Tests that `end_poll` works properly in failure scenario (poll does not exist).
-        fn test_end_poll_failure_poll_does_not_exist() {
+        /// Tests that `get_report` returns the correct report (poll has ended with votes).
+        fn test_get_report_poll_has_ended_with_votes() {
             let mut voting_contract = VotingContract::default();
             assert_eq!(
-                voting_contract.end_poll(1),
-                Err(Error::PollWithIdDoesNotExist)
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 1, "test1".to_string()),
+                Ok(())
+            );
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Ok(())
             );
-        }
 
-        #[ink::test]
-        /// Tests that `end_poll` works properly in failure scenario (poll has ended).
-        fn test_end_poll_failure_poll_has_ended() {
-            let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
             assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.vote(1, 2), Ok(1));
             assert_eq!(voting_contract.end_poll(1), Ok(()));
-            assert_eq!(voting_contract.end_poll(1), Err(Error::PollHasEnded));
-        }
 
-        #[ink::test]
-        /// Tests that `end_poll` works properly in failure scenario (poll has not started).
-        fn test_end_poll_failure_poll_has_not_started() {
-            let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.end_poll(1), Err(Error::PollHasNotStarted));
-        }
+            let report = voting_contract.get_report(1).unwrap();
 
-        #[ink::test]
-        /// Tests that `end_poll` works properly in failure scenario (caller is not owner).
-        fn test_end_poll_failure_caller_is_not_owner() {
-            let mut voting_contract = VotingContract::default();
+            assert_eq!(report.id, 1);
+            assert_eq!(report.description, "test".to_string());
+            assert_eq!(report.status, PollStatus::Ended);
 
-            let default_accounts =
-                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(report.choices.len(), 2);
 
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.add_choice(1, 1, "test".to_string()), Ok(()));
-            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(report.choices[0].id, 1);
+            assert_eq!(report.choices[0].description, "test1".to_string());
+            assert_eq!(report.choices[0].vote_count, 0);
 
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
-            assert_eq!(voting_contract.end_poll(1), Err(Error::OnlyOwnerCanEndPoll));
+            assert_eq!(report.choices[1].id, 2);
+            assert_eq!(report.choices[1].description, "test2".to_string());
+            assert_eq!(report.choices[1].vote_count, 1);
+
+            assert_eq!(report.winner, Some(2));
         }
 
         #[ink::test]
-        /// Tests that `vote` works properly in success scenario.
-        fn test_vote_success() {
+        /// Tests that `get_turnout` counts every account that voted on the poll.
+        fn test_get_turnout() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
             assert_eq!(
-                voting_contract.add_choice(1, 1, "test1".to_string()),
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
                 Ok(())
             );
-            assert_eq!(voting_contract.start_poll(1), Ok(()));
-            assert_eq!(voting_contract.vote(1, 1), Ok(()));
-
-            // Check if the vote has been registered.
-            let vote_count = voting_contract.vote_counts.get((1, 1)).unwrap();
-            assert_eq!(vote_count, 1);
-            assert!(voting_contract
-                .voted_by
-                .contains(&(1, ink::env::caller::<ink::env::DefaultEnvironment>())));
-        }
-
-        #[ink::test]
-        /// Tests that `vote` works properly in failure scenario (contract paused).
-        fn test_vote_failure_contract_paused() {
-            let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
             assert_eq!(
                 voting_contract.add_choice(1, 1, "test1".to_string()),
                 Ok(())
             );
+            assert_eq!(
+                voting_contract.add_choice(1, 2, "test2".to_string()),
+                Ok(())
+            );
+            assert_eq!(voting_contract.get_turnout(1).unwrap().total_ballots, 0);
+
             assert_eq!(voting_contract.start_poll(1), Ok(()));
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
 
-            assert!(voting_contract.pause().is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 2), Ok(2));
+            assert_eq!(
+                voting_contract.vote(1, 2),
+                Err(Error::CallerAlreadyVotedOnPoll)
+            );
 
-            assert_eq!(voting_contract.vote(1, 1), Err(Error::ContractIsPaused));
-        }
+            let turnout = voting_contract.get_turnout(1).unwrap();
+            assert_eq!(turnout.id, 1);
+            assert_eq!(turnout.total_ballots, 2);
 
-        #[ink::test]
-        /// Tests that `vote` works properly in failure scenario (poll does not exist).
-        fn test_vote_failure_poll_does_not_exist() {
-            let mut voting_contract = VotingContract::default();
             assert_eq!(
-                voting_contract.vote(1, 1),
+                voting_contract.get_turnout(2),
                 Err(Error::PollWithIdDoesNotExist)
             );
         }
 
         #[ink::test]
-        /// Tests that `vote` works properly in failure scenario (poll has ended).
-        fn test_vote_failure_poll_has_ended() {
+        /// Tests that missing choices are skipped by `get_choices`, reported by `get_report` and
+        /// removed from the indexes by `repair_indexes`.
+        fn test_repair_indexes() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
             assert_eq!(
-                voting_contract.add_choice(1, 1, "test1".to_string()),
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
                 Ok(())
             );
-            assert_eq!(voting_contract.start_poll(1), Ok(()));
-            assert_eq!(voting_contract.end_poll(1), Ok(()));
-            assert_eq!(voting_contract.vote(1, 1), Err(Error::PollHasEnded));
-        }
-
-        #[ink::test]
-        /// Tests that `vote` works properly in failure scenario (poll has not started).
-        fn test_vote_failure_poll_has_not_started() {
-            let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
             assert_eq!(
-                voting_contract.add_choice(1, 1, "test1".to_string()),
+                voting_contract
+                    .add_choices(1, vec![(1, "test1".to_string()), (2, "test2".to_string())]),
                 Ok(())
             );
-            assert_eq!(voting_contract.vote(1, 1), Err(Error::PollHasNotStarted));
-        }
 
-        #[ink::test]
-        /// Tests that `vote` works properly in failure scenario (choice does not exist).
-        fn test_vote_failure_choice_does_not_exist() {
-            let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+            // Simulate an index out of sync with the stored choices.
+            voting_contract.choices.remove((1, 1));
+
             assert_eq!(
-                voting_contract.add_choice(1, 1, "test1".to_string()),
-                Ok(())
+                voting_contract.get_choices(1),
+                vec![(
+                    2,
+                    Choice {
+                        description: "test2".to_string().try_into().unwrap()
+                    }
+                )]
             );
-            assert_eq!(voting_contract.start_poll(1), Ok(()));
             assert_eq!(
-                voting_contract.vote(1, 2),
-                Err(Error::ChoiceWithIdDoesNotExist)
+                voting_contract.get_report(1),
+                Err(Error::StorageInconsistency { choice_id: 1 })
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.repair_indexes(1),
+                Err(Error::CallerIsNotAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                voting_contract.repair_indexes(2),
+                Err(Error::PollWithIdDoesNotExist)
             );
+            assert_eq!(voting_contract.repair_indexes(1), Ok(1));
+            assert_eq!(voting_contract.repair_indexes(1), Ok(0));
+            assert!(!voting_contract.has_choice(1, 1));
+            assert_eq!(voting_contract.get_report(1).unwrap().choices.len(), 1);
         }
 
         #[ink::test]
-        /// Tests that `vote` works properly in failure scenario (caller has already voted).
-        fn test_vote_failure_caller_has_already_voted() {
+        /// Tests that `get_vote_count` and `get_vote_counts` report the votes of the choices.
+        fn test_get_vote_counts() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
             assert_eq!(
-                voting_contract.add_choice(1, 1, "test1".to_string()),
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
                 Ok(())
             );
-            assert_eq!(voting_contract.start_poll(1), Ok(()));
-            assert_eq!(voting_contract.vote(1, 1), Ok(()));
             assert_eq!(
-                voting_contract.vote(1, 1),
-                Err(Error::CallerAlreadyVotedOnPoll)
+                voting_contract
+                    .add_choices(1, vec![(1, "test1".to_string()), (2, "test2".to_string())]),
+                Ok(())
             );
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(voting_contract.vote(1, 1), Ok(2));
+
+            assert_eq!(voting_contract.get_vote_count(1, 1), 2);
+            assert_eq!(voting_contract.get_vote_count(1, 2), 0);
+            assert_eq!(voting_contract.get_vote_count(1, 3), 0);
+            assert_eq!(voting_contract.get_vote_count(2, 1), 0);
+            assert_eq!(voting_contract.get_vote_counts(1), vec![(1, 2), (2, 0)]);
+            assert_eq!(voting_contract.get_vote_counts(2), vec![]);
         }
 
         #[ink::test]
-        /// Tests that `get_report` returns the correct report (poll has not started).
-        fn test_get_report_poll_has_not_started() {
+        /// Tests that polls requiring registration only accept votes from accounts registered
+        /// before the poll opens.
+        fn test_registration() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), ONE_DAY, 2 * ONE_DAY),
+                Ok(())
+            );
             assert_eq!(
                 voting_contract.add_choice(1, 1, "test1".to_string()),
                 Ok(())
             );
+            assert_eq!(
+                voting_contract.register(1),
+                Err(Error::PollDoesNotRequireRegistration)
+            );
+            assert_eq!(voting_contract.set_registration(1, Some(100)), Ok(()));
+            assert_eq!(voting_contract.get_registration(1), Some(100));
 
-            let report = voting_contract.get_report(1).unwrap();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                contract_id,
+                1_000_000,
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(99);
+            assert_eq!(voting_contract.register(1), Err(Error::InsufficientDeposit));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(voting_contract.register(1), Ok(()));
+            assert_eq!(
+                voting_contract.register(1),
+                Err(Error::CallerAlreadyRegistered)
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            assert!(voting_contract.is_registered(1, default_accounts.bob));
 
-            assert_eq!(report.id, 1);
-            assert_eq!(report.description, "test".to_string());
-            assert_eq!(report.status, PollStatus::NotStarted);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(ONE_DAY);
 
-            assert_eq!(report.choices.len(), 1);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(
+                voting_contract.register(1),
+                Err(Error::RegistrationIsClosed)
+            );
+            assert_eq!(voting_contract.vote(1, 1), Err(Error::VoterIsNotRegistered));
 
-            assert_eq!(report.choices[0].id, 1);
-            assert_eq!(report.choices[0].description, "test1".to_string());
-            assert_eq!(report.choices[0].vote_count, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            assert_eq!(
+                voting_contract.get_turnout(1),
+                Ok(TurnoutReport {
+                    id: 1,
+                    total_ballots: 1,
+                    registered_voters: 1,
+                    changed_ballots: 0,
+                })
+            );
+            assert_eq!(
+                voting_contract.claim_registration_deposit(1),
+                Err(Error::PollHasNotEnded)
+            );
 
-            assert_eq!(report.winner, None);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            let balance_before =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.bob,
+                )
+                .unwrap();
+            assert_eq!(voting_contract.claim_registration_deposit(1), Ok(()));
+            let balance_after =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                    default_accounts.bob,
+                )
+                .unwrap();
+            assert_eq!(balance_after - balance_before, 100);
+            assert_eq!(
+                voting_contract.claim_registration_deposit(1),
+                Err(Error::NothingToClaim)
+            );
         }
 
         #[ink::test]
-        /// Tests that `get_report` returns the correct report (poll has started).
-        fn test_get_report_poll_has_started() {
+        /// Tests that a poll without enough weight cast to reach its quorum has no winner.
+        fn test_quorum_weight() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
-            assert_eq!(
-                voting_contract.add_choice(1, 1, "test1".to_string()),
-                Ok(())
-            );
 
-            assert_eq!(voting_contract.start_poll(1), Ok(()));
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            let report = voting_contract.get_report(1).unwrap();
+            for poll_id in [1, 2] {
+                assert_eq!(
+                    voting_contract.create_poll(poll_id, "test".to_string(), 0, ONE_DAY),
+                    Ok(())
+                );
+                assert_eq!(
+                    voting_contract.add_choice(poll_id, 1, "test1".to_string()),
+                    Ok(())
+                );
+                assert_eq!(
+                    voting_contract.set_voter_weights(
+                        poll_id,
+                        vec![(default_accounts.bob, 60), (default_accounts.charlie, 40)]
+                    ),
+                    Ok(())
+                );
+                assert_eq!(
+                    voting_contract.set_quorum_weight_bps(poll_id, 10_001),
+                    Err(Error::InvalidQuorum)
+                );
+                assert_eq!(
+                    voting_contract.set_quorum_weight_bps(poll_id, 5_000),
+                    Ok(())
+                );
+                assert_eq!(voting_contract.start_poll(poll_id), Ok(()));
+            }
 
-            assert_eq!(report.id, 1);
-            assert_eq!(report.description, "test".to_string());
-            assert_eq!(report.status, PollStatus::Started);
+            // The eligible weight is the total of the uploaded weights unless attested.
+            let quorum = voting_contract.get_quorum(1).unwrap();
+            assert_eq!(quorum.eligible_weight, 100);
+            assert_eq!(quorum.required_weight, 50);
+            assert!(!quorum.reached);
 
-            assert_eq!(report.choices.len(), 1);
+            assert_eq!(voting_contract.record_eligible_weight(2, 101), Ok(()));
+            assert_eq!(voting_contract.get_quorum(2).unwrap().required_weight, 51);
+            assert_eq!(voting_contract.record_eligible_weight(2, 100), Ok(()));
 
-            assert_eq!(report.choices[0].id, 1);
-            assert_eq!(report.choices[0].description, "test1".to_string());
-            assert_eq!(report.choices[0].vote_count, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.charlie);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                voting_contract.record_eligible_weight(2, 100),
+                Err(Error::OnlyOwnerCanSetQuorum)
+            );
+            assert_eq!(voting_contract.vote(2, 1), Ok(1));
 
-            assert_eq!(report.winner, None);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(voting_contract.end_poll(1), Ok(()));
+            assert_eq!(voting_contract.end_poll(2), Ok(()));
+
+            let quorum = voting_contract.get_quorum(1).unwrap();
+            assert_eq!(quorum.cast_weight, 40);
+            assert!(!quorum.reached);
+            assert_eq!(voting_contract.polls.get(1).unwrap().winner, None);
+            assert!(voting_contract.get_quorum(2).unwrap().reached);
+            assert_eq!(
+                voting_contract.record_eligible_weight(2, 100),
+                Err(Error::PollHasEnded)
+            );
         }
 
         #[ink::test]
-        /// Tests that `get_report` returns the correct report (poll has started with votes).
-        fn test_get_report_poll_has_started_with_votes() {
+        /// Tests that `get_summary` returns the correct summary.
+        fn test_get_summary() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let description = "a".repeat(SUMMARY_DESCRIPTION_LENGTH + 10);
+
+            assert_eq!(
+                voting_contract.create_poll(1, description, 0, ONE_DAY),
+                Ok(())
+            );
             assert_eq!(
                 voting_contract.add_choice(1, 1, "test1".to_string()),
                 Ok(())
@@ -1210,71 +11515,62 @@ mod voting_contract {
                 voting_contract.add_choice(1, 2, "test2".to_string()),
                 Ok(())
             );
-
             assert_eq!(voting_contract.start_poll(1), Ok(()));
-            assert_eq!(voting_contract.vote(1, 1), Ok(()));
-
-            let report = voting_contract.get_report(1).unwrap();
-
-            assert_eq!(report.id, 1);
-            assert_eq!(report.description, "test".to_string());
-            assert_eq!(report.status, PollStatus::Started);
-
-            assert_eq!(report.choices.len(), 2);
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
 
-            assert_eq!(report.choices[0].id, 1);
-            assert_eq!(report.choices[0].description, "test1".to_string());
-            assert_eq!(report.choices[0].vote_count, 1);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(voting_contract.vote(1, 2), Ok(2));
 
-            assert_eq!(report.choices[1].id, 2);
-            assert_eq!(report.choices[1].description, "test2".to_string());
-            assert_eq!(report.choices[1].vote_count, 0);
+            let summary = voting_contract.get_summary(1).unwrap();
+            assert_eq!(summary.id, 1);
+            assert_eq!(
+                summary.short_description,
+                "a".repeat(SUMMARY_DESCRIPTION_LENGTH)
+            );
+            assert_eq!(summary.status, PollStatus::Started);
+            assert_eq!(summary.owner, default_accounts.alice);
+            assert_eq!(summary.total_votes, 2);
+            assert_eq!(summary.winner, None);
 
-            assert_eq!(report.winner, None);
+            assert_eq!(
+                voting_contract.get_summary(2),
+                Err(Error::PollWithIdDoesNotExist)
+            );
         }
 
         #[ink::test]
-        /// Tests that `get_report` returns the correct report (poll has ended with votes).
-        fn test_get_report_poll_has_ended_with_votes() {
+        /// Tests that `get_summaries` returns the summaries in the requested order.
+        fn test_get_summaries() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
             assert_eq!(
-                voting_contract.add_choice(1, 1, "test1".to_string()),
+                voting_contract.create_poll(1, "test1".to_string(), 0, ONE_DAY),
                 Ok(())
             );
             assert_eq!(
-                voting_contract.add_choice(1, 2, "test2".to_string()),
+                voting_contract.create_poll(2, "test2".to_string(), 0, ONE_DAY),
                 Ok(())
             );
 
-            assert_eq!(voting_contract.start_poll(1), Ok(()));
-            assert_eq!(voting_contract.vote(1, 2), Ok(()));
-            assert_eq!(voting_contract.end_poll(1), Ok(()));
-
-            let report = voting_contract.get_report(1).unwrap();
-
-            assert_eq!(report.id, 1);
-            assert_eq!(report.description, "test".to_string());
-            assert_eq!(report.status, PollStatus::Ended);
-
-            assert_eq!(report.choices.len(), 2);
-
-            assert_eq!(report.choices[0].id, 1);
-            assert_eq!(report.choices[0].description, "test1".to_string());
-            assert_eq!(report.choices[0].vote_count, 0);
-
-            assert_eq!(report.choices[1].id, 2);
-            assert_eq!(report.choices[1].description, "test2".to_string());
-            assert_eq!(report.choices[1].vote_count, 1);
+            let summaries = voting_contract.get_summaries(vec![2, 3, 1]).unwrap();
+            assert_eq!(summaries.len(), 3);
+            assert_eq!(summaries[0].as_ref().unwrap().id, 2);
+            assert_eq!(summaries[1], None);
+            assert_eq!(summaries[2].as_ref().unwrap().id, 1);
 
-            assert_eq!(report.winner, Some(2));
+            assert_eq!(
+                voting_contract.get_summaries(vec![1; MAX_SUMMARY_BATCH_SIZE as usize + 1]),
+                Err(Error::BatchTooLarge)
+            );
         }
 
         #[ink::test]
         /// Tests that `get_choices` returns the correct choices.
         fn test_get_choices() {
             let mut voting_contract = VotingContract::default();
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
             assert_eq!(
                 voting_contract.add_choice(1, 1, "test1".to_string()),
                 Ok(())
@@ -1309,7 +11605,10 @@ mod voting_contract {
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
 
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
             assert_eq!(
                 voting_contract.add_choice(1, 1, "test1".to_string()),
                 Ok(())
@@ -1326,30 +11625,35 @@ mod voting_contract {
             assert_eq!(voting_contract.start_poll(1), Ok(()));
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
-            assert_eq!(voting_contract.vote(1, 1), Ok(()));
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.eve);
-            assert_eq!(voting_contract.vote(1, 2), Ok(()));
+            assert_eq!(voting_contract.vote(1, 2), Ok(2));
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
-            assert_eq!(voting_contract.vote(1, 3), Ok(()));
+            assert_eq!(voting_contract.vote(1, 3), Ok(3));
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.django);
-            assert_eq!(voting_contract.vote(1, 2), Ok(()));
+            assert_eq!(voting_contract.vote(1, 2), Ok(4));
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.frank);
-            assert_eq!(voting_contract.vote(1, 2), Ok(()));
+            assert_eq!(voting_contract.vote(1, 2), Ok(5));
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
             assert_eq!(voting_contract.end_poll(1), Ok(()));
 
-            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let emitted_events = recorded_poll_events();
             assert_poll_created_event(&emitted_events[0], 1, "test", default_accounts.alice);
             assert_add_choice_event(&emitted_events[1], 1, 1, "test1");
             assert_add_choice_event(&emitted_events[2], 1, 2, "test2");
             assert_add_choice_event(&emitted_events[3], 1, 3, "test3");
             assert_start_poll_event(&emitted_events[4], 1);
-            assert_end_poll_event(&emitted_events[5], 1, Some(2));
+            assert_vote_cast_event(&emitted_events[5], 1, default_accounts.bob, 1);
+            assert_vote_cast_event(&emitted_events[6], 1, default_accounts.eve, 2);
+            assert_vote_cast_event(&emitted_events[7], 1, default_accounts.alice, 3);
+            assert_vote_cast_event(&emitted_events[8], 1, default_accounts.django, 4);
+            assert_vote_cast_event(&emitted_events[9], 1, default_accounts.frank, 5);
+            assert_end_poll_event(&emitted_events[10], 1, Some(2), 5);
 
             let report = voting_contract.get_report(1).unwrap();
 
@@ -1384,7 +11688,10 @@ mod voting_contract {
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
 
-            assert_eq!(voting_contract.create_poll(1, "test".to_string()), Ok(()));
+            assert_eq!(
+                voting_contract.create_poll(1, "test".to_string(), 0, ONE_DAY),
+                Ok(())
+            );
             assert_eq!(
                 voting_contract.add_choice(1, 1, "test1".to_string()),
                 Ok(())
@@ -1401,30 +11708,35 @@ mod voting_contract {
             assert_eq!(voting_contract.start_poll(1), Ok(()));
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
-            assert_eq!(voting_contract.vote(1, 1), Ok(()));
+            assert_eq!(voting_contract.vote(1, 1), Ok(1));
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.eve);
-            assert_eq!(voting_contract.vote(1, 2), Ok(()));
+            assert_eq!(voting_contract.vote(1, 2), Ok(2));
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
-            assert_eq!(voting_contract.vote(1, 3), Ok(()));
+            assert_eq!(voting_contract.vote(1, 3), Ok(3));
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.django);
-            assert_eq!(voting_contract.vote(1, 2), Ok(()));
+            assert_eq!(voting_contract.vote(1, 2), Ok(4));
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.frank);
-            assert_eq!(voting_contract.vote(1, 3), Ok(()));
+            assert_eq!(voting_contract.vote(1, 3), Ok(5));
 
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
             assert_eq!(voting_contract.end_poll(1), Ok(()));
 
-            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let emitted_events = recorded_poll_events();
             assert_poll_created_event(&emitted_events[0], 1, "test", default_accounts.alice);
             assert_add_choice_event(&emitted_events[1], 1, 1, "test1");
             assert_add_choice_event(&emitted_events[2], 1, 2, "test2");
             assert_add_choice_event(&emitted_events[3], 1, 3, "test3");
             assert_start_poll_event(&emitted_events[4], 1);
-            assert_end_poll_event(&emitted_events[5], 1, None);
+            assert_vote_cast_event(&emitted_events[5], 1, default_accounts.bob, 1);
+            assert_vote_cast_event(&emitted_events[6], 1, default_accounts.eve, 2);
+            assert_vote_cast_event(&emitted_events[7], 1, default_accounts.alice, 3);
+            assert_vote_cast_event(&emitted_events[8], 1, default_accounts.django, 4);
+            assert_vote_cast_event(&emitted_events[9], 1, default_accounts.frank, 5);
+            assert_end_poll_event(&emitted_events[10], 1, None, 5);
 
             let report = voting_contract.get_report(1).unwrap();
 
@@ -1448,5 +11760,541 @@ mod voting_contract {
             assert_eq!(report.choices[2].description, "test3".to_string());
             assert_eq!(report.choices[2].vote_count, 2);
         }
+
+        /// Call of the voting state machine generated by the property-based tests.
+        #[derive(Debug, Clone)]
+        enum Call {
+            CreatePoll {
+                caller: usize,
+                poll_id: PollId,
+            },
+            AddChoice {
+                caller: usize,
+                poll_id: PollId,
+                choice_id: ChoiceId,
+            },
+            StartPoll {
+                caller: usize,
+                poll_id: PollId,
+            },
+            Vote {
+                caller: usize,
+                poll_id: PollId,
+                choice_id: ChoiceId,
+            },
+            EndPoll {
+                caller: usize,
+                poll_id: PollId,
+            },
+        }
+
+        impl quickcheck::Arbitrary for Call {
+            fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+                // Small domains make calls on the same polls, choices and accounts likely.
+                let caller = usize::arbitrary(g) % 3;
+                let poll_id = PollId::arbitrary(g) % 3 + 1;
+                let choice_id = ChoiceId::arbitrary(g) % 3 + 1;
+
+                match u8::arbitrary(g) % 5 {
+                    0 => Call::CreatePoll { caller, poll_id },
+                    1 => Call::AddChoice {
+                        caller,
+                        poll_id,
+                        choice_id,
+                    },
+                    2 => Call::StartPoll { caller, poll_id },
+                    3 => Call::Vote {
+                        caller,
+                        poll_id,
+                        choice_id,
+                    },
+                    _ => Call::EndPoll { caller, poll_id },
+                }
+            }
+        }
+
+        /// Expected state of a poll, tracked alongside the contract by the property-based tests.
+        struct PollModel {
+            owner: usize,
+            status: PollStatus,
+            vote_counts: Vec<(ChoiceId, u128)>,
+            voters: Vec<usize>,
+        }
+
+        /// Replays the calls against a fresh contract and checks that each call succeeds exactly
+        /// when the model allows it and that the reports agree with the model after each call.
+        fn check_state_machine(calls: Vec<Call>) -> bool {
+            ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|accounts| {
+                let accounts = [accounts.alice, accounts.bob, accounts.charlie];
+                let mut voting_contract = VotingContract::default();
+                let mut polls: Vec<(PollId, PollModel)> = Vec::new();
+
+                for call in calls {
+                    let (caller, result, allowed) = match call {
+                        Call::CreatePoll { caller, poll_id } => {
+                            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                                accounts[caller],
+                            );
+                            let result = voting_contract.create_poll(
+                                poll_id,
+                                "test".to_string(),
+                                0,
+                                ONE_DAY,
+                            );
+                            let allowed = !polls.iter().any(|(id, _)| *id == poll_id);
+                            if allowed {
+                                polls.push((
+                                    poll_id,
+                                    PollModel {
+                                        owner: caller,
+                                        status: PollStatus::NotStarted,
+                                        vote_counts: Vec::new(),
+                                        voters: Vec::new(),
+                                    },
+                                ));
+                            }
+                            (caller, result, allowed)
+                        }
+                        Call::AddChoice {
+                            caller,
+                            poll_id,
+                            choice_id,
+                        } => {
+                            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                                accounts[caller],
+                            );
+                            let result =
+                                voting_contract.add_choice(poll_id, choice_id, "test".to_string());
+                            let poll = polls.iter_mut().find(|(id, _)| *id == poll_id);
+                            let allowed = match poll {
+                                Some((_, poll))
+                                    if poll.owner == caller
+                                        && poll.status == PollStatus::NotStarted
+                                        && !poll
+                                            .vote_counts
+                                            .iter()
+                                            .any(|(id, _)| *id == choice_id) =>
+                                {
+                                    poll.vote_counts.push((choice_id, 0));
+                                    true
+                                }
+                                _ => false,
+                            };
+                            (caller, result, allowed)
+                        }
+                        Call::StartPoll { caller, poll_id } => {
+                            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                                accounts[caller],
+                            );
+                            let result = voting_contract.start_poll(poll_id);
+                            let poll = polls.iter_mut().find(|(id, _)| *id == poll_id);
+                            let allowed = match poll {
+                                Some((_, poll))
+                                    if poll.owner == caller
+                                        && poll.status == PollStatus::NotStarted
+                                        && !poll.vote_counts.is_empty() =>
+                                {
+                                    poll.status = PollStatus::Started;
+                                    true
+                                }
+                                _ => false,
+                            };
+                            (caller, result, allowed)
+                        }
+                        Call::Vote {
+                            caller,
+                            poll_id,
+                            choice_id,
+                        } => {
+                            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                                accounts[caller],
+                            );
+                            let result = voting_contract.vote(poll_id, choice_id).map(|_| ());
+                            let poll = polls.iter_mut().find(|(id, _)| *id == poll_id);
+                            let allowed = match poll {
+                                Some((_, poll))
+                                    if poll.status == PollStatus::Started
+                                        && !poll.voters.contains(&caller) =>
+                                {
+                                    match poll
+                                        .vote_counts
+                                        .iter_mut()
+                                        .find(|(id, _)| *id == choice_id)
+                                    {
+                                        Some((_, count)) => {
+                                            *count += 1;
+                                            poll.voters.push(caller);
+                                            true
+                                        }
+                                        None => false,
+                                    }
+                                }
+                                _ => false,
+                            };
+                            (caller, result, allowed)
+                        }
+                        Call::EndPoll { caller, poll_id } => {
+                            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                                accounts[caller],
+                            );
+                            let result = voting_contract.end_poll(poll_id);
+                            let poll = polls.iter_mut().find(|(id, _)| *id == poll_id);
+                            let allowed = match poll {
+                                Some((_, poll))
+                                    if poll.owner == caller
+                                        && poll.status == PollStatus::Started =>
+                                {
+                                    poll.status = PollStatus::Ended;
+                                    true
+                                }
+                                _ => false,
+                            };
+                            (caller, result, allowed)
+                        }
+                    };
+
+                    assert_eq!(result.is_ok(), allowed, "{caller}: {result:?}");
+
+                    for (poll_id, poll) in &polls {
+                        let report = voting_contract.get_report(*poll_id).unwrap();
+                        let vote_counts: Vec<(ChoiceId, u128)> = report
+                            .choices
+                            .iter()
+                            .map(|choice| (choice.id, choice.vote_count))
+                            .collect();
+
+                        // The counts equal the accepted ballots.
+                        assert_eq!(report.status, poll.status);
+                        assert_eq!(vote_counts, poll.vote_counts);
+
+                        // The winner is only known once the poll has ended.
+                        if poll.status == PollStatus::Ended {
+                            assert_eq!(report.winner, crate::tally::plurality(&poll.vote_counts));
+                        } else {
+                            assert_eq!(report.winner, None);
+                        }
+                    }
+                }
+
+                Ok(())
+            })
+            .is_ok()
+        }
+
+        #[test]
+        /// Tests random sequences of calls against a model of the voting state machine.
+        fn test_state_machine() {
+            quickcheck::QuickCheck::new()
+                .tests(200)
+                .quickcheck(check_state_machine as fn(Vec<Call>) -> bool);
+        }
+
+        /// Returns the default account with the given name, as used by the JSON scenarios.
+        fn scenario_account(name: &str) -> AccountId {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            match name {
+                "alice" => accounts.alice,
+                "bob" => accounts.bob,
+                "charlie" => accounts.charlie,
+                "django" => accounts.django,
+                "eve" => accounts.eve,
+                "frank" => accounts.frank,
+                _ => panic!("unknown account `{name}`"),
+            }
+        }
+
+        /// Replays a call of a JSON scenario and returns the `Debug` representation of its
+        /// result.
+        fn replay_call(
+            voting_contract: &mut VotingContract,
+            call: &str,
+            args: &[serde_json::Value],
+        ) -> String {
+            let int_arg = |index: usize| {
+                args.get(index)
+                    .and_then(serde_json::Value::as_u64)
+                    .unwrap_or_else(|| panic!("argument {index} of `{call}` must be an integer"))
+            };
+            let choice_arg = |index: usize| {
+                ChoiceId::try_from(int_arg(index))
+                    .unwrap_or_else(|_| panic!("argument {index} of `{call}` must be a choice id"))
+            };
+            let string_arg = |index: usize| {
+                args.get(index)
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_else(|| panic!("argument {index} of `{call}` must be a string"))
+                    .to_string()
+            };
+            let ranking_arg = |index: usize| {
+                args.get(index)
+                    .and_then(serde_json::Value::as_array)
+                    .unwrap_or_else(|| panic!("argument {index} of `{call}` must be an array"))
+                    .iter()
+                    .map(|choice_id| {
+                        choice_id
+                            .as_u64()
+                            .and_then(|choice_id| ChoiceId::try_from(choice_id).ok())
+                            .unwrap_or_else(|| {
+                                panic!("argument {index} of `{call}` must be a ranking")
+                            })
+                    })
+                    .collect()
+            };
+
+            match call {
+                "pause" => format!("{:?}", voting_contract.pause()),
+                "unpause" => format!("{:?}", voting_contract.unpause()),
+                "add_moderator" => format!(
+                    "{:?}",
+                    voting_contract.add_moderator(scenario_account(&string_arg(0)))
+                ),
+                "create_poll" => format!(
+                    "{:?}",
+                    voting_contract.create_poll(int_arg(0), string_arg(1), int_arg(2), int_arg(3))
+                ),
+                "add_choice" => format!(
+                    "{:?}",
+                    voting_contract.add_choice(int_arg(0), choice_arg(1), string_arg(2))
+                ),
+                "remove_choice" => format!(
+                    "{:?}",
+                    voting_contract.remove_choice(int_arg(0), choice_arg(1))
+                ),
+                "start_poll" => format!("{:?}", voting_contract.start_poll(int_arg(0))),
+                "end_poll" => format!("{:?}", voting_contract.end_poll(int_arg(0))),
+                "finalize_if_expired" => {
+                    format!("{:?}", voting_contract.finalize_if_expired(int_arg(0)))
+                }
+                "remove_poll" => format!("{:?}", voting_contract.remove_poll(int_arg(0))),
+                "vote" => format!("{:?}", voting_contract.vote(int_arg(0), choice_arg(1))),
+                "vote_ranked" => format!(
+                    "{:?}",
+                    voting_contract.vote_ranked(int_arg(0), ranking_arg(1))
+                ),
+                "poll_exists" => format!("{:?}", voting_contract.poll_exists(int_arg(0))),
+                "choice_exists" => format!(
+                    "{:?}",
+                    voting_contract.choice_exists(int_arg(0), choice_arg(1))
+                ),
+                "get_turnout" => format!("{:?}", voting_contract.get_turnout(int_arg(0))),
+                "get_winners" => format!("{:?}", voting_contract.get_winners(int_arg(0))),
+                // Winner of the report of a poll (reports are too verbose to be compared whole).
+                "winner" => format!(
+                    "{:?}",
+                    voting_contract
+                        .get_report(int_arg(0))
+                        .map(|report| report.winner)
+                ),
+                _ => panic!("unsupported call `{call}`"),
+            }
+        }
+
+        /// Replays a JSON scenario against a fresh contract (instantiated by alice) and checks
+        /// the result of each step. See `scenarios/README.md` for the format of the scenarios.
+        fn replay_scenario(path: &std::path::Path) {
+            let scenario: serde_json::Value = std::fs::read_to_string(path)
+                .map_err(|error| error.to_string())
+                .and_then(|json| serde_json::from_str(&json).map_err(|error| error.to_string()))
+                .unwrap_or_else(|error| panic!("cannot read {}: {error}", path.display()));
+
+            ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|_| {
+                let mut voting_contract = VotingContract::default();
+
+                let steps = scenario["steps"]
+                    .as_array()
+                    .unwrap_or_else(|| panic!("{} has no steps", path.display()));
+
+                for (index, step) in steps.iter().enumerate() {
+                    if let Some(timestamp) = step.get("timestamp") {
+                        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                            timestamp.as_u64().expect("timestamp must be an integer"),
+                        );
+                    }
+
+                    ink::env::test::set_caller::<ink::env::DefaultEnvironment>(scenario_account(
+                        step["caller"].as_str().unwrap_or("alice"),
+                    ));
+
+                    let call = step["call"].as_str().expect("call must be a string");
+                    let args = step["args"]
+                        .as_array()
+                        .map(Vec::as_slice)
+                        .unwrap_or_default();
+
+                    assert_eq!(
+                        replay_call(&mut voting_contract, call, args),
+                        step["expect"].as_str().expect("expect must be a string"),
+                        "step {index} (`{call}`) of {}",
+                        path.display()
+                    );
+                }
+
+                Ok(())
+            })
+            .unwrap();
+        }
+
+        #[test]
+        /// Tests that the JSON scenarios of the `scenarios` directory replay as expected.
+        fn test_scenarios() {
+            let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("scenarios");
+
+            let mut paths: Vec<_> = std::fs::read_dir(dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| {
+                    path.extension()
+                        .is_some_and(|extension| extension == "json")
+                })
+                .collect();
+            paths.sort();
+
+            assert!(!paths.is_empty());
+
+            for path in paths {
+                replay_scenario(&path);
+            }
+        }
+    }
+
+    /// Gas benchmarks of the hot paths of the contract. They run against a local contracts node
+    /// (like end-to-end tests) and compare the upgraded version with the original version.
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_benches {
+        use ink_e2e::ContractsBackend;
+
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+
+        /// The end-to-end test `Result` type.
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// Maximum increase (in percent) of the gas consumed by a call in the upgraded version
+        /// over the original version.
+        const MAX_REGRESSION_PERCENT: u64 = 10;
+
+        /// Numbers of choices of the benchmarked polls.
+        const CHOICE_COUNTS: [ChoiceId; 3] = [2, 50, 200];
+
+        #[ink_e2e::test]
+        /// Measures the gas (reference time) consumed by `add_choices`, `vote` and `get_report` on
+        /// polls with 2, 50 and 200 choices and fails if the upgraded version regressed.
+        async fn bench_hot_paths<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let mut measurements: Vec<Vec<(String, u64)>> = Vec::new();
+
+            for contract_name in ["voting_contract", "upgraded_voting_contract"] {
+                let mut constructor = VotingContractRef::default();
+                let contract = client
+                    .instantiate(contract_name, &ink_e2e::alice(), &mut constructor)
+                    .submit()
+                    .await
+                    .expect("instantiate failed");
+                let mut call_builder = contract.call_builder::<VotingContract>();
+
+                let mut gas = Vec::new();
+
+                // Keep the polls open whatever the time of the node.
+                let set_duration_bounds = call_builder.set_duration_bounds(1, Timestamp::MAX);
+                let result = client
+                    .call(&ink_e2e::alice(), &set_duration_bounds)
+                    .submit()
+                    .await
+                    .expect("set_duration_bounds failed");
+                assert_eq!(result.return_value(), Ok(()));
+
+                for num_choices in CHOICE_COUNTS {
+                    let poll_id = PollId::from(num_choices);
+
+                    let create_poll =
+                        call_builder.create_poll(poll_id, "bench".to_string(), 0, Timestamp::MAX);
+                    let result = client
+                        .call(&ink_e2e::alice(), &create_poll)
+                        .submit()
+                        .await
+                        .expect("create_poll failed");
+                    assert_eq!(result.return_value(), Ok(()));
+
+                    // Add the choices in batches of the maximum size. The last choice (the one
+                    // voted for) has the longest description, so that the cost of checking that
+                    // it exists is measured in the worst case.
+                    let choices: Vec<(ChoiceId, String)> = (1..=num_choices)
+                        .map(|choice_id| {
+                            if choice_id == num_choices {
+                                (choice_id, "c".repeat(MAX_DESCRIPTION_LENGTH as usize))
+                            } else {
+                                (choice_id, format!("choice{choice_id}"))
+                            }
+                        })
+                        .collect();
+                    let mut add_choices_gas = 0;
+
+                    for batch in choices.chunks(MAX_CHOICE_BATCH_SIZE as usize) {
+                        let add_choices = call_builder.add_choices(poll_id, batch.to_vec());
+                        let result = client
+                            .call(&ink_e2e::alice(), &add_choices)
+                            .submit()
+                            .await
+                            .expect("add_choices failed");
+                        add_choices_gas += result.dry_run.exec_result.gas_consumed.ref_time();
+                        assert_eq!(result.return_value(), Ok(()));
+                    }
+
+                    gas.push((format!("add_choices ({num_choices})"), add_choices_gas));
+
+                    let start_poll = call_builder.start_poll(poll_id);
+                    let result = client
+                        .call(&ink_e2e::alice(), &start_poll)
+                        .submit()
+                        .await
+                        .expect("start_poll failed");
+                    assert_eq!(result.return_value(), Ok(()));
+
+                    // Vote for the last choice, which is the most expensive to look up.
+                    let vote = call_builder.vote(poll_id, num_choices);
+                    let result = client
+                        .call(&ink_e2e::bob(), &vote)
+                        .submit()
+                        .await
+                        .expect("vote failed");
+                    gas.push((
+                        format!("vote ({num_choices})"),
+                        result.dry_run.exec_result.gas_consumed.ref_time(),
+                    ));
+                    assert_eq!(result.return_value(), Ok(1));
+
+                    let get_report = call_builder.get_report(poll_id);
+                    let result = client
+                        .call(&ink_e2e::alice(), &get_report)
+                        .dry_run()
+                        .await
+                        .expect("get_report failed");
+                    gas.push((
+                        format!("get_report ({num_choices})"),
+                        result.exec_result.gas_consumed.ref_time(),
+                    ));
+                    assert!(result.return_value().is_ok());
+                }
+
+                measurements.push(gas);
+            }
+
+            println!(
+                "{:<24} {:>16} {:>16}",
+                "call (choices)", "original", "upgraded"
+            );
+
+            for ((name, original), (_, upgraded)) in measurements[0].iter().zip(&measurements[1]) {
+                println!("{name:<24} {original:>16} {upgraded:>16}");
+
+                assert!(
+                    upgraded * 100 <= original * (100 + MAX_REGRESSION_PERCENT),
+                    "gas of {name} regressed by more than {MAX_REGRESSION_PERCENT}%: \
+                     {original} -> {upgraded}",
+                );
+            }
+
+            Ok(())
+        }
     }
 }
@@ -0,0 +1,127 @@
+//! Incremental Merkle tree of the ballots of a poll, independent from the storage of the contract.
+//!
+//! The tree has a fixed depth of `DEPTH` levels, the missing leaves being zero. Only the left
+//! siblings of the path of the next leaf are stored (one node per level), so that leaves are
+//! appended and the root is computed in at most `DEPTH` hashes without storing the leaves. Nodes
+//! are the BLAKE2b-256 hash of the concatenation of their children.
+
+use ink::{env::hash::Blake2x256, prelude::vec::Vec};
+
+/// Number of levels of the tree (the tree holds up to `2^DEPTH` leaves).
+pub const DEPTH: usize = 32;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Incremental Merkle tree.
+pub struct MerkleTree {
+    /// Number of leaves of the tree.
+    count: u64,
+    /// Left siblings of the path of the next leaf, from the leaves up.
+    branch: Vec<[u8; 32]>,
+}
+
+impl MerkleTree {
+    /// Appends a leaf to the tree. Leaves appended to a full tree are ignored.
+    pub fn push(&mut self, leaf: [u8; 32]) {
+        if self.count >= 1 << DEPTH {
+            return;
+        }
+
+        self.count += 1;
+
+        let mut node = leaf;
+        let mut size = self.count;
+
+        for level in 0..DEPTH {
+            // Store the node if it is a left child, its parent is computed with the next leaves.
+            if size & 1 == 1 {
+                if level == self.branch.len() {
+                    self.branch.push(node);
+                } else {
+                    self.branch[level] = node;
+                }
+
+                return;
+            }
+
+            node = hash_pair(&self.branch[level], &node);
+            size >>= 1;
+        }
+    }
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> [u8; 32] {
+        let mut node = [0; 32];
+        let mut zero = [0; 32];
+        let mut size = self.count;
+
+        for level in 0..DEPTH {
+            node = if size & 1 == 1 {
+                hash_pair(&self.branch[level], &node)
+            } else {
+                hash_pair(&node, &zero)
+            };
+
+            zero = hash_pair(&zero, &zero);
+            size >>= 1;
+        }
+
+        node
+    }
+}
+
+/// Returns the parent of two nodes.
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut input = [0; 64];
+    input[..32].copy_from_slice(left);
+    input[32..].copy_from_slice(right);
+
+    let mut output = [0; 32];
+    ink::env::hash_bytes::<Blake2x256>(&input, &mut output);
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the root of the tree of the given leaves, computed level by level.
+    fn naive_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+        let mut nodes = leaves.to_vec();
+        let mut zero = [0; 32];
+
+        for _ in 0..DEPTH {
+            if nodes.len() % 2 == 1 {
+                nodes.push(zero);
+            }
+
+            nodes = nodes
+                .chunks(2)
+                .map(|pair| hash_pair(&pair[0], &pair[1]))
+                .collect();
+            zero = hash_pair(&zero, &zero);
+        }
+
+        nodes.first().copied().unwrap_or(zero)
+    }
+
+    /// Tests that the root of the incremental tree matches the root computed from all leaves.
+    #[test]
+    fn test_root() {
+        let mut tree = MerkleTree::default();
+        let mut leaves = Vec::new();
+
+        assert_eq!(tree.root(), naive_root(&leaves));
+
+        for leaf in 1..=9 {
+            tree.push([leaf; 32]);
+            leaves.push([leaf; 32]);
+
+            assert_eq!(tree.root(), naive_root(&leaves));
+        }
+    }
+}
@@ -0,0 +1,409 @@
+//! Winner computation of the tally strategies, independent from the storage of the contract.
+//!
+//! The functions of this module operate on plain vote counts and ballots so that they can be
+//! unit tested (and fuzzed) off-chain.
+
+use ink::prelude::{vec, vec::Vec};
+
+/// Defines the type of choice identifiers.
+pub type ChoiceId = u32;
+
+/// Fraction of a vote (as its inverse) tracked when transferring STV surpluses. The tallies
+/// recorded in the rounds of an STV count are in units of `1 / STV_PRECISION` of a vote.
+pub const STV_PRECISION: u128 = 1_000_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Record of a round of an STV count.
+pub struct CountRound {
+    /// Votes (in units of `1 / STV_PRECISION` of a vote) of each continuing choice at the
+    /// start of the round.
+    pub tallies: Vec<(ChoiceId, u128)>,
+    /// Choices elected in the round.
+    pub elected: Vec<ChoiceId>,
+    /// Choice eliminated in the round (present if no choice was elected).
+    pub eliminated: Option<ChoiceId>,
+}
+
+/// Returns the choice with the highest count (not present if there are no choices or in case
+/// of a tie for the highest count).
+pub fn plurality(counts: &[(ChoiceId, u128)]) -> Option<ChoiceId> {
+    let (first, rest) = counts.split_first()?;
+
+    let mut candidate_winner = first;
+    let mut found_tie = false;
+
+    for count in rest {
+        if count.1 == candidate_winner.1 {
+            found_tie = true;
+        }
+
+        if count.1 > candidate_winner.1 {
+            candidate_winner = count;
+            found_tie = false;
+        }
+    }
+
+    if found_tie {
+        None
+    } else {
+        Some(candidate_winner.0)
+    }
+}
+
+/// Returns the Smith set of the given choices: the smallest set of choices which are each
+/// preferred over every choice outside of the set.
+///
+/// `preference(a, b)` returns the number of votes preferring choice `a` over choice `b`.
+pub fn smith_set(
+    choices: &[ChoiceId],
+    preference: impl Fn(ChoiceId, ChoiceId) -> u128,
+) -> Vec<ChoiceId> {
+    let n = choices.len();
+
+    // `reaches[i][j]` is true if choice `i` is not beaten by choice `j`, directly or
+    // through a chain of choices.
+    let mut reaches: Vec<Vec<bool>> = choices
+        .iter()
+        .map(|a| {
+            choices
+                .iter()
+                .map(|b| preference(*a, *b) >= preference(*b, *a))
+                .collect()
+        })
+        .collect();
+
+    for k in 0..n {
+        let via = reaches[k].clone();
+        for row in reaches.iter_mut().filter(|row| row[k]) {
+            for (reached, reached_via) in row.iter_mut().zip(&via) {
+                *reached |= reached_via;
+            }
+        }
+    }
+
+    // The Smith set contains the choices that are not beaten by every other choice.
+    (0..n)
+        .filter(|&i| reaches[i].iter().all(|reached| *reached))
+        .map(|i| choices[i])
+        .collect()
+}
+
+/// Fills the given number of seats by single transferable vote and returns the elected choices
+/// (in order of election) along with the rounds of the count.
+///
+/// Each round, the choice with the most votes is elected if it reaches the Droop quota and
+/// its surplus is transferred to the next preferences of its ballots at a reduced value.
+/// Otherwise, the choice with the fewest votes is eliminated and its ballots are transferred
+/// at their full value. Ties are broken by electing or eliminating the choice listed first.
+pub fn single_transferable_vote(
+    choices: &[ChoiceId],
+    ballots: &[(Vec<ChoiceId>, u128)],
+    seats: u32,
+) -> (Vec<ChoiceId>, Vec<CountRound>) {
+    let mut continuing = choices.to_vec();
+    let mut ballots: Vec<(&[ChoiceId], u128)> = ballots
+        .iter()
+        .map(|(ranking, weight)| (&ranking[..], weight.saturating_mul(STV_PRECISION)))
+        .collect();
+
+    let total = ballots
+        .iter()
+        .fold(0u128, |total, (_, value)| total.saturating_add(*value));
+    let quota = total / (u128::from(seats) + 1) + 1;
+
+    let mut elected: Vec<ChoiceId> = Vec::new();
+    let mut rounds = Vec::new();
+
+    while elected.len() < seats as usize && !continuing.is_empty() {
+        // Count each ballot for its most preferred continuing choice.
+        let tallies: Vec<(ChoiceId, u128)> = continuing
+            .iter()
+            .map(|choice_id| {
+                let votes = ballots
+                    .iter()
+                    .filter(|(ranking, _)| {
+                        ranking.iter().find(|c| continuing.contains(c)) == Some(choice_id)
+                    })
+                    .fold(0u128, |votes, (_, value)| votes.saturating_add(*value));
+
+                (*choice_id, votes)
+            })
+            .collect();
+
+        let (leader, leader_votes) =
+            tallies.iter().fold(
+                tallies[0],
+                |best, tally| {
+                    if tally.1 > best.1 {
+                        *tally
+                    } else {
+                        best
+                    }
+                },
+            );
+        let (last, _) =
+            tallies.iter().fold(
+                tallies[0],
+                |worst, tally| {
+                    if tally.1 < worst.1 {
+                        *tally
+                    } else {
+                        worst
+                    }
+                },
+            );
+
+        let mut round = CountRound {
+            tallies,
+            elected: Vec::new(),
+            eliminated: None,
+        };
+
+        if continuing.len() <= seats as usize - elected.len() {
+            // Every continuing choice fills one of the remaining seats.
+            round.elected = core::mem::take(&mut continuing);
+        } else if leader_votes >= quota {
+            // Transfer the surplus of the elected choice.
+            let surplus = leader_votes - quota;
+
+            for (ranking, value) in ballots.iter_mut() {
+                if ranking.iter().find(|c| continuing.contains(c)) == Some(&leader) {
+                    *value = value.saturating_mul(surplus) / leader_votes;
+                }
+            }
+
+            continuing.retain(|choice_id| *choice_id != leader);
+            round.elected = vec![leader];
+        } else {
+            continuing.retain(|choice_id| *choice_id != last);
+            round.eliminated = Some(last);
+        }
+
+        elected.extend_from_slice(&round.elected);
+        rounds.push(round);
+    }
+
+    (elected, rounds)
+}
+
+/// Returns the index of the bucket of a histogram holding the median of the counted values (the
+/// lower median if their number is even), or `None` if the histogram is empty. Each bucket holds
+/// the number (or the total weight) of the values falling into it.
+pub fn median_bucket(histogram: &[u128]) -> Option<usize> {
+    let total = histogram
+        .iter()
+        .fold(0u128, |total, count| total.saturating_add(*count));
+
+    if total == 0 {
+        return None;
+    }
+
+    // Rank of the median among the counted values (starting at 1).
+    let rank = total / 2 + total % 2;
+    let mut counted = 0u128;
+
+    histogram.iter().position(|count| {
+        counted = counted.saturating_add(*count);
+        counted >= rank
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the number of votes preferring `a` over `b` in the given ballots.
+    fn preference(ballots: &[(Vec<ChoiceId>, u128)], a: ChoiceId, b: ChoiceId) -> u128 {
+        ballots
+            .iter()
+            .filter(|(ranking, _)| {
+                let position = |c| ranking.iter().position(|r| *r == c);
+                match (position(a), position(b)) {
+                    (Some(a), Some(b)) => a < b,
+                    (Some(_), None) => true,
+                    _ => false,
+                }
+            })
+            .map(|(_, weight)| weight)
+            .sum()
+    }
+
+    /// Tests that plurality reports no winner when there are no choices.
+    #[test]
+    fn test_plurality_without_choices() {
+        assert_eq!(plurality(&[]), None);
+    }
+
+    /// Tests that plurality reports the only choice as winner, even without votes.
+    #[test]
+    fn test_plurality_single_choice() {
+        assert_eq!(plurality(&[(7, 0)]), Some(7));
+        assert_eq!(plurality(&[(7, 3)]), Some(7));
+    }
+
+    /// Tests that plurality reports no winner when no choice received votes.
+    #[test]
+    fn test_plurality_zero_votes() {
+        assert_eq!(plurality(&[(1, 0), (2, 0), (3, 0)]), None);
+    }
+
+    /// Tests that plurality reports the choice with the most votes, wherever it is listed.
+    #[test]
+    fn test_plurality_winner() {
+        assert_eq!(plurality(&[(1, 5), (2, 3), (3, 1)]), Some(1));
+        assert_eq!(plurality(&[(1, 1), (2, 5), (3, 3)]), Some(2));
+        assert_eq!(plurality(&[(1, 1), (2, 3), (3, 5)]), Some(3));
+    }
+
+    /// Tests that plurality reports no winner in case of a tie for the most votes only.
+    #[test]
+    fn test_plurality_ties() {
+        assert_eq!(plurality(&[(1, 5), (2, 5)]), None);
+        assert_eq!(plurality(&[(1, 5), (2, 5), (3, 3)]), None);
+        assert_eq!(plurality(&[(1, 3), (2, 5), (3, 5)]), None);
+        assert_eq!(plurality(&[(1, 3), (2, 3), (3, 5)]), Some(3));
+        assert_eq!(plurality(&[(1, 5), (2, 3), (3, 3)]), Some(1));
+    }
+
+    /// Tests that plurality handles vote counts at the limits of `u128`.
+    #[test]
+    fn test_plurality_overflow() {
+        assert_eq!(plurality(&[(1, u128::MAX), (2, u128::MAX - 1)]), Some(1));
+        assert_eq!(plurality(&[(1, u128::MAX - 1), (2, u128::MAX)]), Some(2));
+        assert_eq!(plurality(&[(1, u128::MAX), (2, u128::MAX)]), None);
+    }
+
+    /// Tests that the Smith set of a Condorcet winner contains only that choice.
+    #[test]
+    fn test_smith_set_condorcet_winner() {
+        let ballots = vec![(vec![1, 2, 3], 2), (vec![2, 1, 3], 1), (vec![3, 1, 2], 1)];
+
+        assert_eq!(
+            smith_set(&[1, 2, 3], |a, b| preference(&ballots, a, b)),
+            vec![1]
+        );
+    }
+
+    /// Tests that the Smith set of a preference cycle contains every choice of the cycle.
+    #[test]
+    fn test_smith_set_cycle() {
+        let ballots = vec![
+            (vec![1, 2, 3, 4], 1),
+            (vec![2, 3, 1, 4], 1),
+            (vec![3, 1, 2, 4], 1),
+        ];
+
+        assert_eq!(
+            smith_set(&[1, 2, 3, 4], |a, b| preference(&ballots, a, b)),
+            vec![1, 2, 3]
+        );
+    }
+
+    /// Tests that the Smith set contains every choice without votes or with a single choice.
+    #[test]
+    fn test_smith_set_zero_votes_and_single_choice() {
+        assert_eq!(smith_set(&[], |_, _| 0), Vec::<ChoiceId>::new());
+        assert_eq!(smith_set(&[1], |_, _| 0), vec![1]);
+        assert_eq!(smith_set(&[1, 2, 3], |_, _| 0), vec![1, 2, 3]);
+    }
+
+    /// Tests that the Smith set handles preferences at the limits of `u128`.
+    #[test]
+    fn test_smith_set_overflow() {
+        let preference = |a, _| if a == 2 { u128::MAX } else { u128::MAX - 1 };
+
+        assert_eq!(smith_set(&[1, 2], preference), vec![2]);
+        assert_eq!(smith_set(&[1, 2], |_, _| u128::MAX), vec![1, 2]);
+    }
+
+    /// Tests that STV elects no choice without seats or choices.
+    #[test]
+    fn test_stv_without_seats_or_choices() {
+        let ballots = vec![(vec![1], 1)];
+
+        assert_eq!(
+            single_transferable_vote(&[1], &ballots, 0),
+            (vec![], vec![])
+        );
+        assert_eq!(single_transferable_vote(&[], &ballots, 1), (vec![], vec![]));
+    }
+
+    /// Tests that STV elects the only choice, even without votes.
+    #[test]
+    fn test_stv_single_choice() {
+        let (elected, rounds) = single_transferable_vote(&[1], &[], 1);
+
+        assert_eq!(elected, vec![1]);
+        assert_eq!(rounds.len(), 1);
+        assert_eq!(rounds[0].tallies, vec![(1, 0)]);
+    }
+
+    /// Tests that STV eliminates choices in order of listing when no choice received votes.
+    #[test]
+    fn test_stv_zero_votes() {
+        let (elected, rounds) = single_transferable_vote(&[1, 2, 3], &[], 1);
+
+        assert_eq!(elected, vec![3]);
+        assert_eq!(rounds[0].eliminated, Some(1));
+        assert_eq!(rounds[1].eliminated, Some(2));
+        assert_eq!(rounds[2].elected, vec![3]);
+    }
+
+    /// Tests that STV transfers surpluses and eliminated ballots to the next preferences.
+    #[test]
+    fn test_stv_transfers() {
+        let ballots = vec![(vec![1, 2], 4), (vec![3], 2), (vec![4, 2], 1)];
+        let (elected, rounds) = single_transferable_vote(&[1, 2, 3, 4], &ballots, 2);
+
+        // Quota: 7 / 3 + 1 = 3 (in units of `1 / STV_PRECISION`).
+        assert_eq!(elected, vec![1, 2]);
+        assert_eq!(rounds[0].elected, vec![1]);
+        assert_eq!(rounds[1].eliminated, Some(4));
+        assert_eq!(
+            rounds[1].tallies,
+            vec![
+                (2, 4 * STV_PRECISION - (7 * STV_PRECISION / 3 + 1)),
+                (3, 2 * STV_PRECISION),
+                (4, STV_PRECISION),
+            ]
+        );
+        assert_eq!(rounds[2].elected, vec![2]);
+    }
+
+    /// Tests that STV eliminates the choice listed first in case of a tie for the fewest votes.
+    #[test]
+    fn test_stv_ties() {
+        let ballots = vec![(vec![1], 1), (vec![2], 1), (vec![3], 1)];
+        let (elected, rounds) = single_transferable_vote(&[1, 2, 3], &ballots, 1);
+
+        assert_eq!(rounds[0].eliminated, Some(1));
+        assert_eq!(rounds[1].eliminated, Some(2));
+        assert_eq!(elected, vec![3]);
+    }
+
+    /// Tests that STV saturates ballot values at the limits of `u128`.
+    #[test]
+    fn test_stv_overflow() {
+        let ballots = vec![(vec![1], u128::MAX), (vec![2], u128::MAX), (vec![1], 1)];
+        let (elected, rounds) = single_transferable_vote(&[1, 2], &ballots, 1);
+
+        assert_eq!(elected, vec![1]);
+        assert_eq!(rounds[0].tallies, vec![(1, u128::MAX), (2, u128::MAX)]);
+    }
+
+    /// Tests that the median bucket is the first one reaching half of the counted values.
+    #[test]
+    fn test_median_bucket() {
+        assert_eq!(median_bucket(&[]), None);
+        assert_eq!(median_bucket(&[0, 0]), None);
+        assert_eq!(median_bucket(&[1, 1, 1]), Some(1));
+        assert_eq!(median_bucket(&[1, 0, 0, 1]), Some(0));
+        assert_eq!(median_bucket(&[0, 0, 5]), Some(2));
+        assert_eq!(median_bucket(&[3, 1, 1]), Some(0));
+        assert_eq!(median_bucket(&[u128::MAX, u128::MAX]), Some(0));
+    }
+}
@@ -0,0 +1,23 @@
+//! Admin log helpers of the voting contract.
+
+use super::*;
+
+impl VotingContract {
+    /// Appends an administrative action performed by the caller to the admin log.
+    pub(crate) fn log_admin_action<P: scale::Encode>(&mut self, action: AdminAction, payload: &P) {
+        let index = self.admin_log_length;
+
+        let entry = AdminLogEntry {
+            index,
+            action,
+            actor: self.env().caller(),
+            timestamp: self.env().block_timestamp(),
+            payload_hash: self
+                .env()
+                .hash_encoded::<ink::env::hash::Blake2x256, _>(payload),
+        };
+
+        self.admin_log.insert(index % ADMIN_LOG_CAPACITY, &entry);
+        self.admin_log_length = index + 1;
+    }
+}
@@ -0,0 +1,303 @@
+//! Errors returned by the voting contract.
+
+use super::*;
+
+/// Errors that can occur in the voting contract.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    /// Returned if the poll with the given id already exist.
+    PollWithIdAlreadyExists,
+    /// Returned if the poll with the given id does not exist.
+    PollWithIdDoesNotExist,
+    /// Returned if the poll has not started yet.
+    PollHasNotStarted,
+    /// Returned if the poll has ended.
+    PollHasEnded,
+    /// Returned if the poll has already started.
+    PollHasStarted,
+    /// Returned if the caller is not the owner of the poll.
+    OnlyOwnerCanAddChoice,
+    /// Returned if the caller is not the owner of the poll.
+    OnlyOwnerCanStartPoll,
+    /// Returned if the owner tries to start a poll with no choices (and no questions).
+    CannotStartPollWithNoChoices,
+    /// Returned if the caller is neither the owner nor the finalizer of the poll.
+    OnlyOwnerCanEndPoll,
+    /// Returned if the choice with the given id already exist.
+    ChoiceWithIdAlreadyExists,
+    /// Returned if the choice with the given id does not exist.
+    ChoiceWithIdDoesNotExist,
+    /// Returned if the caller has already voted on the poll.
+    CallerAlreadyVotedOnPoll,
+    /// Returned when the contract is paused by the admin.
+    ContractIsPaused,
+    /// Returned when the caller is not the admin.
+    CallerIsNotAdmin,
+    /// Returned when the contract fails to set the code hash.
+    FailedToSetCodeHash(String),
+    /// Returned if the duration of a poll is outside the bounds configured by the admin.
+    InvalidPollDuration,
+    /// Returned if the voting window of the poll has not passed yet.
+    PollHasNotExpired,
+    /// Returned when the contract fails to transfer funds.
+    TransferFailed,
+    /// Returned if the weight of the caller's vote is zero.
+    CallerHasNoVotingWeight,
+    /// Returned if no more choice ids can be assigned automatically for the poll.
+    ChoiceIdsExhausted,
+    /// Returned if the storage of the poll is already in the current layout.
+    PollAlreadyMigrated,
+    /// Returned if too many items are requested in a single call.
+    BatchTooLarge,
+    /// Returned when voting is paused by the admin.
+    VotingIsPaused,
+    /// Returned when poll creation is paused by the admin.
+    PollCreationIsPaused,
+    /// Returned when upgrades are paused by the admin.
+    UpgradesArePaused,
+    /// Returned if an account tries to delegate its vote to itself.
+    CannotDelegateToSelf,
+    /// Returned if the account has not delegated its vote on the poll.
+    NoDelegation,
+    /// Returned if the delegate has not voted on the poll yet.
+    DelegateHasNotVoted,
+    /// Returned if the value transferred when creating a poll is less than the poll deposit.
+    InsufficientDeposit,
+    /// Returned if the caller does not have the moderator role.
+    CallerIsNotModerator,
+    /// Returned if the caller is not the owner of the poll when claiming its deposit.
+    OnlyOwnerCanClaimDeposit,
+    /// Returned if a deposit is claimed before the poll has ended.
+    PollHasNotEnded,
+    /// Returned if the poll has no deposit to claim (never paid, already claimed or slashed).
+    NoDepositToClaim,
+    /// Returned if the creator allow-list is enabled and the caller is not on it.
+    CallerIsNotAllowedToCreatePoll,
+    /// Returned if a poll cannot move from its current status to the requested status.
+    InvalidStatusTransition { from: PollStatus, to: PollStatus },
+    /// Returned if the caller is not the owner of the poll when setting its observer.
+    OnlyOwnerCanSetObserver,
+    /// Returned if the caller is not the owner of the poll when changing its prediction mode.
+    OnlyOwnerCanSetPredictionMode,
+    /// Returned if value is transferred with a vote on a poll that is not in prediction mode.
+    PollDoesNotAcceptStakes,
+    /// Returned if the caller has no winnings to claim on the poll.
+    NothingToClaim,
+    /// Returned if an arithmetic operation overflows.
+    ArithmeticOverflow,
+    /// Returned if the protocol fee exceeds the maximum allowed fee.
+    FeeTooHigh,
+    /// Returned if native value is transferred for a poll paid in a PSP22 token.
+    NativeValueNotAccepted,
+    /// Returned if tokens are offered for a poll paid in the native currency.
+    PollDoesNotUseTokens,
+    /// Returned if a PSP22 token transfer fails.
+    TokenTransferFailed,
+    /// Returned if the caller is not the owner of the poll when changing the presentation of
+    /// its choices.
+    OnlyOwnerCanOrderChoices,
+    /// Returned if the new order of choices is not a permutation of the choices of the poll.
+    InvalidChoiceOrder,
+    /// Returned if the caller is not the owner of the poll when uploading voter weights.
+    OnlyOwnerCanSetVoterWeights,
+    /// Returned if the caller is not the owner of the poll when updating or removing a choice.
+    OnlyOwnerCanEditChoice,
+    /// Returned if the expiry of a delegation is not in the future.
+    InvalidDelegationExpiry,
+    /// Returned if the caller is not the owner of the poll when changing its tally strategy.
+    OnlyOwnerCanSetTallyStrategy,
+    /// Returned if a ranked ballot is cast on a poll whose tally strategy is not ranked.
+    PollDoesNotAcceptRankedBallots,
+    /// Returned if a ranking is empty or contains a choice more than once.
+    InvalidRanking,
+    /// Returned if an STV tally strategy does not fill any seat.
+    InvalidSeatCount,
+    /// Returned if a poll or choice description contains a term of the denylist.
+    DescriptionContainsDeniedTerm,
+    /// Returned if the caller is not the owner of the poll when enabling private voting.
+    OnlyOwnerCanSetPrivateVoting,
+    /// Returned if a voting key is registered for or used on a poll without private voting.
+    PollIsNotPrivate,
+    /// Returned if an account votes directly on a poll that only accepts private votes.
+    PollRequiresPrivateVote,
+    /// Returned if the caller or the key has already been registered for the poll.
+    VotingKeyAlreadyRegistered,
+    /// Returned if a private vote is not signed by a key registered for the poll.
+    VotingKeyNotRegistered,
+    /// Returned if the signature of a private vote cannot be verified.
+    InvalidSignature,
+    /// Returned if the caller is not the owner of the poll when setting its verifier key.
+    OnlyOwnerCanSetVerifierKey,
+    /// Returned if an anonymous vote is cast on a poll without a verifier key.
+    PollDoesNotAcceptAnonymousVotes,
+    /// Returned if no membership verifier is configured.
+    NoMembershipVerifier,
+    /// Returned if the nullifier of an anonymous vote has already been used on the poll.
+    NullifierAlreadyUsed,
+    /// Returned if the membership proof of an anonymous vote is rejected by the verifier.
+    InvalidProof,
+    /// Returned if the caller is not the owner of the poll when configuring ballot encryption.
+    OnlyOwnerCanSetBallotEncryption,
+    /// Returned if the decryption threshold is zero or larger than the committee.
+    InvalidThreshold,
+    /// Returned if a plaintext vote is cast on a poll with encrypted ballots.
+    PollRequiresEncryptedVote,
+    /// Returned if an encrypted ballot, decryption share or tally is submitted for a poll
+    /// without encrypted ballots.
+    PollDoesNotUseEncryptedBallots,
+    /// Returned if the caller is not a member of the decryption committee of the poll.
+    CallerIsNotInDecryptionCommittee,
+    /// Returned if the caller has already submitted a decryption share for the poll.
+    DecryptionShareAlreadySubmitted,
+    /// Returned if a tally is posted before enough decryption shares are submitted.
+    NotEnoughDecryptionShares,
+    /// Returned if the caller is neither the owner nor a committee member when posting a tally.
+    CallerCannotPostTally,
+    /// Returned if the tally of the poll has already been posted.
+    TallyAlreadyPosted,
+    /// Returned if a tally contains unknown choices or more votes than the encrypted ballots.
+    InvalidTally,
+    /// Returned if the caller is not the owner of the poll when changing its result visibility.
+    OnlyOwnerCanHideResults,
+    /// Returned if the caller is not the owner of the poll when changing its visibility.
+    OnlyOwnerCanSetVisibility,
+    /// Returned if the caller is not allowed to view a private poll.
+    CallerCannotViewPoll,
+    /// Returned if the caller is not the owner of the poll when making its ballots public.
+    OnlyOwnerCanSetPublicBallots,
+    /// Returned if the caller accepts terms while voting does not require accepting terms.
+    NoTermsToAccept,
+    /// Returned if the accepted hash is not the hash of the current terms.
+    TermsHashMismatch,
+    /// Returned if the voter has not accepted the current terms.
+    TermsNotAccepted,
+    /// Returned if the caller is not the owner of the poll when setting its challenge period.
+    OnlyOwnerCanSetChallengePeriod,
+    /// Returned if the result of the poll is challenged outside of its challenge period or
+    /// while another challenge is pending.
+    ResultIsNotProvisional,
+    /// Returned if the result of the poll has no pending challenge.
+    ResultIsNotChallenged,
+    /// Returned if winnings are claimed before the result of the poll is final.
+    ResultIsNotFinal,
+    /// Returned if the caller is not a resolver when resolving a challenge.
+    CallerIsNotResolver,
+    /// Returned if the poll template does not exist.
+    TemplateWithIdDoesNotExist,
+    /// Returned if the caller is not the owner of the template when creating a poll from it.
+    OnlyOwnerCanInstantiateTemplate,
+    /// Returned if the caller is not the owner of the poll when changing its registration
+    /// requirement.
+    OnlyOwnerCanSetRegistration,
+    /// Returned if the caller registers for a poll that does not require registration.
+    PollDoesNotRequireRegistration,
+    /// Returned if the caller registers for a poll that has opened.
+    RegistrationIsClosed,
+    /// Returned if the caller has already registered for the poll.
+    CallerAlreadyRegistered,
+    /// Returned if the voter has not registered for a poll requiring registration.
+    VoterIsNotRegistered,
+    /// Returned if the caller is not the owner of the poll when changing its quorum or eligible
+    /// weight.
+    OnlyOwnerCanSetQuorum,
+    /// Returned if the quorum is larger than 10 000 basis points.
+    InvalidQuorum,
+    /// Returned if a choice listed for the poll is missing from the storage (see
+    /// `repair_indexes`).
+    StorageInconsistency { choice_id: ChoiceId },
+    /// Returned if a message transferring value is called while another one is executing.
+    ReentrantCall,
+    /// Returned if the caller has reached the maximum number of votes per block.
+    TooManyRequests,
+    /// Returned if the caller is not the owner of the poll when changing its milestones.
+    OnlyOwnerCanSetMilestones,
+    /// Returned if an account approves itself as operator.
+    CannotApproveSelf,
+    /// Returned if the caller is not an operator approved by the principal of a vote.
+    CallerIsNotOperator,
+    /// Returned if the poll has been scheduled to start.
+    PollIsScheduled,
+    /// Returned if the poll is waiting for approval.
+    PollIsPendingApproval,
+    /// Returned if the poll has been cancelled.
+    PollIsCancelled,
+    /// Returned if the caller is not the owner of the poll when funding its keeper bounty.
+    OnlyOwnerCanFundKeeperBounty,
+    /// Returned if the list of eligible voters of the poll has been sealed.
+    VoterListIsSealed,
+    /// Returned if a poll with an uploaded list of eligible voters is started before the list
+    /// is sealed.
+    VoterListIsNotSealed,
+    /// Returned if a member votes while no member registry is set.
+    NoMemberRegistry,
+    /// Returned if the caller is not the member with the given index in the member registry.
+    CallerIsNotMember,
+    /// Returned if migration mode is entered after it has already been entered once.
+    MigrationModeUnavailable,
+    /// Returned if polls are imported (or migration mode is exited) outside of migration mode.
+    MigrationModeIsNotActive,
+    /// Returned if a poll or choice description is longer than `MAX_DESCRIPTION_LENGTH` bytes.
+    DescriptionTooLong,
+    /// Returned if the admin is changed to the account of the contract, the zero account or an
+    /// account of the denylist of admins.
+    InvalidAdmin,
+    /// Returned if the caller is not the owner of the poll when setting its finalizer.
+    OnlyOwnerCanSetFinalizer,
+    /// Returned if a poll is started while the maximum number of active polls have started.
+    TooManyActivePolls,
+    /// Returned if the caller is not the owner of the poll when deleting it.
+    OnlyOwnerCanDeletePoll,
+    /// Returned if a poll with choices, registered voters or a fee pool is deleted.
+    PollIsInUse,
+    /// Returned if the caller is not the owner of the poll when setting its choice capacity.
+    OnlyOwnerCanSetChoiceCapacity,
+    /// Returned if a vote is cast for a choice that has received as many votes as it can.
+    ChoiceCapacityReached,
+    /// Returned if the caller is already waiting for a seat on a choice of the poll.
+    CallerAlreadyWaitlisted,
+    /// Returned if the waitlist of a choice holds `MAX_WAITLIST_LENGTH` voters.
+    WaitlistIsFull,
+    /// Returned if the caller has not voted on the poll.
+    CallerHasNotVotedOnPoll,
+    /// Returned if a vote is retracted from a poll without a choice capacity, with a ranked
+    /// tally strategy, or with a stake attached to it.
+    VoteCannotBeRetracted,
+    /// Returned if the caller is not the owner of the poll when adding a question.
+    OnlyOwnerCanAddQuestion,
+    /// Returned if a question with the same id already exists in the poll.
+    QuestionWithIdAlreadyExists,
+    /// Returned if a single choice is voted for on a poll with questions (see `vote_ballot`).
+    PollRequiresBallot,
+    /// Returned if a ballot is cast on a poll without questions or with a ranked tally strategy.
+    PollDoesNotAcceptBallots,
+    /// Returned if a ballot answers no question, answers a question more than once or answers
+    /// it with a choice of another question (or a value outside the range of a numeric one).
+    InvalidAnswers,
+    /// Returned if the minimum of the range of a numeric question is above its maximum.
+    InvalidNumericRange,
+    /// Returned if the caller is not the owner of the poll when setting its topic seed.
+    OnlyOwnerCanSetTopicSeed,
+    /// Returned if the version of a code is lower than the version of the code which last
+    /// migrated the storage.
+    CodeVersionTooLow,
+    /// Returned if the version of the running code is not the version announced by `set_code`.
+    CodeVersionMismatch,
+    /// Returned if the caller is not the owner of the poll when setting whether the owner may
+    /// vote on it.
+    OnlyOwnerCanSetOwnerMayVote,
+    /// Returned if the owner of a poll votes on it while the poll does not allow it.
+    OwnerCannotVote,
+    /// Returned if the caller is neither the owner of the poll nor a moderator when recusing an
+    /// account from voting on it.
+    OnlyOwnerOrModeratorCanRecuse,
+    /// Returned if an account recused from voting on a poll votes on it.
+    AccountIsRecused,
+    /// Returned if the name or the URI of the instance is longer than
+    /// `MAX_INSTANCE_METADATA_LENGTH`.
+    InstanceMetadataTooLong,
+    /// Returned if the caller is not the owner of the poll when archiving it.
+    OnlyOwnerCanArchivePoll,
+    /// Returned if an archived poll is archived again.
+    PollIsArchived,
+}
@@ -0,0 +1,530 @@
+//! Events emitted by the voting contract.
+//!
+//! The events are standalone ink! 5 events whose signature topics are pinned to the ones
+//! derived by ink! 4 (the byte `0x00` followed by `VotingContract::<event>`, hashed with
+//! Blake2x256 if it does not fit in a topic), so that indexers identify the events emitted
+//! before and after the upgrade to ink! 5 alike. None of the events is anonymous, as an
+//! anonymous event has no signature topic to be identified by.
+
+use super::*;
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a506f6c6c4372656174656400000000"
+)]
+/// Event emitted when a poll is created.
+pub struct PollCreated {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    /// Description of the poll.
+    pub(crate) description: String,
+    #[ink(topic)]
+    /// Account that created the poll.
+    pub(crate) owner: AccountId,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a43686f696365416464656400000000"
+)]
+/// Event emitted when a choice is added to a poll.
+pub struct ChoiceAdded {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    #[ink(topic)]
+    /// Id of the choice.
+    pub(crate) choice_id: ChoiceId,
+    /// Description of the choice.
+    pub(crate) description: String,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a43686f696365557064617465640000"
+)]
+/// Event emitted when the description of a choice is updated.
+pub struct ChoiceUpdated {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    /// Id of the choice.
+    pub(crate) choice_id: ChoiceId,
+    /// New description of the choice.
+    pub(crate) description: String,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a43686f69636552656d6f7665640000"
+)]
+/// Event emitted when a choice is removed from a poll.
+pub struct ChoiceRemoved {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    /// Id of the choice.
+    pub(crate) choice_id: ChoiceId,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a506f6c6c5374617274656400000000"
+)]
+/// Event emitted when a poll is started.
+pub struct PollStarted {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a566f74654361737400000000000000"
+)]
+/// Event emitted when a ballot is accepted, with its number (a receipt of the vote).
+pub struct VoteCast {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    #[ink(topic)]
+    /// Account whose ballot was accepted.
+    pub(crate) voter: AccountId,
+    /// Sequential number of the ballot within the poll (starting at 1).
+    pub(crate) ballot_number: u64,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a506f6c6c456e646564000000000000"
+)]
+/// Event emitted when a poll is ended.
+pub struct PollEnded {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    /// Id of the winning choice (not present in case of a tie).
+    pub(crate) winner: Option<ChoiceId>,
+    /// Number of accounts that voted on the poll.
+    pub(crate) total_ballots: u64,
+    #[ink(topic)]
+    /// Id of the winning choice, or `NO_WINNER_TOPIC` in case of a tie, so that the polls won
+    /// by a choice can be filtered.
+    pub(crate) winner_topic: u64,
+    /// Number of votes (total weight) for the winning choice (zero in case of a tie).
+    pub(crate) winner_vote_count: u128,
+    /// Number of accounts that voted for the winning choice (zero in case of a tie).
+    pub(crate) winner_voter_count: u64,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a54616c6c79506f7374656400000000"
+)]
+/// Event emitted when the decrypted tally of an encrypted poll is posted.
+pub struct TallyPosted {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    /// Id of the winning choice (not present in case of a tie).
+    pub(crate) winner: Option<ChoiceId>,
+    /// Hash committing to the decryption of the ballots, for verification off-chain.
+    pub(crate) verification_hash: [u8; 32],
+}
+
+#[ink::event(
+    signature_topic = "0x7fd6bf670c6e07aace9a405ab2d2a24739af61acd86b256fddd83f4c4ea5dddc"
+)]
+/// Event emitted when the observer of a poll could not be notified that the poll ended.
+pub struct ObserverNotificationFailed {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    /// Account of the observer.
+    pub(crate) observer: AccountId,
+}
+
+#[ink::event(
+    signature_topic = "0x4066b67635126b5fce615f308c23cc723c506fecbc96b26caf74dc5d3136794e"
+)]
+/// Event emitted when the result of a poll could not be pushed to the results publisher.
+pub struct ResultPublicationFailed {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    /// Account of the results publisher.
+    pub(crate) publisher: AccountId,
+}
+
+#[ink::event(
+    signature_topic = "0xfc143ccc24c13b16f80c32b2aca20f84aa8ee15df80af88bd02c8404afd0a3c9"
+)]
+/// Event emitted when an update of a poll could not be pushed to the stats contract.
+pub struct StatsUpdateFailed {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    /// Account of the stats contract.
+    pub(crate) stats: AccountId,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a506f6c6c496d706f72746564000000"
+)]
+/// Event emitted when a historical poll is imported from another instance of the contract.
+pub struct PollImported {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Account that created the poll.
+    pub(crate) owner: AccountId,
+    /// Hash of the imported data (the SCALE-encoded poll, choices and counts), so that it
+    /// can be matched with the state exported by the source instance.
+    pub(crate) data_hash: [u8; 32],
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a557067726164654578656375746564"
+)]
+/// Event emitted when the code of the contract is changed using `set_code`. The code hash
+/// is a topic, so that explorers and watchdog services can subscribe to the upgrades of the
+/// contract.
+pub struct UpgradeExecuted {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Hash of the new code of the contract.
+    pub(crate) code_hash: [u8; 32],
+    /// Admin that changed the code.
+    pub(crate) admin: AccountId,
+}
+
+#[ink::event(
+    signature_topic = "0x5ecab2402a14ae2ae510eb88147b5b0d6d9b2d1fb46d0ebb1641d4ecbdba0c48"
+)]
+/// Event emitted when the owner of a poll appoints or removes its finalizer.
+pub struct FinalizerChanged {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    /// Account allowed to end the poll besides its owner (not present if removed).
+    pub(crate) finalizer: Option<AccountId>,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a506f6c6c44656c6574656400000000"
+)]
+/// Event emitted when a poll is deleted by its owner.
+pub struct PollDeleted {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    #[ink(topic)]
+    /// Account that created the poll.
+    pub(crate) owner: AccountId,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a566f74655265747261637465640000"
+)]
+/// Event emitted when a voter retracts their vote.
+pub struct VoteRetracted {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    #[ink(topic)]
+    /// Account whose vote was retracted.
+    pub(crate) voter: AccountId,
+    /// Id of the choice the vote was cast for.
+    pub(crate) choice_id: ChoiceId,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a566f746572576169746c6973746564"
+)]
+/// Event emitted when a voter joins the waitlist of a full choice.
+pub struct VoterWaitlisted {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    #[ink(topic)]
+    /// Account waiting for a seat.
+    pub(crate) voter: AccountId,
+    /// Id of the choice.
+    pub(crate) choice_id: ChoiceId,
+    /// Position of the voter in the waitlist (starting at 1).
+    pub(crate) position: u32,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a566f74657250726f6d6f7465640000"
+)]
+/// Event emitted when a seat is freed on a choice and the vote of a waitlisted voter is
+/// recorded.
+pub struct VoterPromoted {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    #[ink(topic)]
+    /// Account whose vote was recorded.
+    pub(crate) voter: AccountId,
+    /// Id of the choice.
+    pub(crate) choice_id: ChoiceId,
+    /// Sequential number of the ballot within the poll (starting at 1).
+    pub(crate) ballot_number: u64,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a506f6c6c4c6966656379636c650000"
+)]
+/// Event emitted alongside each event of the lifecycle of a poll. Its topic only depends on
+/// the poll (see `get_poll_topic`), so that light clients can follow the whole lifecycle of a
+/// poll with a single topic filter.
+pub struct PollLifecycle {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Blake2x256 hash of the SCALE encoding of `(POLL_TOPIC_DOMAIN, poll_id)`.
+    pub(crate) poll_topic: [u8; 32],
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    /// Stage of its lifecycle reached by the poll.
+    pub(crate) stage: PollStage,
+}
+
+#[ink::event(
+    signature_topic = "0x00566f74696e67436f6e74726163743a3a5465726d7341636365707465640000"
+)]
+/// Event emitted when an account accepts the terms of the contract.
+pub struct TermsAccepted {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Account that accepted the terms.
+    pub(crate) account: AccountId,
+    /// Hash of the accepted terms.
+    pub(crate) terms_hash: [u8; 32],
+    /// Version of the accepted terms.
+    pub(crate) version: u32,
+}
+
+#[ink::event(
+    signature_topic = "0x4dff730057f89fb21f7d705a0629ae564f009791c1518915dbe26efd0d3d91e4"
+)]
+/// Event emitted when the provisional result of a poll is challenged.
+pub struct ResultChallenged {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    #[ink(topic)]
+    /// Account that challenged the result.
+    pub(crate) challenger: AccountId,
+    /// Deposit of the challenge.
+    pub(crate) deposit: Balance,
+}
+
+#[ink::event(
+    signature_topic = "0xbd490d6503fb6af3d2de688aac46a84c2e1689c26c1f30d4fb1201402efeeb07"
+)]
+/// Event emitted when the challenge of the result of a poll is resolved.
+pub struct ChallengeResolved {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    #[ink(topic)]
+    /// Resolver that resolved the challenge.
+    pub(crate) resolver: AccountId,
+    /// Outcome of the challenge.
+    pub(crate) outcome: ChallengeOutcome,
+    /// Deposit of the challenge returned to the challenger (`0` if it was slashed).
+    pub(crate) refunded: Balance,
+}
+
+#[ink::event(
+    signature_topic = "0x7982d946fec9044d109fbb5b8fb3f8b6e53b9b9d372baf2e115cab8e3f6a0f32"
+)]
+/// Event emitted when a poll ends without reaching its quorum (the poll has no winner).
+pub struct QuorumNotReached {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    /// Weight of the votes cast on the poll.
+    pub(crate) cast_weight: u128,
+    /// Weight of the votes required to reach the quorum.
+    pub(crate) required_weight: u128,
+}
+
+#[ink::event(
+    signature_topic = "0x38450cf59255166ed7d5109791ceb741a7940b05c50da6b4233967bd1c879b99"
+)]
+/// Event emitted when a choice of a poll reaches one of the milestones configured for the
+/// poll (see `set_milestones`).
+pub struct MilestoneReached {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    #[ink(topic)]
+    /// Id of the choice.
+    pub(crate) choice_id: ChoiceId,
+    /// Milestone reached by the choice.
+    pub(crate) kind: MilestoneKind,
+}
+
+#[ink::event(
+    signature_topic = "0x31636e04728e1816c44443f908c0aeec978e2464f0bf85a29d523c3770060e02"
+)]
+/// Event emitted when a poll template is created.
+pub struct PollTemplateCreated {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the template.
+    pub(crate) template_id: TemplateId,
+    #[ink(topic)]
+    /// Account that created the template.
+    pub(crate) owner: AccountId,
+}
+
+#[ink::event(
+    signature_topic = "0xbcab6b104b71acee1d43abc61bfc1f7312aca8e381083dafd320e897ea517b7c"
+)]
+/// Event emitted when a poll is created from a template.
+pub struct PollInstantiated {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the template.
+    pub(crate) template_id: TemplateId,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+}
+
+#[ink::event(
+    signature_topic = "0x67fbc59f4bc6e040840cfea62c0fdd14d4670dceeb9c36be4a578514d7dde145"
+)]
+/// Event emitted when the keeper bounty of a poll is claimed by the account finalizing it.
+pub struct KeeperBountyClaimed {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    #[ink(topic)]
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    #[ink(topic)]
+    /// Namespace of the poll derived from its topic seed (see `set_topic_seed`).
+    pub(crate) namespace: Option<[u8; 32]>,
+    #[ink(topic)]
+    /// Account that finalized the poll.
+    pub(crate) keeper: AccountId,
+    /// Amount of the bounty.
+    pub(crate) amount: Balance,
+}
+
+#[ink::event(
+    signature_topic = "0xbbe5b14f7ba999dc83bbadccd988fdd54a54374709fca957d7d450981a980512"
+)]
+/// Event emitted when the protocol fee configuration is changed.
+pub struct FeeConfigUpdated {
+    /// Version of the event schema (see `EVENT_SCHEMA_VERSION`).
+    pub(crate) schema_version: u8,
+    /// Protocol fee in basis points.
+    pub(crate) bps: u16,
+    /// Account receiving the protocol fee.
+    pub(crate) recipient: AccountId,
+}
@@ -0,0 +1,157 @@
+//! Calls to the external contracts configured for the voting contract (observers, results
+//! publisher, identity registry, member registry, tenure registry and stats contract).
+
+use super::*;
+
+impl VotingContract {
+    /// Resolves an account to its display name using the identity registry (if any).
+    pub(crate) fn display_name(&self, account: AccountId) -> Option<String> {
+        let registry = self.identity_registry?;
+
+        build_call::<Environment>()
+            .call(registry)
+            .ref_time_limit(IDENTITY_GAS_LIMIT)
+            .exec_input(
+                ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                    "IdentityRegistry::display_name"
+                )))
+                .push_arg(account),
+            )
+            .returns::<Option<String>>()
+            .try_invoke()
+            .ok()?
+            .ok()?
+    }
+
+    /// Resolves an account to its member index using the member registry (if any).
+    pub(crate) fn member_index(&self, account: AccountId) -> Option<u32> {
+        let registry = self.member_registry?;
+
+        build_call::<Environment>()
+            .call(registry)
+            .ref_time_limit(MEMBER_REGISTRY_GAS_LIMIT)
+            .exec_input(
+                ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                    "MemberRegistry::member_index"
+                )))
+                .push_arg(account),
+            )
+            .returns::<Option<u32>>()
+            .try_invoke()
+            .ok()?
+            .ok()?
+    }
+
+    /// Get the date (timestamp in milliseconds) at which an account joined according to a tenure
+    /// registry (if it is a member).
+    pub(crate) fn member_since(
+        &self,
+        registry: AccountId,
+        account: AccountId,
+    ) -> Option<Timestamp> {
+        build_call::<Environment>()
+            .call(registry)
+            .ref_time_limit(TENURE_REGISTRY_GAS_LIMIT)
+            .exec_input(
+                ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                    "TenureRegistry::member_since"
+                )))
+                .push_arg(account),
+            )
+            .returns::<Option<Timestamp>>()
+            .try_invoke()
+            .ok()?
+            .ok()?
+    }
+
+    /// Pushes the result of an ended poll to the results publisher (if any). Like observer
+    /// notifications, publishing is best-effort and a failure is reported with an event.
+    pub(crate) fn publish_result(&mut self, poll_id: PollId, winner: Option<ChoiceId>) {
+        let Some(publisher) = self.results_publisher else {
+            return;
+        };
+
+        let result = build_call::<Environment>()
+            .call(publisher)
+            .ref_time_limit(PUBLISHER_GAS_LIMIT)
+            .exec_input(
+                ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                    "ResultsPublisher::publish_result"
+                )))
+                .push_arg(poll_id)
+                .push_arg(winner)
+                .push_arg(self.total_votes(poll_id)),
+            )
+            .returns::<()>()
+            .try_invoke();
+
+        if !matches!(result, Ok(Ok(()))) {
+            self.env().emit_event(ResultPublicationFailed {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                publisher,
+            });
+        }
+    }
+
+    /// Pushes an incremental update of a poll to the stats contract (if any). Like observer
+    /// notifications, pushes are best-effort and a failure is reported with an event.
+    pub(crate) fn push_stats<Args: scale::Encode>(
+        &mut self,
+        poll_id: PollId,
+        input: ExecutionInput<Args>,
+    ) {
+        let Some(stats) = self.stats_contract else {
+            return;
+        };
+
+        let result = build_call::<Environment>()
+            .call(stats)
+            .ref_time_limit(STATS_GAS_LIMIT)
+            .exec_input(input)
+            .returns::<()>()
+            .try_invoke();
+
+        if !matches!(result, Ok(Ok(()))) {
+            self.env().emit_event(StatsUpdateFailed {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                stats,
+            });
+        }
+    }
+
+    /// Notifies the observer of a poll (if any) that the poll has ended. The notification is
+    /// best-effort: a failure is recorded and reported with an event instead of reverting.
+    pub(crate) fn notify_observer(&mut self, poll_id: PollId, winner: Option<ChoiceId>) {
+        let Some(observer) = self.poll_observers.get(poll_id) else {
+            return;
+        };
+
+        let result = build_call::<Environment>()
+            .call(observer)
+            .ref_time_limit(OBSERVER_GAS_LIMIT)
+            .exec_input(
+                ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                    "PollObserver::on_poll_ended"
+                )))
+                .push_arg(poll_id)
+                .push_arg(winner),
+            )
+            .returns::<()>()
+            .try_invoke();
+
+        if !matches!(result, Ok(Ok(()))) {
+            self.failed_notifications.insert(poll_id, &());
+
+            self.env().emit_event(ObserverNotificationFailed {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                observer,
+            });
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Guards of the messages of the voting contract: the reentrancy guard of the messages
+//! transferring value and the rate limit of the messages submitting votes.
+
+use super::*;
+
+/// Guard of the `entered: bool` flag stored under `ENTERED_KEY`, held for the duration of a
+/// message transferring value (or calling other contracts) so that a callee cannot re-enter
+/// such a message. The flag is written to its own storage cell as soon as the guard is acquired
+/// (the fields of the contract are only written back when the message returns, so a reentrant
+/// call would not see them) and cleared when the guard is dropped.
+pub(crate) struct ReentrancyGuard;
+
+impl ReentrancyGuard {
+    /// Acquires the guard, returning an error if it is already held (i.e. on a reentrant call).
+    pub(crate) fn enter() -> Result<Self, Error> {
+        let entered = ink::env::get_contract_storage::<_, bool>(&ENTERED_KEY)
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+
+        if entered {
+            return Err(Error::ReentrantCall);
+        }
+
+        ink::env::set_contract_storage(&ENTERED_KEY, &true);
+
+        Ok(Self)
+    }
+}
+
+impl Drop for ReentrancyGuard {
+    fn drop(&mut self) {
+        ink::env::clear_contract_storage(&ENTERED_KEY);
+    }
+}
+
+impl VotingContract {
+    /// Counts a rate-limited call of the caller in the current block, returning an error if the
+    /// caller has already made as many such calls in the block as allowed by the rate limit.
+    pub(crate) fn consume_rate_limit(&mut self) -> Result<(), Error> {
+        let Some(rate_limit) = self.rate_limit else {
+            return Ok(());
+        };
+
+        let caller = self.env().caller();
+        let block = self.env().block_number();
+
+        // Reset the counter of the caller on the first call of a block.
+        let calls = match self.call_counters.get(caller) {
+            Some((last_block, calls)) if last_block == block => calls,
+            _ => 0,
+        };
+
+        if calls >= rate_limit {
+            return Err(Error::TooManyRequests);
+        }
+
+        self.call_counters.insert(caller, &(block, calls + 1));
+
+        Ok(())
+    }
+}
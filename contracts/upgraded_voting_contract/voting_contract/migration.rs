@@ -0,0 +1,272 @@
+//! Storage views and migration helpers for polls written by older versions of the voting
+//! contract.
+
+use super::*;
+
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
+/// A poll as stored by polls with storage version 0.
+pub(crate) struct PollV0 {
+    /// Description of the poll
+    pub(crate) description: Description,
+    /// Status of the poll.
+    pub(crate) status: PollStatus,
+    /// Account that created the poll.
+    pub(crate) owner: AccountId,
+    /// Winner of the poll (present if the poll has ended).
+    pub(crate) winner: Option<ChoiceIdV0>,
+    /// Timestamp (in milliseconds) from which the poll accepts votes.
+    pub(crate) start_at: Timestamp,
+    /// Timestamp (in milliseconds) after which the poll no longer accepts votes.
+    pub(crate) end_at: Timestamp,
+}
+
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
+/// A poll as stored by polls with storage versions 1 to 3.
+pub(crate) struct PollV3 {
+    /// Description of the poll
+    pub(crate) description: Description,
+    /// Status of the poll.
+    pub(crate) status: PollStatus,
+    /// Account that created the poll.
+    pub(crate) owner: AccountId,
+    /// Winner of the poll (present if the poll has ended).
+    pub(crate) winner: Option<ChoiceId>,
+    /// Timestamp (in milliseconds) from which the poll accepts votes.
+    pub(crate) start_at: Timestamp,
+    /// Timestamp (in milliseconds) after which the poll no longer accepts votes.
+    pub(crate) end_at: Timestamp,
+}
+
+/// Views of the storage of polls with storage version 0. These share the storage keys of the
+/// corresponding fields of `VotingContract` but decode values using the types of that version.
+pub(crate) struct StorageV0 {
+    /// Version 0 view of `VotingContract::polls`.
+    pub(crate) polls:
+        Mapping<PollId, PollV0, ManualKey<{ KeyComposer::from_str("VotingContract::polls") }>>,
+    /// Version 0 view of `VotingContract::choices`.
+    pub(crate) choices: Mapping<
+        (PollId, ChoiceIdV0),
+        Choice,
+        ManualKey<{ KeyComposer::from_str("VotingContract::choices") }>,
+    >,
+    /// Version 0 view of `VotingContract::choice_ids`.
+    pub(crate) choice_ids: Mapping<
+        PollId,
+        Vec<ChoiceIdV0>,
+        ManualKey<{ KeyComposer::from_str("VotingContract::choice_ids") }>,
+    >,
+    /// Version 0 view of `VotingContract::next_choice_ids`.
+    pub(crate) next_choice_ids: Mapping<
+        PollId,
+        ChoiceIdV0,
+        ManualKey<{ KeyComposer::from_str("VotingContract::next_choice_ids") }>,
+    >,
+    /// Version 0 view of `VotingContract::vote_counts`.
+    pub(crate) vote_counts: Mapping<
+        (PollId, ChoiceIdV0),
+        u64,
+        ManualKey<{ KeyComposer::from_str("VotingContract::vote_counts") }>,
+    >,
+}
+
+impl StorageV0 {
+    /// Creates the views of the storage with version 0.
+    pub(crate) fn new() -> Self {
+        Self {
+            polls: Mapping::new(),
+            choices: Mapping::new(),
+            choice_ids: Mapping::new(),
+            next_choice_ids: Mapping::new(),
+            vote_counts: Mapping::new(),
+        }
+    }
+}
+
+/// Views of the storage of polls with storage version 1. These share the storage keys of the
+/// corresponding fields of `VotingContract` but decode values using the types of that version.
+pub(crate) struct StorageV1 {
+    /// Version 1 view of `VotingContract::vote_counts`.
+    pub(crate) vote_counts: Mapping<
+        (PollId, ChoiceId),
+        u64,
+        ManualKey<{ KeyComposer::from_str("VotingContract::vote_counts") }>,
+    >,
+}
+
+impl StorageV1 {
+    /// Creates the views of the storage with version 1.
+    pub(crate) fn new() -> Self {
+        Self {
+            vote_counts: Mapping::new(),
+        }
+    }
+}
+
+/// Views of the storage of polls with storage version 3. These share the storage keys of the
+/// corresponding fields of `VotingContract` but decode values using the types of that version.
+pub(crate) struct StorageV3 {
+    /// Version 3 view of `VotingContract::polls`.
+    pub(crate) polls:
+        Mapping<PollId, PollV3, ManualKey<{ KeyComposer::from_str("VotingContract::polls") }>>,
+}
+
+impl StorageV3 {
+    /// Creates the views of the storage with version 3.
+    pub(crate) fn new() -> Self {
+        Self {
+            polls: Mapping::new(),
+        }
+    }
+}
+
+/// Views of the storage of polls with storage version 4. These share the storage keys of the
+/// corresponding fields of `VotingContract` but decode values using the types of that version.
+pub(crate) struct StorageV4 {
+    /// Version 4 view of the list of choice ids of each poll (replaced by
+    /// `VotingContract::choice_slots`).
+    pub(crate) choice_ids: Mapping<
+        PollId,
+        Vec<ChoiceId>,
+        ManualKey<{ KeyComposer::from_str("VotingContract::choice_ids") }>,
+    >,
+}
+
+impl StorageV4 {
+    /// Creates the views of the storage with version 4.
+    pub(crate) fn new() -> Self {
+        Self {
+            choice_ids: Mapping::new(),
+        }
+    }
+}
+
+impl VotingContract {
+    /// Records the version of the running code as the version of the code which last migrated
+    /// the storage, after verifying that it is the version announced by `set_code` and that it
+    /// is not older than the recorded one. Returns true if the recorded version changed.
+    pub(crate) fn record_code_version(&mut self) -> Result<bool, Error> {
+        if self
+            .pending_code_version
+            .is_some_and(|code_version| code_version != CODE_VERSION)
+        {
+            return Err(Error::CodeVersionMismatch);
+        }
+
+        if CODE_VERSION < self.code_version {
+            return Err(Error::CodeVersionTooLow);
+        }
+
+        let changed = self.code_version != CODE_VERSION;
+
+        self.code_version = CODE_VERSION;
+        self.pending_code_version = None;
+
+        Ok(changed)
+    }
+
+    /// Rewrites the storage of a poll with storage version 0 using `u32` choice ids.
+    pub(crate) fn migrate_choice_ids(&mut self, poll_id: PollId) -> Result<(), Error> {
+        let v0 = StorageV0::new();
+        let mut v1 = StorageV1::new();
+        let mut v3 = StorageV3::new();
+        let mut v4 = StorageV4::new();
+
+        // Get the poll and return error if it does not exist.
+        let poll_v0 = v0.polls.get(poll_id).ok_or(Error::PollWithIdDoesNotExist)?;
+
+        // Rewrite the choices and vote counts of the poll.
+        let choice_ids_v0 = v0.choice_ids.get(poll_id).unwrap_or_default();
+
+        for &choice_id_v0 in choice_ids_v0.iter() {
+            let choice_id = ChoiceId::from(choice_id_v0);
+
+            if let Some(choice) = v0.choices.take((poll_id, choice_id_v0)) {
+                self.choices.insert((poll_id, choice_id), &choice);
+            }
+
+            if let Some(vote_count) = v0.vote_counts.take((poll_id, choice_id_v0)) {
+                v1.vote_counts.insert((poll_id, choice_id), &vote_count);
+            }
+        }
+
+        let choice_id_list: Vec<ChoiceId> = choice_ids_v0.into_iter().map(ChoiceId::from).collect();
+
+        if !choice_id_list.is_empty() {
+            v4.choice_ids.insert(poll_id, &choice_id_list);
+        }
+
+        if let Some(next_choice_id) = v0.next_choice_ids.get(poll_id) {
+            self.next_choice_ids
+                .insert(poll_id, &ChoiceId::from(next_choice_id));
+        }
+
+        // Rewrite the poll.
+        let poll = PollV3 {
+            description: poll_v0.description,
+            status: poll_v0.status,
+            owner: poll_v0.owner,
+            winner: poll_v0.winner.map(ChoiceId::from),
+            start_at: poll_v0.start_at,
+            end_at: poll_v0.end_at,
+        };
+
+        v3.polls.insert(poll_id, &poll);
+
+        Ok(())
+    }
+
+    /// Moves the description of a poll with storage version 3 out of the poll.
+    pub(crate) fn migrate_poll_description(&mut self, poll_id: PollId) -> Result<(), Error> {
+        let v3 = StorageV3::new();
+
+        // Get the poll and return error if it does not exist.
+        let poll_v3 = v3.polls.get(poll_id).ok_or(Error::PollWithIdDoesNotExist)?;
+
+        // Rewrite the poll without its description.
+        let poll = Poll {
+            status: poll_v3.status,
+            owner: poll_v3.owner,
+            winner: poll_v3.winner,
+            start_at: poll_v3.start_at,
+            end_at: poll_v3.end_at,
+        };
+
+        self.polls.insert(poll_id, &poll);
+        self.poll_descriptions.insert(poll_id, &poll_v3.description);
+
+        Ok(())
+    }
+
+    /// Builds the choice bitmap of a poll with storage version 2 from its list of choices.
+    pub(crate) fn migrate_choice_bitmap(&mut self, poll_id: PollId) {
+        let v4 = StorageV4::new();
+
+        for choice_id in v4.choice_ids.get(poll_id).unwrap_or_default() {
+            if self.choices.contains((poll_id, choice_id)) {
+                self.set_choice_bit(poll_id, choice_id, true);
+            }
+        }
+    }
+
+    /// Rewrites the vote counts of a poll with storage version 1 as `u128`.
+    pub(crate) fn migrate_vote_counts(&mut self, poll_id: PollId) {
+        let v1 = StorageV1::new();
+        let v4 = StorageV4::new();
+
+        for choice_id in v4.choice_ids.get(poll_id).unwrap_or_default() {
+            if let Some(vote_count) = v1.vote_counts.get((poll_id, choice_id)) {
+                self.vote_counts
+                    .insert((poll_id, choice_id), &u128::from(vote_count));
+            }
+        }
+    }
+
+    /// Splits the list of choices of a poll with storage version 4 into slots.
+    pub(crate) fn migrate_choice_slots(&mut self, poll_id: PollId) {
+        let v4 = StorageV4::new();
+
+        if let Some(choice_id_list) = v4.choice_ids.take(poll_id) {
+            self.set_choice_id_list(poll_id, &choice_id_list);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Winner computation of the tally strategies of the voting contract, loading the votes of a
+//! poll from storage and delegating the count to the `tally` module.
+
+use super::*;
+
+impl VotingContract {
+    /// Returns the count (votes or points) of each choice of a poll.
+    pub(crate) fn choice_counts(
+        &self,
+        poll_id: PollId,
+        count: impl Fn(ChoiceId) -> Option<u128>,
+    ) -> Vec<(ChoiceId, u128)> {
+        self.choice_id_list(poll_id)
+            .into_iter()
+            .map(|choice_id| (choice_id, count(choice_id).unwrap_or_default()))
+            .collect()
+    }
+
+    /// Returns the choice with the most Borda points (not present in case of a tie).
+    pub(crate) fn borda_winner(&self, poll_id: PollId) -> Option<ChoiceId> {
+        crate::tally::plurality(&self.choice_counts(poll_id, |choice_id| {
+            self.borda_points.get((poll_id, choice_id))
+        }))
+    }
+
+    /// Fills the seats of a poll by single transferable vote, stores the elected choices and
+    /// the rounds of the count and returns the winner (present if a single choice is elected).
+    pub(crate) fn stv_winner(&mut self, poll_id: PollId, seats: u32) -> Option<ChoiceId> {
+        let (elected, rounds) = crate::tally::single_transferable_vote(
+            &self.choice_id_list(poll_id),
+            &self.stv_ballots.get(poll_id).unwrap_or_default(),
+            seats,
+        );
+
+        self.winners.insert(poll_id, &elected);
+        self.count_rounds.insert(poll_id, &rounds);
+
+        match elected[..] {
+            [winner] => Some(winner),
+            _ => None,
+        }
+    }
+
+    /// Computes the Smith set of a poll from its pairwise preferences, stores it and returns
+    /// the Condorcet winner (present if the Smith set contains a single choice).
+    pub(crate) fn condorcet_winner(&mut self, poll_id: PollId) -> Option<ChoiceId> {
+        let smith_set = crate::tally::smith_set(&self.choice_id_list(poll_id), |a, b| {
+            self.get_pairwise_preference(poll_id, a, b)
+        });
+
+        self.smith_sets.insert(poll_id, &smith_set);
+
+        match smith_set[..] {
+            [winner] => Some(winner),
+            _ => None,
+        }
+    }
+
+    /// Returns the choice with the most votes (not present in case of a tie).
+    pub(crate) fn plurality_winner(&self, poll_id: PollId) -> Option<ChoiceId> {
+        crate::tally::plurality(&self.choice_counts(poll_id, |choice_id| {
+            self.vote_counts.get((poll_id, choice_id))
+        }))
+    }
+}
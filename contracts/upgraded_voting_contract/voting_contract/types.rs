@@ -0,0 +1,1077 @@
+//! Types stored and returned by the voting contract.
+
+use super::*;
+
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// A poll that can be voted on. Only holds the fields read when voting and tallying, so that
+/// reading a poll is cheap whatever the length of its description (which is stored separately,
+/// see `poll_descriptions`).
+pub struct Poll {
+    /// Status of the poll.
+    pub(crate) status: PollStatus,
+    /// Account that created the poll.
+    pub(crate) owner: AccountId,
+    /// Winner of the poll (present if the poll has ended).
+    pub(crate) winner: Option<ChoiceId>,
+    /// Timestamp (in milliseconds) from which the poll accepts votes.
+    pub(crate) start_at: Timestamp,
+    /// Timestamp (in milliseconds) after which the poll no longer accepts votes.
+    pub(crate) end_at: Timestamp,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Switches that pause individual operations of the contract (independent of the global pause).
+pub struct CircuitBreakers {
+    /// Pauses voting on polls.
+    pub(crate) pause_voting: bool,
+    /// Pauses creation of new polls.
+    pub(crate) pause_poll_creation: bool,
+    /// Pauses upgrades of the contract code.
+    pub(crate) pause_upgrades: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Kind of an administrative action.
+pub enum AdminAction {
+    /// The contract was paused.
+    Pause,
+    /// The contract was unpaused.
+    Unpause,
+    /// The circuit breakers were changed.
+    SetCircuitBreakers,
+    /// The code of the contract was changed.
+    SetCode,
+    /// The storage of a poll was migrated.
+    Migrate,
+    /// The admin of the contract was changed.
+    ChangeAdmin,
+    /// The poll duration bounds were changed.
+    SetDurationBounds,
+    /// The finalization bounty was changed.
+    SetFinalizationBounty,
+    /// The deposit required to create a poll was changed.
+    SetPollDeposit,
+    /// A moderator was added.
+    AddModerator,
+    /// A moderator was removed.
+    RemoveModerator,
+    /// The protocol fee configuration was changed.
+    SetFeeConfig,
+    /// The payment token of new polls was changed.
+    SetPaymentToken,
+    /// The results publisher was changed.
+    SetResultsPublisher,
+    /// The membership verifier was changed.
+    SetMembershipVerifier,
+    /// The identity registry was changed.
+    SetIdentityRegistry,
+    /// The storage deposit rates used to estimate poll deposits were changed.
+    SetStorageDepositRates,
+    /// The creator allow-list was enabled or disabled.
+    SetCreatorAllowList,
+    /// An account was added to the creator allow-list.
+    AddCreator,
+    /// An account was removed from the creator allow-list.
+    RemoveCreator,
+    /// A term was added to the description denylist.
+    AddDeniedTerm,
+    /// A term was removed from the description denylist.
+    RemoveDeniedTerm,
+    /// The terms voters must accept were changed.
+    SetTerms,
+    /// The deposit required to challenge the result of a poll was changed.
+    SetChallengeDeposit,
+    /// A resolver was added.
+    AddResolver,
+    /// A resolver was removed.
+    RemoveResolver,
+    /// The choice indexes of a poll were repaired.
+    RepairIndexes,
+    /// The rate limit of votes was changed.
+    SetRateLimit,
+    /// The default configuration of new polls was changed.
+    SetDefaultPollConfig,
+    /// The member registry was changed.
+    SetMemberRegistry,
+    /// The stats contract was changed.
+    SetStatsContract,
+    /// Migration mode was entered.
+    EnterMigrationMode,
+    /// Migration mode was exited.
+    ExitMigrationMode,
+    /// An account was added to the denylist of admins.
+    AddDeniedAdmin,
+    /// An account was removed from the denylist of admins.
+    RemoveDeniedAdmin,
+    /// The tenure schedule was changed.
+    SetTenureSchedule,
+    /// The maximum number of active polls was changed.
+    SetMaxActivePolls,
+    /// The code of the contract was changed to the proxy (see `enable_proxy`).
+    EnableProxy,
+    /// The metadata of the instance was changed.
+    SetInstanceMetadata,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Human-readable name and link of an instance of the contract.
+pub struct InstanceMetadata {
+    /// Name of the instance.
+    pub(crate) name: String,
+    /// Link to a description of the instance.
+    pub(crate) uri: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Discovery feed of the running polls, rendered by clients with a single call.
+pub struct ActiveFeed {
+    /// Ids of the most recently started polls (most recent first).
+    pub(crate) recently_started: Vec<PollId>,
+    /// Ids and ends of the running polls ending the soonest (soonest first).
+    pub(crate) ending_soon: Vec<(PollId, Timestamp)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Compact record of the result of an archived poll, kept forever.
+pub struct ResultRecord {
+    /// Id of the winning choice (not present in case of a tie).
+    pub(crate) winner: Option<ChoiceId>,
+    /// Total number of votes cast on the poll.
+    pub(crate) total_votes: u128,
+    /// Blake2x256 hash of the SCALE-encoded vote counts of the choices (as returned by
+    /// `get_vote_counts` once the poll ended).
+    pub(crate) per_choice_totals_hash: [u8; 32],
+    /// Block in which the poll ended.
+    pub(crate) ended_at: BlockNumber,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// State of the migration mode of the contract, during which the admin can import historical
+/// polls from another instance (see `import_poll`). Migration mode can only be entered once.
+pub enum MigrationMode {
+    /// Migration mode has not been entered yet.
+    #[default]
+    Inactive,
+    /// Polls can be imported.
+    Active,
+    /// Migration mode has been exited and can no longer be entered.
+    Closed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// An entry of the admin log.
+pub struct AdminLogEntry {
+    /// Index of the entry in the admin log.
+    pub(crate) index: u64,
+    /// Kind of the action.
+    pub(crate) action: AdminAction,
+    /// Account that performed the action.
+    pub(crate) actor: AccountId,
+    /// Timestamp (in milliseconds) of the block in which the action was performed.
+    pub(crate) timestamp: Timestamp,
+    /// Blake2x256 hash of the SCALE-encoded parameters of the action.
+    pub(crate) payload_hash: [u8; 32],
+}
+
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// A choice that can be voted on.
+pub struct Choice {
+    /// Description of the choice
+    pub(crate) description: Description,
+}
+
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// A question of a poll with multiple questions, answered with one of its choices.
+pub struct Question {
+    /// Description of the question.
+    pub(crate) description: Description,
+    /// Ids of the choices of the question.
+    pub(crate) choice_ids: Vec<ChoiceId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Range of the values accepted as answers by a numeric question (bounds included).
+pub struct NumericRange {
+    /// Smallest accepted value.
+    pub(crate) min: u64,
+    /// Largest accepted value.
+    pub(crate) max: u64,
+}
+
+impl NumericRange {
+    /// Returns true if the value lies within the range.
+    pub(crate) fn contains(self, value: u64) -> bool {
+        self.min <= value && value <= self.max
+    }
+
+    /// Returns the number of consecutive values counted together in a bucket of the histograms
+    /// of the question, so that they have at most `MAX_NUMERIC_BUCKETS` buckets.
+    pub(crate) fn bucket_width(self) -> u64 {
+        (self.max - self.min) / MAX_NUMERIC_BUCKETS as u64 + 1
+    }
+
+    /// Returns the bucket of a value of the range.
+    pub(crate) fn bucket_of(self, value: u64) -> usize {
+        ((value - self.min) / self.bucket_width()) as usize
+    }
+
+    /// Returns the smallest value of a bucket.
+    pub(crate) fn bucket_start(self, bucket: usize) -> u64 {
+        self.min + bucket as u64 * self.bucket_width()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+/// Answer to a question of a poll with multiple questions.
+pub enum Answer {
+    /// One of the choices of the question.
+    Choice(ChoiceId),
+    /// A value within the range of a numeric question.
+    Value(u64),
+}
+
+#[derive(Debug, Default, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Running tally of the answers to a numeric question.
+pub struct NumericTally {
+    /// Number of answers.
+    pub(crate) count: u64,
+    /// Sum of the answers.
+    pub(crate) sum: u128,
+    /// Number of answers falling into each bucket of the range of the question (trailing empty
+    /// buckets are not stored).
+    pub(crate) histogram: Vec<u128>,
+    /// Total weight of the answers falling into each bucket of the range of the question, only
+    /// counted for polls with the `WeightedMedian` tally strategy (trailing empty buckets are not
+    /// stored).
+    pub(crate) weight_histogram: Vec<u128>,
+}
+
+impl NumericTally {
+    /// Counts an answer lying within the range of the question, along with the weight of the
+    /// voter if the answers are weighted.
+    pub(crate) fn record(&mut self, range: NumericRange, value: u64, weight: Option<u128>) {
+        self.count = self.count.saturating_add(1);
+        self.sum = self.sum.saturating_add(value as u128);
+
+        let bucket = range.bucket_of(value);
+        Self::add_to_bucket(&mut self.histogram, bucket, 1);
+
+        if let Some(weight) = weight {
+            Self::add_to_bucket(&mut self.weight_histogram, bucket, weight);
+        }
+    }
+
+    /// Adds an amount to a bucket of a histogram, growing the histogram if needed.
+    pub(crate) fn add_to_bucket(histogram: &mut Vec<u128>, bucket: usize, amount: u128) {
+        if histogram.len() <= bucket {
+            histogram.resize(bucket + 1, 0);
+        }
+
+        histogram[bucket] = histogram[bucket].saturating_add(amount);
+    }
+
+    /// Returns the aggregate statistics of the answers.
+    pub(crate) fn statistics(&self, range: NumericRange) -> NumericStatistics {
+        NumericStatistics {
+            count: self.count,
+            sum: self.sum,
+            mean: (self.count > 0).then(|| (self.sum / self.count as u128) as u64),
+            median: crate::tally::median_bucket(&self.histogram)
+                .map(|bucket| range.bucket_start(bucket)),
+            weighted_median: crate::tally::median_bucket(&self.weight_histogram)
+                .map(|bucket| range.bucket_start(bucket)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Method used to compute the winner of a poll when it ends.
+pub enum TallyStrategy {
+    /// The choice with the most votes wins.
+    #[default]
+    Plurality,
+    /// Voters rank the choices and the choice that beats every other choice in pairwise
+    /// comparisons wins. If there is no such choice, the Smith set is reported instead.
+    Condorcet,
+    /// Voters rank the choices and each choice gets one point for every choice ranked below
+    /// it (unranked choices get no points). The choice with the most points wins.
+    Borda,
+    /// Voters rank the choices and the given number of seats are filled by single
+    /// transferable vote (using the Droop quota).
+    Stv {
+        /// Number of choices elected.
+        seats: u32,
+    },
+    /// The choice with the most votes wins, and the numeric questions of the poll resolve to
+    /// the median of their answers weighted by the weight of the voters, which extreme answers
+    /// cannot move further than their weight allows.
+    WeightedMedian,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Who can view the choices and results of a poll.
+pub enum PollVisibility {
+    /// Everyone can view the poll.
+    #[default]
+    Public,
+    /// Only the owner, the moderators and the accounts on the eligibility list (the accounts
+    /// with an uploaded voter weight) can view the poll.
+    Private,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Configuration inherited by new polls (see `set_default_poll_config`). The owner of a poll can
+/// change it using the corresponding setters until the poll starts.
+pub struct PollConfig {
+    /// Visibility of the poll (see `set_poll_visibility`).
+    pub(crate) visibility: PollVisibility,
+    /// Tally strategy of the poll (see `set_tally_strategy`).
+    pub(crate) tally_strategy: TallyStrategy,
+    /// Whether the results of the poll are hidden until it ends (see
+    /// `set_hide_results_until_end`).
+    pub(crate) hide_results_until_end: bool,
+    /// Challenge period (in milliseconds) of the result of the poll (see
+    /// `set_challenge_period`).
+    pub(crate) challenge_period: Timestamp,
+}
+
+impl TallyStrategy {
+    /// Returns true if the strategy accepts ranked ballots.
+    pub(crate) fn is_ranked(self) -> bool {
+        matches!(
+            self,
+            TallyStrategy::Condorcet | TallyStrategy::Borda | TallyStrategy::Stv { .. }
+        )
+    }
+
+    /// Returns the bit of the strategy in the `tally_strategies` bitset of `Constants`.
+    pub(crate) const fn flag(self) -> u32 {
+        match self {
+            TallyStrategy::Plurality => 1 << 0,
+            TallyStrategy::Condorcet => 1 << 1,
+            TallyStrategy::Borda => 1 << 2,
+            TallyStrategy::Stv { .. } => 1 << 3,
+            TallyStrategy::WeightedMedian => 1 << 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Configuration of a poll whose ballots are encrypted until the poll ends.
+pub struct BallotEncryption {
+    /// Public key of the poll to which the ballots are encrypted.
+    pub(crate) public_key: Vec<u8>,
+    /// Members of the committee holding shares of the decryption key.
+    pub(crate) committee: Vec<AccountId>,
+    /// Number of decryption shares required before the tally can be posted.
+    pub(crate) threshold: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// An encrypted ballot.
+pub struct EncryptedBallot {
+    /// The ballot encrypted to the public key of the poll.
+    pub(crate) ciphertext: Vec<u8>,
+    /// Voting weight of the voter.
+    pub(crate) weight: u128,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Delegation of the vote of an account to another account.
+pub struct Delegation {
+    /// Account to which the vote is delegated.
+    pub(crate) delegate: AccountId,
+    /// Timestamp (in milliseconds) from which the delegation is ignored (never expires if not
+    /// present).
+    pub(crate) expires_at: Option<Timestamp>,
+}
+
+impl Delegation {
+    /// Returns true if the delegation is still in effect at the given time.
+    pub(crate) fn is_active(&self, now: Timestamp) -> bool {
+        !matches!(self.expires_at, Some(expires_at) if expires_at <= now)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+/// Errors returned by PSP22 token contracts.
+pub enum PSP22Error {
+    /// Custom error type for implementation-based errors.
+    Custom(String),
+    /// Returned when an account does not have enough tokens.
+    InsufficientBalance,
+    /// Returned when the allowance is not enough.
+    InsufficientAllowance,
+    /// Returned when the recipient address is zero.
+    ZeroRecipientAddress,
+    /// Returned when the sender address is zero.
+    ZeroSenderAddress,
+    /// Returned when a safe transfer check fails.
+    SafeTransferCheckFailed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Protocol fee taken from every value transferred to the contract (poll deposits, stakes and
+/// fee pools).
+pub struct FeeConfig {
+    /// Fee in basis points (1/100th of a percent).
+    pub(crate) bps: u16,
+    /// Account receiving the fee.
+    pub(crate) recipient: AccountId,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Status of a poll. The allowed changes of status are listed in `POLL_STATUS_TRANSITIONS`.
+///
+/// New statuses are appended so that the encoding of the statuses stored by older versions of the
+/// contract is unchanged.
+pub enum PollStatus {
+    /// Poll has not started yet.
+    NotStarted,
+    /// Poll has started.
+    Started,
+    /// Poll has ended.
+    Ended,
+    /// Poll has been scheduled to start and can no longer be changed.
+    Scheduled,
+    /// Poll is waiting for approval before it can be started.
+    PendingApproval,
+    /// Poll has been cancelled before it started.
+    Cancelled,
+}
+
+/// Allowed changes of the status of a poll, as `(from, to)` pairs.
+pub(crate) const POLL_STATUS_TRANSITIONS: &[(PollStatus, PollStatus)] = &[
+    (PollStatus::PendingApproval, PollStatus::NotStarted),
+    (PollStatus::PendingApproval, PollStatus::Cancelled),
+    (PollStatus::NotStarted, PollStatus::Scheduled),
+    (PollStatus::NotStarted, PollStatus::Started),
+    (PollStatus::NotStarted, PollStatus::Cancelled),
+    (PollStatus::Scheduled, PollStatus::Started),
+    (PollStatus::Scheduled, PollStatus::Cancelled),
+    (PollStatus::Started, PollStatus::Ended),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+/// Stage of the lifecycle of a poll reported by `PollLifecycle` events.
+pub enum PollStage {
+    /// Poll has been created.
+    Created,
+    /// Choice has been added to the poll.
+    ChoiceAdded,
+    /// Choice of the poll has been updated.
+    ChoiceUpdated,
+    /// Choice has been removed from the poll.
+    ChoiceRemoved,
+    /// Poll has started.
+    Started,
+    /// Poll has ended.
+    Ended,
+    /// Poll has been removed by a moderator.
+    Removed,
+    /// Decrypted tally of the poll has been posted.
+    TallyPosted,
+    /// Result of the poll has been challenged.
+    Challenged,
+    /// Challenge of the result of the poll has been resolved.
+    Resolved,
+    /// Poll has been deleted by its owner.
+    Deleted,
+    /// Poll has been archived by its owner.
+    Archived,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+/// Status of the result of an ended poll.
+pub enum ResultStatus {
+    /// Result can be challenged until the end of the challenge period of the poll.
+    Provisional,
+    /// Result has been challenged and awaits review.
+    Challenged,
+    /// Result is final.
+    Final,
+    /// Poll has been voided by a resolver (it has no winner and stakes are refunded).
+    Voided,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+/// Outcome of the resolution of a challenge.
+pub enum ChallengeOutcome {
+    /// Result stands and the deposit of the challenge is slashed.
+    Uphold,
+    /// Winner is replaced (no winner if not present) and the deposit is returned.
+    Overturn {
+        /// New winner of the poll.
+        winner: Option<ChoiceId>,
+    },
+    /// Poll is voided and the deposit is returned.
+    Void,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Milestones of the choices of a poll reported by `MilestoneReached` events.
+pub struct MilestoneConfig {
+    /// Reports the vote count of a choice each time it passes a multiple of this interval (`0`
+    /// to disable).
+    pub(crate) vote_interval: u128,
+    /// Reports a choice taking the lead of the poll.
+    pub(crate) lead_changes: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+/// Milestone reached by a choice of a poll.
+pub enum MilestoneKind {
+    /// Vote count of the choice has passed a multiple of the vote interval.
+    VoteCount {
+        /// Multiple of the vote interval passed.
+        count: u128,
+    },
+    /// Choice has taken the lead of the poll.
+    TookLead,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Challenge of the provisional result of a poll.
+pub struct Challenge {
+    /// Account that challenged the result.
+    pub(crate) challenger: AccountId,
+    /// Deposit transferred with the challenge.
+    pub(crate) deposit: Balance,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Claim of the keeper bounty of a poll.
+pub struct KeeperClaim {
+    /// Account that finalized the poll and claimed the bounty.
+    pub(crate) keeper: AccountId,
+    /// Amount of the bounty.
+    pub(crate) amount: Balance,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Schedule boosting the votes of long-term members, based on the date at which they joined
+/// according to a registry implementing `TenureRegistry`.
+pub struct TenureSchedule {
+    /// Contract implementing `TenureRegistry`.
+    pub(crate) registry: AccountId,
+    /// Bonus (in basis points of the weight) per full year of membership.
+    pub(crate) bonus_bps_per_year: u32,
+    /// Maximum bonus (in basis points of the weight).
+    pub(crate) max_bonus_bps: u32,
+}
+
+impl TenureSchedule {
+    /// Returns a weight boosted by the bonus of a member with the given tenure (in
+    /// milliseconds).
+    pub(crate) fn boost(&self, weight: u128, tenure: Timestamp) -> u128 {
+        let years = u128::from(tenure / TENURE_YEAR);
+        let bonus_bps = years
+            .saturating_mul(u128::from(self.bonus_bps_per_year))
+            .min(u128::from(self.max_bonus_bps));
+
+        weight.saturating_add(weight.saturating_mul(bonus_bps) / 10_000)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Upload session of the list of eligible voters of a poll (see `add_eligible_voters_chunk`).
+pub struct VoterListUpload {
+    /// Number of accounts added to the list.
+    pub(crate) count: u32,
+    /// Whether the list has been sealed (no accounts can be added once sealed).
+    pub(crate) sealed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Number of ballots cast on a poll during a bucket of the vote timeline of the poll.
+pub struct TimelineBucket {
+    /// Start of the bucket (a multiple of `TIMELINE_BUCKET_DURATION`).
+    pub(crate) start_at: Timestamp,
+    /// Number of ballots cast during the bucket.
+    pub(crate) ballots: u64,
+}
+
+impl PollStatus {
+    /// Returns true if a poll can move from this status to the given status.
+    pub(crate) fn can_transition_to(self, to: PollStatus) -> bool {
+        POLL_STATUS_TRANSITIONS.contains(&(self, to))
+    }
+}
+
+impl Poll {
+    /// Returns an error describing the current status of the poll if it is not the expected
+    /// status.
+    pub(crate) fn ensure_status(&self, expected: PollStatus) -> Result<(), Error> {
+        if self.status == expected {
+            return Ok(());
+        }
+
+        match self.status {
+            PollStatus::NotStarted => Err(Error::PollHasNotStarted),
+            PollStatus::Started => Err(Error::PollHasStarted),
+            PollStatus::Ended => Err(Error::PollHasEnded),
+            PollStatus::Scheduled => Err(Error::PollIsScheduled),
+            PollStatus::PendingApproval => Err(Error::PollIsPendingApproval),
+            PollStatus::Cancelled => Err(Error::PollIsCancelled),
+        }
+    }
+
+    /// Returns an error if the poll has ended or has been cancelled.
+    pub(crate) fn ensure_not_closed(&self) -> Result<(), Error> {
+        match self.status {
+            PollStatus::Ended => Err(Error::PollHasEnded),
+            PollStatus::Cancelled => Err(Error::PollIsCancelled),
+            _ => Ok(()),
+        }
+    }
+
+    /// Changes the status of the poll, returning an error if the transition is not allowed.
+    pub(crate) fn transition_to(&mut self, to: PollStatus) -> Result<(), Error> {
+        if !self.status.can_transition_to(to) {
+            return Err(Error::InvalidStatusTransition {
+                from: self.status,
+                to,
+            });
+        }
+
+        self.status = to;
+
+        Ok(())
+    }
+}
+
+/// Report generated for a poll.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PollReport {
+    /// Id of the poll.
+    pub(crate) id: PollId,
+    /// Description of the poll
+    pub(crate) description: String,
+    /// Status of the poll.
+    pub(crate) status: PollStatus,
+    /// Account that created the poll.
+    pub(crate) owner: AccountId,
+    /// List of choices for the poll.
+    pub(crate) choices: Vec<ChoiceReport>,
+    /// Id of the winning choice (present if the poll has ended).
+    pub(crate) winner: Option<ChoiceId>,
+    /// Timestamp (in milliseconds) from which the poll accepts votes.
+    pub(crate) start_at: Timestamp,
+    /// Timestamp (in milliseconds) after which the poll no longer accepts votes.
+    pub(crate) end_at: Timestamp,
+    /// Seed of the shuffle applied to the order of the choices (present if the report was
+    /// generated for an account and the poll shuffles its choices).
+    pub(crate) shuffle_seed: Option<[u8; 32]>,
+    /// Questions of the poll (empty unless the poll has multiple questions).
+    pub(crate) questions: Vec<QuestionReport>,
+    /// Number of accounts recused from voting on the poll.
+    pub(crate) recusal_count: u32,
+}
+
+/// Report of a question of a poll with multiple questions.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct QuestionReport {
+    /// Id of the question.
+    pub(crate) id: QuestionId,
+    /// Description of the question.
+    pub(crate) description: String,
+    /// Ids of the choices of the question (whose counts are reported with the choices of the
+    /// poll).
+    pub(crate) choice_ids: Vec<ChoiceId>,
+    /// Id of the choice with the most votes among the choices of the question (present if the
+    /// poll has ended and there is no tie).
+    pub(crate) winner: Option<ChoiceId>,
+    /// Range of the accepted values (present if the question is numeric).
+    pub(crate) range: Option<NumericRange>,
+    /// Aggregate statistics of the answers (present if the question is numeric, unless the
+    /// results of the poll are hidden).
+    pub(crate) statistics: Option<NumericStatistics>,
+}
+
+/// Aggregate statistics of the answers to a numeric question.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct NumericStatistics {
+    /// Number of answers.
+    pub(crate) count: u64,
+    /// Sum of the answers.
+    pub(crate) sum: u128,
+    /// Mean of the answers, rounded down (present if there are answers).
+    pub(crate) mean: Option<u64>,
+    /// Median of the answers (present if there are answers). It is exact if the range spans at
+    /// most `MAX_NUMERIC_BUCKETS` values, and otherwise the smallest value of the bucket holding
+    /// the median.
+    pub(crate) median: Option<u64>,
+    /// Median of the answers weighted by the weight of the voters, rounded like `median`
+    /// (present if the poll has the `WeightedMedian` tally strategy and the answers have a
+    /// weight).
+    pub(crate) weighted_median: Option<u64>,
+}
+
+/// Report generated for a poll with the accounts resolved to their display names.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct IdentityReport {
+    /// Report of the poll.
+    pub(crate) report: PollReport,
+    /// Display name of the owner of the poll (if registered).
+    pub(crate) owner_name: Option<String>,
+    /// Voters of the poll with their display names and choices (empty unless the poll has
+    /// public ballots).
+    pub(crate) voters: Vec<VoterIdentity>,
+    /// Offset of the next page of voters (not present if this page is the last one).
+    pub(crate) next_offset: Option<u32>,
+}
+
+/// Voter of a poll with public ballots.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct VoterIdentity {
+    /// Account of the voter.
+    pub(crate) account: AccountId,
+    /// Display name of the voter (if registered).
+    pub(crate) display_name: Option<String>,
+    /// Id of the choice voted for.
+    pub(crate) choice_id: ChoiceId,
+}
+
+/// Template of recurring polls (see `create_poll_template`).
+#[derive(Debug, Clone, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct PollTemplate {
+    /// Account that created the template.
+    pub(crate) owner: AccountId,
+    /// Description of the polls.
+    pub(crate) description: Description,
+    /// Descriptions of the choices of the polls.
+    pub(crate) choices: Vec<Description>,
+    /// Duration (in milliseconds) of the polls.
+    pub(crate) duration: Timestamp,
+    /// Tally strategy of the polls.
+    pub(crate) tally_strategy: TallyStrategy,
+    /// Challenge period (in milliseconds) of the polls.
+    pub(crate) challenge_period: Timestamp,
+}
+
+/// Compact summary of a poll, suitable for list views.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PollSummary {
+    /// Id of the poll.
+    pub(crate) id: PollId,
+    /// Description of the poll (truncated to `SUMMARY_DESCRIPTION_LENGTH` characters).
+    pub(crate) short_description: String,
+    /// Status of the poll.
+    pub(crate) status: PollStatus,
+    /// Account that created the poll.
+    pub(crate) owner: AccountId,
+    /// Total number of votes across all the choices of the poll.
+    pub(crate) total_votes: u128,
+    /// Id of the winning choice (present if the poll has ended).
+    pub(crate) winner: Option<ChoiceId>,
+}
+
+/// Scheme used to derive the topics of the `PollLifecycle` events.
+///
+/// The topic of a poll is the Blake2x256 hash of the SCALE encoding of `(domain, poll_id)`. The
+/// namespace of a poll with a topic seed is the Blake2x256 hash of the SCALE encoding of
+/// `(namespace_domain, owner, seed)`, and the events of the poll carry the namespace itself as a
+/// topic (the topic of the events of polls without a namespace is all zeros).
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct TopicScheme {
+    /// Version of the scheme.
+    pub(crate) version: u8,
+    /// Domain hashed with the id of a poll.
+    pub(crate) domain: String,
+    /// Domain hashed with the owner and the topic seed of a poll to derive its namespace.
+    pub(crate) namespace_domain: String,
+}
+
+/// Report of the turnout of a poll.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct TurnoutReport {
+    /// Id of the poll.
+    pub(crate) id: PollId,
+    /// Number of accounts that voted on the poll.
+    pub(crate) total_ballots: u64,
+    /// Number of accounts registered to vote on the poll (the electorate of polls requiring
+    /// registration, `0` otherwise).
+    pub(crate) registered_voters: u64,
+    /// Number of ballots cast again after being retracted (see `retract_vote`).
+    pub(crate) changed_ballots: u64,
+}
+
+/// Report of the quorum of a poll.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct QuorumReport {
+    /// Share (in basis points) of the eligible weight that must be cast (`0` if the poll has no
+    /// quorum).
+    pub(crate) quorum_weight_bps: u16,
+    /// Total eligible weight of the poll.
+    pub(crate) eligible_weight: u128,
+    /// Weight of the votes required to reach the quorum.
+    pub(crate) required_weight: u128,
+    /// Weight of the votes cast on the poll.
+    pub(crate) cast_weight: u128,
+    /// Whether the poll has reached its quorum.
+    pub(crate) reached: bool,
+}
+
+/// Report generated for a choice.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ChoiceReport {
+    /// Id of the choice.
+    pub(crate) id: ChoiceId,
+    /// Description of the choice.
+    pub(crate) description: String,
+    /// Number of votes for the choice (first preferences for ranked polls).
+    pub(crate) vote_count: u128,
+    /// Number of accounts that voted for the choice, regardless of the weight of their votes.
+    pub(crate) voter_count: u64,
+    /// Number of points of the choice (zero unless the poll uses the Borda tally strategy).
+    pub(crate) points: u128,
+    /// Number of votes the choice can still receive (`None` if the choices of the poll are
+    /// uncapped or its results are hidden).
+    pub(crate) remaining_capacity: Option<u32>,
+}
+
+/// Record of the state of a poll exported by `export_state`. The records of a poll are the poll
+/// itself, followed by its choices and (for polls with public ballots) its ballots.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ExportRecord {
+    /// The poll.
+    Poll {
+        /// The poll.
+        poll: Poll,
+        /// Description of the poll.
+        description: Description,
+    },
+    /// A choice of the poll with its results.
+    Choice(ChoiceReport),
+    /// A public ballot of the poll.
+    Ballot {
+        /// Account of the voter.
+        voter: AccountId,
+        /// Id of the choice voted for.
+        choice_id: ChoiceId,
+    },
+}
+
+/// Result of an ended poll attested by `get_result_attestation`.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ResultAttestation {
+    /// Id of the poll.
+    pub(crate) poll_id: PollId,
+    /// Hash of the configuration of the poll (its description, owner, schedule, tally strategy
+    /// and choices).
+    pub(crate) config_hash: [u8; 32],
+    /// Winner of the poll.
+    pub(crate) winner: Option<ChoiceId>,
+    /// Number of votes for each choice of the poll.
+    pub(crate) counts: Vec<(ChoiceId, u128)>,
+    /// Block in which the poll ended (`0` for polls ended before blocks were recorded).
+    pub(crate) end_block: BlockNumber,
+    /// Root of the Merkle tree of the ballots of the poll (see `merkle`), whose leaves are the
+    /// hashes of the encoded `(voter, choice_id, weight)` of the recorded ballots.
+    pub(crate) ballot_root: [u8; 32],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+/// Action pending on a poll, reported by `get_owner_dashboard`.
+pub enum PendingAction {
+    /// Poll has no choices yet and cannot be started.
+    AddChoices,
+    /// Poll has not been started although its start time has passed.
+    Start,
+    /// Poll has expired but has not been ended or finalized yet.
+    Finalize,
+    /// Poll has ended and its deposit has not been claimed yet.
+    ClaimDeposit,
+}
+
+/// Entry of a poll in the dashboard of its owner.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OwnerPollEntry {
+    /// Id of the poll.
+    pub(crate) id: PollId,
+    /// Status of the poll.
+    pub(crate) status: PollStatus,
+    /// Number of accounts that voted on the poll.
+    pub(crate) total_ballots: u64,
+    /// Number of accounts registered to vote on the poll (`0` unless the poll requires
+    /// registration).
+    pub(crate) registered_voters: u64,
+    /// Actions pending on the poll.
+    pub(crate) pending_actions: Vec<PendingAction>,
+}
+
+/// Dashboard of the polls created by an account.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OwnerDashboard {
+    /// Account that created the polls.
+    pub(crate) owner: AccountId,
+    /// Polls created by the account (oldest first).
+    pub(crate) polls: Vec<OwnerPollEntry>,
+}
+
+/// Compile-time limits and versions of the deployed build of the contract, returned by
+/// `constants`.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Constants {
+    /// Maximum number of choices of a poll (the number of choice ids).
+    pub(crate) max_choices: u64,
+    /// Maximum length (in bytes) of a description (not present if descriptions are not limited).
+    pub(crate) max_description_length: Option<u32>,
+    /// Maximum number of characters of the poll description included in a `PollSummary`.
+    pub(crate) summary_description_length: u32,
+    /// Maximum number of choices added in a single call to `add_choices`.
+    pub(crate) max_choice_batch_size: u32,
+    /// Maximum number of voter weights uploaded in a single call to `set_voter_weights`.
+    pub(crate) max_voter_weight_batch_size: u32,
+    /// Maximum number of accounts added in a single call to `add_eligible_voters_chunk`.
+    pub(crate) max_eligible_voter_chunk_size: u32,
+    /// Duration (in milliseconds) of the buckets of the vote timelines.
+    pub(crate) timeline_bucket_duration: Timestamp,
+    /// Maximum number of records exported in a single call to `export_state`.
+    pub(crate) max_export_chunk_size: u32,
+    /// Maximum number of polls summarized in a single call to `get_summaries`.
+    pub(crate) max_summary_batch_size: u32,
+    /// Maximum number of admin log entries returned by a single call to `get_admin_log`.
+    pub(crate) max_admin_log_page_size: u32,
+    /// Maximum protocol fee in basis points.
+    pub(crate) max_fee_bps: u16,
+    /// Storage version of the polls written by the contract.
+    pub(crate) poll_storage_version: u8,
+    /// Version of the schema of the events emitted by the contract.
+    pub(crate) event_schema_version: u8,
+    /// Version of the scheme of the poll topics.
+    pub(crate) topic_scheme_version: u8,
+    /// Bitset of the supported tally strategies: plurality (bit 0), Condorcet (bit 1), Borda
+    /// (bit 2) and STV (bit 3).
+    pub(crate) tally_strategies: u32,
+}
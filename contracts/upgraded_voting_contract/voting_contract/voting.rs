@@ -0,0 +1,761 @@
+//! Vote validation and recording helpers of the voting contract.
+
+use super::*;
+
+impl VotingContract {
+    /// Counts a voter for a choice of a poll.
+    pub(crate) fn add_voter(&mut self, poll_id: PollId, choice_id: ChoiceId) {
+        let voter_count = self
+            .voter_counts
+            .get((poll_id, choice_id))
+            .unwrap_or_default();
+        self.voter_counts
+            .insert((poll_id, choice_id), &voter_count.saturating_add(1));
+    }
+
+    /// Stops counting a voter (whose vote was retracted) for a choice of a poll.
+    pub(crate) fn remove_voter(&mut self, poll_id: PollId, choice_id: ChoiceId) {
+        let voter_count = self
+            .voter_counts
+            .get((poll_id, choice_id))
+            .unwrap_or_default();
+        self.voter_counts
+            .insert((poll_id, choice_id), &voter_count.saturating_sub(1));
+    }
+
+    /// Records the vote of an account on a poll after checking that the vote is allowed and
+    /// returns the number of its ballot.
+    pub(crate) fn record_vote(
+        &mut self,
+        poll_id: PollId,
+        choice_id: ChoiceId,
+        voter: AccountId,
+    ) -> Result<u64, Error> {
+        self.record_vote_with_weight(poll_id, choice_id, voter, None)
+    }
+
+    /// Records the vote of an account on a poll like `record_vote`, counting the vote with the
+    /// given weight instead of the voting weight of the account (if present).
+    pub(crate) fn record_vote_with_weight(
+        &mut self,
+        poll_id: PollId,
+        choice_id: ChoiceId,
+        voter: AccountId,
+        weight: Option<u128>,
+    ) -> Result<u64, Error> {
+        // Check if the vote is allowed and get its weight.
+        let weight = self.check_vote_with_weight(poll_id, Some(choice_id), voter, weight)?;
+
+        // Get the current vote count.
+        let current_vote_count = self
+            .vote_counts
+            .get((poll_id, choice_id))
+            .unwrap_or_default();
+
+        // Calculate the new vote count (increment the current vote count by the weight of the vote).
+        let new_vote_count = current_vote_count.saturating_add(weight);
+
+        // Insert the new vote count into storage.
+        self.vote_counts
+            .insert((poll_id, choice_id), &new_vote_count);
+        self.add_voter(poll_id, choice_id);
+
+        // Count the vote against the capacity of the choice (keeping its weight, as the vote can
+        // be retracted).
+        if self.choice_capacities.contains(poll_id) {
+            let ballots = self
+                .choice_ballots
+                .get((poll_id, choice_id))
+                .unwrap_or_default();
+            self.choice_ballots
+                .insert((poll_id, choice_id), &(ballots + 1));
+            self.retractable_weights.insert((poll_id, voter), &weight);
+        }
+
+        // Report the milestones reached by the choice.
+        self.emit_milestones(poll_id, choice_id, current_vote_count, new_vote_count);
+
+        // Insert the voter and their ballot into storage.
+        self.voted_by.insert((poll_id, voter), &true);
+        self.ballots.insert((poll_id, voter), &choice_id);
+
+        // Append the ballot to the Merkle tree of the ballots of the poll.
+        let mut ballot_tree = self.ballot_trees.get(poll_id).unwrap_or_default();
+        ballot_tree.push(
+            self.env()
+                .hash_encoded::<ink::env::hash::Blake2x256, _>(&(voter, choice_id, weight)),
+        );
+        self.ballot_trees.insert(poll_id, &ballot_tree);
+
+        // List the voter if the ballots of the poll are public.
+        if let Some(slot) = self.public_ballots.get(poll_id) {
+            self.public_voters.insert((poll_id, slot), &voter);
+            self.public_voter_slots.insert((poll_id, voter), &slot);
+            self.public_ballots.insert(poll_id, &(slot + 1));
+        }
+
+        // Count the ballot.
+        Ok(self.count_ballot(poll_id, voter))
+    }
+
+    /// Records the ballot of the caller answering the questions of a poll (each answer counts
+    /// with the full weight of the caller for its choice) and returns the number of the ballot.
+    pub(crate) fn cast_ballot(
+        &mut self,
+        poll_id: PollId,
+        answers: Vec<(QuestionId, Answer)>,
+    ) -> Result<u64, Error> {
+        // Count the call against the rate limit of the caller.
+        self.consume_rate_limit()?;
+
+        // Check if the vote is allowed and get its weight.
+        let caller = self.env().caller();
+        let weight = self.check_vote(poll_id, None, caller)?;
+
+        // Check if the poll accepts plaintext ballots answering its questions.
+        if self.ballot_encryption.contains(poll_id) {
+            return Err(Error::PollRequiresEncryptedVote);
+        }
+
+        if !self.question_ids.contains(poll_id) || self.get_tally_strategy(poll_id).is_ranked() {
+            return Err(Error::PollDoesNotAcceptBallots);
+        }
+
+        // Check if every answer is a choice (or a value within the range) of its question and
+        // every question is answered at most once.
+        if answers.is_empty() {
+            return Err(Error::InvalidAnswers);
+        }
+
+        for (position, (question_id, answer)) in answers.iter().enumerate() {
+            let is_valid = match answer {
+                Answer::Choice(choice_id) => {
+                    self.choice_questions.get((poll_id, *choice_id)) == Some(*question_id)
+                }
+                Answer::Value(value) => self
+                    .numeric_ranges
+                    .get((poll_id, *question_id))
+                    .is_some_and(|range| range.contains(*value)),
+            };
+
+            if !is_valid
+                || answers[..position]
+                    .iter()
+                    .any(|(answered_id, _)| answered_id == question_id)
+            {
+                return Err(Error::InvalidAnswers);
+            }
+        }
+
+        // Count the answers (weighting the answers to numeric questions for polls tallied by
+        // weighted median).
+        let median_weight =
+            (self.get_tally_strategy(poll_id) == TallyStrategy::WeightedMedian).then_some(weight);
+
+        for (question_id, answer) in &answers {
+            match answer {
+                Answer::Choice(choice_id) => {
+                    let current_vote_count = self
+                        .vote_counts
+                        .get((poll_id, *choice_id))
+                        .unwrap_or_default();
+                    let new_vote_count = current_vote_count.saturating_add(weight);
+
+                    self.vote_counts
+                        .insert((poll_id, *choice_id), &new_vote_count);
+                    self.add_voter(poll_id, *choice_id);
+                    self.emit_milestones(poll_id, *choice_id, current_vote_count, new_vote_count);
+                }
+                Answer::Value(value) => {
+                    if let Some(range) = self.numeric_ranges.get((poll_id, *question_id)) {
+                        let mut tally = self
+                            .numeric_tallies
+                            .get((poll_id, *question_id))
+                            .unwrap_or_default();
+                        tally.record(range, *value, median_weight);
+                        self.numeric_tallies.insert((poll_id, *question_id), &tally);
+                    }
+                }
+            }
+        }
+
+        // Insert the voter into storage.
+        self.voted_by.insert((poll_id, caller), &true);
+
+        // Append the ballot to the Merkle tree of the ballots of the poll.
+        let mut ballot_tree = self.ballot_trees.get(poll_id).unwrap_or_default();
+        ballot_tree.push(
+            self.env()
+                .hash_encoded::<ink::env::hash::Blake2x256, _>(&(caller, &answers, weight)),
+        );
+        self.ballot_trees.insert(poll_id, &ballot_tree);
+
+        // Count the ballot.
+        Ok(self.count_ballot(poll_id, caller))
+    }
+
+    /// Adds the caller to the waitlist of a full choice of a poll.
+    pub(crate) fn join_waitlist(
+        &mut self,
+        poll_id: PollId,
+        choice_id: ChoiceId,
+    ) -> Result<(), Error> {
+        // Count the call against the rate limit of the caller.
+        self.consume_rate_limit()?;
+
+        let caller = self.env().caller();
+
+        // Check if the caller is already waiting for a seat.
+        if self.waitlisted_by.contains((poll_id, caller)) {
+            return Err(Error::CallerAlreadyWaitlisted);
+        }
+
+        let mut waitlist = self.waitlists.get((poll_id, choice_id)).unwrap_or_default();
+
+        if waitlist.len() >= MAX_WAITLIST_LENGTH as usize {
+            return Err(Error::WaitlistIsFull);
+        }
+
+        // Append the caller to the waitlist.
+        waitlist.push(caller);
+        self.waitlists.insert((poll_id, choice_id), &waitlist);
+        self.waitlisted_by.insert((poll_id, caller), &choice_id);
+
+        // Emit the event.
+        self.env().emit_event(VoterWaitlisted {
+            schema_version: EVENT_SCHEMA_VERSION,
+            poll_id,
+            namespace: self.topic_namespaces.get(poll_id),
+            voter: caller,
+            choice_id,
+            position: waitlist.len() as u32,
+        });
+
+        Ok(())
+    }
+
+    /// Records the vote of the first voter of the waitlist of a choice who can still vote for it,
+    /// skipping (and removing) the ones who can't (e.g. because they voted for another choice).
+    pub(crate) fn promote_waitlisted(&mut self, poll_id: PollId, choice_id: ChoiceId) {
+        let mut waitlist = self.waitlists.get((poll_id, choice_id)).unwrap_or_default();
+
+        while !waitlist.is_empty() {
+            let voter = waitlist.remove(0);
+            self.waitlisted_by.remove((poll_id, voter));
+
+            if let Ok(ballot_number) = self.record_vote(poll_id, choice_id, voter) {
+                // Emit the event.
+                self.env().emit_event(VoterPromoted {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    poll_id,
+                    namespace: self.topic_namespaces.get(poll_id),
+                    voter,
+                    choice_id,
+                    ballot_number,
+                });
+
+                break;
+            }
+        }
+
+        if waitlist.is_empty() {
+            self.waitlists.remove((poll_id, choice_id));
+        } else {
+            self.waitlists.insert((poll_id, choice_id), &waitlist);
+        }
+    }
+
+    /// Counts an accepted ballot of a voter on a poll, assigning it the next ballot number of the
+    /// poll (starting at 1), and returns its number.
+    pub(crate) fn count_ballot(&mut self, poll_id: PollId, voter: AccountId) -> u64 {
+        // Increment the number of ballots for the poll.
+        let ballot_number = self.total_ballots.get(poll_id).unwrap_or_default() + 1;
+        self.total_ballots.insert(poll_id, &ballot_number);
+
+        // Insert the ballot number of the voter into storage.
+        self.ballot_numbers.insert((poll_id, voter), &ballot_number);
+
+        // Count the ballot as changed if the voter retracted an earlier one.
+        if self.retracted_voters.take((poll_id, voter)).is_some() {
+            let changed_ballots = self.changed_ballots.get(poll_id).unwrap_or_default();
+            self.changed_ballots
+                .insert(poll_id, &changed_ballots.saturating_add(1));
+        }
+
+        // Count the ballot in the current bucket of the vote timeline (ballots are counted in
+        // chronological order, so only the last bucket can be the current one).
+        let now = self.env().block_timestamp();
+        let start_at = now - now % TIMELINE_BUCKET_DURATION;
+        let mut timeline = self.vote_timelines.get(poll_id).unwrap_or_default();
+
+        match timeline.last_mut() {
+            Some(bucket) if bucket.start_at == start_at => bucket.ballots += 1,
+            _ => timeline.push(TimelineBucket {
+                start_at,
+                ballots: 1,
+            }),
+        }
+
+        self.vote_timelines.insert(poll_id, &timeline);
+
+        // Emit the event.
+        self.env().emit_event(VoteCast {
+            schema_version: EVENT_SCHEMA_VERSION,
+            poll_id,
+            namespace: self.topic_namespaces.get(poll_id),
+            voter,
+            ballot_number,
+        });
+
+        // Push the ballot to the stats contract.
+        self.push_stats(
+            poll_id,
+            ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                "PollStats::on_vote_cast"
+            )))
+            .push_arg(poll_id)
+            .push_arg(ballot_number),
+        );
+
+        ballot_number
+    }
+
+    /// Emits the milestones of a poll reached by a choice whose vote count went from
+    /// `previous_count` to `new_count`, unless the results of the poll are hidden.
+    pub(crate) fn emit_milestones(
+        &mut self,
+        poll_id: PollId,
+        choice_id: ChoiceId,
+        previous_count: u128,
+        new_count: u128,
+    ) {
+        let Some(milestones) = self.milestone_configs.get(poll_id) else {
+            return;
+        };
+
+        if self.hidden_results.contains(poll_id) {
+            return;
+        }
+
+        // Report the last multiple of the vote interval passed by the count.
+        let interval = milestones.vote_interval;
+
+        if interval > 0 && new_count / interval > previous_count / interval {
+            self.env().emit_event(MilestoneReached {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                choice_id,
+                kind: MilestoneKind::VoteCount {
+                    count: new_count / interval * interval,
+                },
+            });
+        }
+
+        // Report the choice taking the lead (ties do not change the leader).
+        if milestones.lead_changes {
+            let takes_lead = match self.leaders.get(poll_id) {
+                Some(leader) if leader == choice_id => false,
+                Some(leader) => {
+                    new_count > self.vote_counts.get((poll_id, leader)).unwrap_or_default()
+                }
+                None => true,
+            };
+
+            if takes_lead {
+                self.leaders.insert(poll_id, &choice_id);
+
+                self.env().emit_event(MilestoneReached {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    poll_id,
+                    namespace: self.topic_namespaces.get(poll_id),
+                    choice_id,
+                    kind: MilestoneKind::TookLead,
+                });
+            }
+        }
+    }
+
+    /// Creates a delegation to an account, checking that its expiry is in the future.
+    pub(crate) fn new_delegation(
+        &self,
+        delegate: AccountId,
+        expires_at: Option<Timestamp>,
+    ) -> Result<Delegation, Error> {
+        if matches!(expires_at, Some(expires_at) if expires_at <= self.env().block_timestamp()) {
+            return Err(Error::InvalidDelegationExpiry);
+        }
+
+        Ok(Delegation {
+            delegate,
+            expires_at,
+        })
+    }
+
+    /// Returns the weight of the vote of an account on a poll: the uploaded weight for polls
+    /// with voter weights and the weight given by the weight source otherwise (boosted by the
+    /// tenure schedule, if any).
+    pub(crate) fn voting_weight(&self, poll_id: PollId, voter: AccountId) -> u128 {
+        if let Some(weight) = self.voting_keys.get((poll_id, voter)) {
+            weight
+        } else if self.weighted_polls.contains(poll_id) {
+            self.voter_weights.get((poll_id, voter)).unwrap_or_default()
+        } else {
+            self.tenure_weight(voter, WEIGHT_SOURCE.weight_of(voter))
+        }
+    }
+
+    /// Returns the weight of an account boosted according to the tenure schedule (unchanged
+    /// without a schedule or if the account is not a member of the tenure registry).
+    pub(crate) fn tenure_weight(&self, account: AccountId, weight: u128) -> u128 {
+        let Some(schedule) = &self.tenure_schedule else {
+            return weight;
+        };
+
+        match self.member_since(schedule.registry, account) {
+            Some(since) => {
+                schedule.boost(weight, self.env().block_timestamp().saturating_sub(since))
+            }
+            None => weight,
+        }
+    }
+
+    /// Returns true if the member with the given index has voted on a poll.
+    pub(crate) fn has_member_voted(&self, poll_id: PollId, member_index: u32) -> bool {
+        let (page, byte, mask) = bitmap_position(member_index);
+
+        self.member_votes
+            .get((poll_id, page))
+            .is_some_and(|bits| bits[byte] & mask != 0)
+    }
+
+    /// Returns the account derived from a voting key (the Blake2x256 hash of the key).
+    pub(crate) fn key_account(&self, public_key: &[u8; 33]) -> AccountId {
+        AccountId::from(
+            self.env()
+                .hash_bytes::<ink::env::hash::Blake2x256>(public_key),
+        )
+    }
+
+    /// Checks a ranking of choices and returns the most preferred choice. Rankings of a single
+    /// choice are accepted by every poll (the choice itself is checked by `check_vote`).
+    pub(crate) fn check_ranking(
+        &self,
+        poll_id: PollId,
+        ranking: &[ChoiceId],
+    ) -> Result<ChoiceId, Error> {
+        let (&choice_id, rest) = ranking.split_first().ok_or(Error::InvalidRanking)?;
+
+        if rest.is_empty() {
+            return Ok(choice_id);
+        }
+
+        // Check if the poll exists and accepts ranked ballots.
+        if !self.polls.contains(poll_id) {
+            return Err(Error::PollWithIdDoesNotExist);
+        }
+
+        if !self.get_tally_strategy(poll_id).is_ranked() {
+            return Err(Error::PollDoesNotAcceptRankedBallots);
+        }
+
+        // Check if every choice exists and is ranked only once.
+        for (position, choice_id) in ranking.iter().enumerate() {
+            if !self.choices.contains((poll_id, *choice_id)) {
+                return Err(Error::ChoiceWithIdDoesNotExist);
+            }
+
+            if ranking[..position].contains(choice_id) {
+                return Err(Error::InvalidRanking);
+            }
+        }
+
+        Ok(choice_id)
+    }
+
+    /// Records the ranking of a voter for polls with a ranked tally strategy.
+    pub(crate) fn record_ranking(
+        &mut self,
+        poll_id: PollId,
+        ranking: &[ChoiceId],
+        voter: AccountId,
+    ) {
+        let weight = self.voting_weight(poll_id, voter);
+
+        match self.get_tally_strategy(poll_id) {
+            TallyStrategy::Plurality | TallyStrategy::WeightedMedian => return,
+            TallyStrategy::Condorcet => self.record_pairwise_preferences(poll_id, ranking, weight),
+            TallyStrategy::Borda => self.record_borda_points(poll_id, ranking, weight),
+            TallyStrategy::Stv { .. } => self.record_stv_ballot(poll_id, ranking, weight),
+        }
+
+        self.ranked_ballots
+            .insert((poll_id, voter), &ranking.to_vec());
+    }
+
+    /// Records the pairwise preferences of a ranking in a poll with the Condorcet tally
+    /// strategy.
+    pub(crate) fn record_pairwise_preferences(
+        &mut self,
+        poll_id: PollId,
+        ranking: &[ChoiceId],
+        weight: u128,
+    ) {
+        let choice_ids = self.choice_id_list(poll_id);
+
+        // Each ranked choice is preferred to the choices ranked after it and to the choices
+        // that are not ranked.
+        for (position, preferred) in ranking.iter().enumerate() {
+            for other in choice_ids
+                .iter()
+                .filter(|choice_id| !ranking[..=position].contains(choice_id))
+            {
+                let key = (poll_id, *preferred, *other);
+                let preference = self.pairwise_preferences.get(key).unwrap_or_default();
+                self.pairwise_preferences
+                    .insert(key, &preference.saturating_add(weight));
+            }
+        }
+    }
+
+    /// Records the points of a ranking in a poll with the Borda tally strategy.
+    pub(crate) fn record_borda_points(
+        &mut self,
+        poll_id: PollId,
+        ranking: &[ChoiceId],
+        weight: u128,
+    ) {
+        let num_choices = self.choice_count.get(poll_id).unwrap_or_default() as usize;
+
+        // A ranked choice gets one point for every choice ranked below it.
+        for (position, choice_id) in ranking.iter().enumerate() {
+            let below = (num_choices - 1 - position) as u128;
+            let points = self
+                .borda_points
+                .get((poll_id, *choice_id))
+                .unwrap_or_default();
+            self.borda_points.insert(
+                (poll_id, *choice_id),
+                &points.saturating_add(below.saturating_mul(weight)),
+            );
+        }
+    }
+
+    /// Records a ranking in a poll with the STV tally strategy.
+    pub(crate) fn record_stv_ballot(
+        &mut self,
+        poll_id: PollId,
+        ranking: &[ChoiceId],
+        weight: u128,
+    ) {
+        let mut ballots = self.stv_ballots.get(poll_id).unwrap_or_default();
+
+        match ballots
+            .iter_mut()
+            .find(|(existing, _)| existing.as_slice() == ranking)
+        {
+            Some((_, existing_weight)) => *existing_weight = existing_weight.saturating_add(weight),
+            None => ballots.push((ranking.to_vec(), weight)),
+        }
+
+        self.stv_ballots.insert(poll_id, &ballots);
+    }
+
+    /// Returns an error if voting requires accepting terms and the account has not accepted the
+    /// current ones.
+    pub(crate) fn ensure_terms_accepted(&self, account: AccountId) -> Result<(), Error> {
+        if self.terms_hash.is_some() && !self.has_accepted(account) {
+            return Err(Error::TermsNotAccepted);
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error if a poll requires registration and the account has not registered.
+    pub(crate) fn ensure_registered(
+        &self,
+        poll_id: PollId,
+        account: AccountId,
+    ) -> Result<(), Error> {
+        if self.registration_deposits.contains(poll_id)
+            && !self.registrations.contains((poll_id, account))
+        {
+            return Err(Error::VoterIsNotRegistered);
+        }
+
+        Ok(())
+    }
+
+    /// Performs all the checks for a vote of an account on a poll without mutating state and
+    /// returns the weight of the vote.
+    pub(crate) fn check_vote(
+        &self,
+        poll_id: PollId,
+        choice_id: Option<ChoiceId>,
+        voter: AccountId,
+    ) -> Result<u128, Error> {
+        self.check_vote_with_weight(poll_id, choice_id, voter, None)
+    }
+
+    /// Checks a vote like `check_vote`, using the given weight instead of the voting weight of
+    /// the voter (if present).
+    pub(crate) fn check_vote_with_weight(
+        &self,
+        poll_id: PollId,
+        choice_id: Option<ChoiceId>,
+        voter: AccountId,
+        weight: Option<u128>,
+    ) -> Result<u128, Error> {
+        // Check if the contract is paused.
+        if self.paused {
+            return Err(Error::ContractIsPaused);
+        }
+
+        // Check if voting is paused.
+        if self.circuit_breakers.pause_voting {
+            return Err(Error::VotingIsPaused);
+        }
+
+        // Get the poll and return error if it does not exist.
+        let poll = self
+            .polls
+            .get(poll_id)
+            .ok_or(Error::PollWithIdDoesNotExist)?;
+
+        // Check the status and return error if the poll has not started or has ended.
+        poll.ensure_status(PollStatus::Started)?;
+
+        // Check if the current time is within the voting window of the poll.
+        let now = self.env().block_timestamp();
+
+        if now < poll.start_at {
+            return Err(Error::PollHasNotStarted);
+        }
+
+        if now >= poll.end_at {
+            return Err(Error::PollHasEnded);
+        }
+
+        if let Some(choice_id) = choice_id {
+            // Check if the poll expects a ballot answering its questions.
+            if self.question_ids.contains(poll_id) {
+                return Err(Error::PollRequiresBallot);
+            }
+
+            // Check if the poll accepts plaintext votes.
+            if self.ballot_encryption.contains(poll_id) {
+                return Err(Error::PollRequiresEncryptedVote);
+            }
+
+            // Check if the choice exists (using the choice bitmap, whose reads do not depend on the
+            // length of the description of the choice).
+            if !self.has_choice(poll_id, choice_id) {
+                return Err(Error::ChoiceWithIdDoesNotExist);
+            }
+        }
+
+        // Check if the voter can vote on a private poll.
+        if self.private_polls.contains(poll_id) {
+            if !self.voting_keys.contains((poll_id, voter)) {
+                return Err(Error::PollRequiresPrivateVote);
+            }
+        } else {
+            // Check if the voter has accepted the terms and registered (the accounts registering
+            // the voting keys of private polls are checked on registration).
+            self.ensure_terms_accepted(voter)?;
+            self.ensure_registered(poll_id, voter)?;
+        }
+
+        // Check if the voter is the owner of a poll on which its owner may not vote.
+        if voter == poll.owner && self.abstaining_owners.contains(poll_id) {
+            return Err(Error::OwnerCannotVote);
+        }
+
+        // Check if the voter is recused from voting on the poll.
+        if self.recusals.contains((poll_id, voter)) {
+            return Err(Error::AccountIsRecused);
+        }
+
+        // Check if the voter has already voted on the poll.
+        if self.voted_by.contains((poll_id, voter)) {
+            return Err(Error::CallerAlreadyVotedOnPoll);
+        }
+
+        // Get the weight of the voter's vote.
+        let weight = weight.unwrap_or_else(|| self.voting_weight(poll_id, voter));
+
+        if weight == 0 {
+            return Err(Error::CallerHasNoVotingWeight);
+        }
+
+        // Check if the choice can receive more votes (last, so that a voter getting this error
+        // is otherwise allowed to vote and can wait for a seat).
+        if let (Some(choice_id), Some(capacity)) = (choice_id, self.choice_capacities.get(poll_id))
+        {
+            if self
+                .choice_ballots
+                .get((poll_id, choice_id))
+                .unwrap_or_default()
+                >= capacity
+            {
+                return Err(Error::ChoiceCapacityReached);
+            }
+        }
+
+        Ok(weight)
+    }
+
+    /// Records the vote of the caller along with the stake attached to it (the transferred
+    /// value or `token_amount` tokens, depending on the currency of the poll) and returns the
+    /// number of its ballot.
+    pub(crate) fn cast_vote(
+        &mut self,
+        poll_id: PollId,
+        ranking: Vec<ChoiceId>,
+        token_amount: Balance,
+    ) -> Result<u64, Error> {
+        // Count the call against the rate limit of the caller.
+        self.consume_rate_limit()?;
+
+        // Check the ranking and get the most preferred choice.
+        let choice_id = self.check_ranking(poll_id, &ranking)?;
+
+        // Get the caller and the stake attached to the vote.
+        let caller = self.env().caller();
+        let stake = self.incoming_amount(poll_id, token_amount)?;
+
+        // Check if the poll accepts stakes.
+        if stake > 0 && !self.prediction_polls.contains(poll_id) {
+            return Err(Error::PollDoesNotAcceptStakes);
+        }
+
+        // Record the vote of the caller.
+        let ballot_number = self.record_vote(poll_id, choice_id, caller)?;
+        self.record_ranking(poll_id, &ranking, caller);
+
+        // Record the stake of the caller (net of the protocol fee).
+        let stake = self.collect_payment(poll_id, stake)?;
+
+        if stake > 0 {
+            self.stakes.insert((poll_id, caller), &stake);
+
+            let choice_stake = self
+                .choice_stakes
+                .get((poll_id, choice_id))
+                .unwrap_or_default();
+            self.choice_stakes
+                .insert((poll_id, choice_id), &(choice_stake + stake));
+
+            let pot = self.prediction_pots.get(poll_id).unwrap_or_default();
+            self.prediction_pots.insert(poll_id, &(pot + stake));
+        }
+
+        Ok(ballot_number)
+    }
+}
+
+/// Returns the page of an index (of a member or a choice) in a paged bitmap (of 256 indexes
+/// each), along with the byte of the page and the mask of the bit of the index.
+pub(crate) fn bitmap_position(index: u32) -> (u32, usize, u8) {
+    (index / 256, (index % 256 / 8) as usize, 1 << (index % 8))
+}
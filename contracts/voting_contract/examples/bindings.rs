@@ -0,0 +1,10 @@
+//! Prints the description of the messages, events and error codes of the contract as JSON.
+
+fn main() {
+    let bindings = voting_contract::bindings::generate();
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&bindings).expect("bindings are valid JSON")
+    );
+}
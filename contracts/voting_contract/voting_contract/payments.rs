@@ -0,0 +1,214 @@
+//! Payment, fee and PSP22 token helpers of the voting contract.
+
+use super::*;
+
+impl VotingContract {
+    /// Adds the transferred value or `token_amount` tokens (depending on the currency of the
+    /// poll) to the fee pool of a poll.
+    pub(crate) fn add_to_fee_pool(
+        &mut self,
+        poll_id: PollId,
+        token_amount: Balance,
+    ) -> Result<(), Error> {
+        // Check if the poll exists.
+        if !self.polls.contains(poll_id) {
+            return Err(Error::PollWithIdDoesNotExist);
+        }
+
+        // Add the amount (net of the protocol fee) to the fee pool.
+        let amount = self.incoming_amount(poll_id, token_amount)?;
+        let amount = self.collect_payment(poll_id, amount)?;
+        let fee_pool = self.fee_pools.get(poll_id).unwrap_or_default();
+        self.fee_pools.insert(poll_id, &(fee_pool + amount));
+
+        Ok(())
+    }
+
+    /// Adds an amount paid by the owner of a poll that has not ended (the transferred value or
+    /// `token_amount` tokens, net of the protocol fee) to the keeper bounty of the poll.
+    pub(crate) fn add_to_keeper_bounty(
+        &mut self,
+        poll_id: PollId,
+        token_amount: Balance,
+    ) -> Result<(), Error> {
+        // Get the poll and return error if it does not exist.
+        let poll = self
+            .polls
+            .get(poll_id)
+            .ok_or(Error::PollWithIdDoesNotExist)?;
+
+        // Check if the caller is the owner of the poll.
+        if self.env().caller() != poll.owner {
+            return Err(Error::OnlyOwnerCanFundKeeperBounty);
+        }
+
+        // Check if the poll has ended.
+        poll.ensure_not_closed()?;
+
+        // Add the amount (net of the protocol fee) to the keeper bounty.
+        let amount = self.incoming_amount(poll_id, token_amount)?;
+        let amount = self.collect_payment(poll_id, amount)?;
+        let bounty = self.keeper_bounties.get(poll_id).unwrap_or_default();
+        self.keeper_bounties.insert(poll_id, &(bounty + amount));
+
+        Ok(())
+    }
+
+    /// Returns the amount paid to the contract in the currency of a poll: the transferred
+    /// value for polls paid in the native currency and `token_amount` for polls paid in a
+    /// PSP22 token.
+    pub(crate) fn incoming_amount(
+        &self,
+        poll_id: PollId,
+        token_amount: Balance,
+    ) -> Result<Balance, Error> {
+        if self.poll_tokens.contains(poll_id) {
+            if self.env().transferred_value() > 0 {
+                return Err(Error::NativeValueNotAccepted);
+            }
+
+            Ok(token_amount)
+        } else {
+            if token_amount > 0 {
+                return Err(Error::PollDoesNotUseTokens);
+            }
+
+            Ok(self.env().transferred_value())
+        }
+    }
+
+    /// Collects an amount paid to the contract in the currency of a poll (pulling PSP22 tokens
+    /// from the caller if needed), transfers the protocol fee to the fee recipient and returns
+    /// the remaining amount.
+    pub(crate) fn collect_payment(
+        &mut self,
+        poll_id: PollId,
+        amount: Balance,
+    ) -> Result<Balance, Error> {
+        // Pull the tokens from the caller.
+        if let Some(token) = self.poll_tokens.get(poll_id) {
+            if amount > 0 {
+                self.psp22_transfer_from(
+                    token,
+                    self.env().caller(),
+                    self.env().account_id(),
+                    amount,
+                )?;
+            }
+        }
+
+        let bps = Balance::from(self.fee_config.bps);
+
+        // Calculate `amount * bps / 10_000` (rounded down) without overflowing.
+        let fee = amount / 10_000 * bps + amount % 10_000 * bps / 10_000;
+
+        if fee > 0 {
+            self.pay(poll_id, self.fee_config.recipient, fee)?;
+        }
+
+        Ok(amount - fee)
+    }
+
+    /// Collects a deposit of at least `required` in the currency of a poll from the caller (the
+    /// transferred value, or exactly `required` tokens pulled from the caller for polls paid in
+    /// a PSP22 token) and returns it.
+    pub(crate) fn collect_deposit(
+        &mut self,
+        poll_id: PollId,
+        required: Balance,
+    ) -> Result<Balance, Error> {
+        let token = self.poll_tokens.get(poll_id);
+        let deposit = match token {
+            Some(_) if self.env().transferred_value() > 0 => {
+                return Err(Error::NativeValueNotAccepted)
+            }
+            Some(_) => required,
+            None => self.env().transferred_value(),
+        };
+
+        // Check if the deposit covers the required deposit.
+        if deposit < required {
+            return Err(Error::InsufficientDeposit);
+        }
+
+        // Pull the tokens of the deposit from the caller.
+        if let Some(token) = token {
+            if deposit > 0 {
+                self.psp22_transfer_from(
+                    token,
+                    self.env().caller(),
+                    self.env().account_id(),
+                    deposit,
+                )?;
+            }
+        }
+
+        Ok(deposit)
+    }
+
+    /// Transfers an amount in the currency of a poll from the contract to an account.
+    pub(crate) fn pay(
+        &mut self,
+        poll_id: PollId,
+        to: AccountId,
+        amount: Balance,
+    ) -> Result<(), Error> {
+        match self.poll_tokens.get(poll_id) {
+            Some(token) => self.psp22_transfer(token, to, amount),
+            None => self
+                .env()
+                .transfer(to, amount)
+                .map_err(|_| Error::TransferFailed),
+        }
+    }
+
+    /// Calls `PSP22::transfer` on a token contract.
+    pub(crate) fn psp22_transfer(
+        &self,
+        token: AccountId,
+        to: AccountId,
+        amount: Balance,
+    ) -> Result<(), Error> {
+        let result = build_call::<Environment>()
+            .call(token)
+            .exec_input(
+                ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer")))
+                    .push_arg(to)
+                    .push_arg(amount)
+                    .push_arg(Vec::<u8>::new()),
+            )
+            .returns::<Result<(), PSP22Error>>()
+            .try_invoke();
+
+        match result {
+            Ok(Ok(Ok(()))) => Ok(()),
+            _ => Err(Error::TokenTransferFailed),
+        }
+    }
+
+    /// Calls `PSP22::transfer_from` on a token contract.
+    pub(crate) fn psp22_transfer_from(
+        &self,
+        token: AccountId,
+        from: AccountId,
+        to: AccountId,
+        amount: Balance,
+    ) -> Result<(), Error> {
+        let result = build_call::<Environment>()
+            .call(token)
+            .exec_input(
+                ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer_from")))
+                    .push_arg(from)
+                    .push_arg(to)
+                    .push_arg(amount)
+                    .push_arg(Vec::<u8>::new()),
+            )
+            .returns::<Result<(), PSP22Error>>()
+            .try_invoke();
+
+        match result {
+            Ok(Ok(Ok(()))) => Ok(()),
+            _ => Err(Error::TokenTransferFailed),
+        }
+    }
+}
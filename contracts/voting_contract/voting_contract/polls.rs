@@ -0,0 +1,475 @@
+//! Poll lifecycle guards and helpers of the voting contract.
+
+use super::*;
+
+impl VotingContract {
+    /// Returns a poll whose choices can still be changed, i.e. the contract is not paused, the
+    /// caller is the owner of the poll (`not_owner` is returned otherwise) and the poll has
+    /// not started. Every message editing the choices of a poll must go through this guard so
+    /// that the choice set is locked once voting starts.
+    pub(crate) fn require_mutable(&self, poll_id: PollId, not_owner: Error) -> Result<Poll, Error> {
+        // Check if the contract is paused.
+        if self.paused {
+            return Err(Error::ContractIsPaused);
+        }
+
+        // Get the poll and return error if it does not exist.
+        let poll = self
+            .polls
+            .get(poll_id)
+            .ok_or(Error::PollWithIdDoesNotExist)?;
+
+        // Check if the caller is the owner of the poll.
+        if self.env().caller() != poll.owner {
+            return Err(not_owner);
+        }
+
+        // Check if the poll has started or ended.
+        poll.ensure_status(PollStatus::NotStarted)?;
+
+        Ok(poll)
+    }
+
+    /// Returns true if the caller can view the choices and results of a poll.
+    pub(crate) fn can_view(&self, poll_id: PollId) -> bool {
+        if self.get_poll_visibility(poll_id) == PollVisibility::Public {
+            return true;
+        }
+
+        let caller = self.env().caller();
+
+        self.polls.get(poll_id).map(|poll| poll.owner) == Some(caller)
+            || self.moderators.contains(caller)
+            || self.voter_weights.contains((poll_id, caller))
+    }
+
+    /// Returns true if the per-choice results of a poll are hidden from the caller, i.e. they
+    /// are hidden until the end of the poll and the caller is not its owner.
+    pub(crate) fn hides_counts(&self, poll_id: PollId, poll: &Poll) -> bool {
+        self.hidden_results.contains(poll_id)
+            && poll.status == PollStatus::Started
+            && self.env().caller() != poll.owner
+    }
+
+    /// Returns the ids of the choices of a poll, in the order in which they are presented.
+    pub(crate) fn choice_id_list(&self, poll_id: PollId) -> Vec<ChoiceId> {
+        (0..self.choice_count.get(poll_id).unwrap_or_default())
+            .filter_map(|position| self.choice_slots.get((poll_id, position)))
+            .collect()
+    }
+
+    /// Appends a choice id to the list of choices of a poll, without rewriting the list.
+    pub(crate) fn push_choice_id(&mut self, poll_id: PollId, choice_id: ChoiceId) {
+        let count = self.choice_count.get(poll_id).unwrap_or_default();
+
+        self.choice_slots.insert((poll_id, count), &choice_id);
+        self.choice_count.insert(poll_id, &count.saturating_add(1));
+    }
+
+    /// Replaces the list of choices of a poll.
+    pub(crate) fn set_choice_id_list(&mut self, poll_id: PollId, choice_id_list: &[ChoiceId]) {
+        let count = self.choice_count.get(poll_id).unwrap_or_default();
+
+        for (position, choice_id) in choice_id_list.iter().enumerate() {
+            self.choice_slots
+                .insert((poll_id, position as u32), choice_id);
+        }
+
+        // Remove the slots past the end of the new list.
+        for position in choice_id_list.len() as u32..count {
+            self.choice_slots.remove((poll_id, position));
+        }
+
+        self.choice_count
+            .insert(poll_id, &(choice_id_list.len() as u32));
+    }
+
+    /// Returns true if a choice of a poll exists according to the choice bitmap of the poll.
+    pub(crate) fn has_choice(&self, poll_id: PollId, choice_id: ChoiceId) -> bool {
+        let (page, byte, mask) = bitmap_position(choice_id);
+
+        self.choice_bitmaps
+            .get((poll_id, page))
+            .is_some_and(|bits| bits[byte] & mask != 0)
+    }
+
+    /// Sets (or clears) the bit of a choice in the choice bitmap of a poll.
+    pub(crate) fn set_choice_bit(&mut self, poll_id: PollId, choice_id: ChoiceId, present: bool) {
+        let (page, byte, mask) = bitmap_position(choice_id);
+        let mut bits = self.choice_bitmaps.get((poll_id, page)).unwrap_or_default();
+
+        if present {
+            bits[byte] |= mask;
+        } else {
+            bits[byte] &= !mask;
+        }
+
+        if bits == BitmapPage::default() {
+            self.choice_bitmaps.remove((poll_id, page));
+        } else {
+            self.choice_bitmaps.insert((poll_id, page), &bits);
+        }
+    }
+
+    /// Adds a question to the questions of a poll.
+    pub(crate) fn push_question(
+        &mut self,
+        poll_id: PollId,
+        question_id: QuestionId,
+        question: &Question,
+    ) {
+        self.questions.insert((poll_id, question_id), question);
+
+        let mut question_ids = self.question_ids.get(poll_id).unwrap_or_default();
+        question_ids.push(question_id);
+        self.question_ids.insert(poll_id, &question_ids);
+    }
+
+    /// Returns the reports of the questions of a poll, with their winners if the poll has ended
+    /// and the statistics of the numeric questions unless they are hidden.
+    pub(crate) fn question_reports(
+        &self,
+        poll_id: PollId,
+        poll: &Poll,
+        hide_counts: bool,
+    ) -> Vec<QuestionReport> {
+        self.question_ids
+            .get(poll_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|question_id| {
+                let question = self.questions.get((poll_id, question_id))?;
+
+                let winner = (poll.status == PollStatus::Ended)
+                    .then(|| {
+                        crate::tally::plurality(
+                            &self.choice_counts_of(poll_id, &question.choice_ids),
+                        )
+                    })
+                    .flatten();
+
+                let range = self.numeric_ranges.get((poll_id, question_id));
+
+                let statistics = range.filter(|_| !hide_counts).map(|range| {
+                    self.numeric_tallies
+                        .get((poll_id, question_id))
+                        .unwrap_or_default()
+                        .statistics(range)
+                });
+
+                Some(QuestionReport {
+                    id: question_id,
+                    description: question.description.into(),
+                    choice_ids: question.choice_ids,
+                    winner,
+                    range,
+                    statistics,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the vote counts of the given choices of a poll.
+    pub(crate) fn choice_counts_of(
+        &self,
+        poll_id: PollId,
+        choice_ids: &[ChoiceId],
+    ) -> Vec<(ChoiceId, u128)> {
+        choice_ids
+            .iter()
+            .map(|choice_id| {
+                (
+                    *choice_id,
+                    self.vote_counts
+                        .get((poll_id, *choice_id))
+                        .unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the report of a choice of a poll, with zero results if they are hidden.
+    pub(crate) fn choice_report(
+        &self,
+        poll_id: PollId,
+        choice_id: ChoiceId,
+        hide_counts: bool,
+    ) -> Result<ChoiceReport, Error> {
+        let choice = self
+            .choices
+            .get((poll_id, choice_id))
+            .ok_or(Error::StorageInconsistency { choice_id })?;
+
+        let mut vote_count = self
+            .vote_counts
+            .get((poll_id, choice_id))
+            .unwrap_or_default();
+
+        let mut voter_count = self
+            .voter_counts
+            .get((poll_id, choice_id))
+            .unwrap_or_default();
+
+        let mut points = self
+            .borda_points
+            .get((poll_id, choice_id))
+            .unwrap_or_default();
+
+        let mut remaining_capacity = self.choice_capacities.get(poll_id).map(|capacity| {
+            capacity.saturating_sub(
+                self.choice_ballots
+                    .get((poll_id, choice_id))
+                    .unwrap_or_default(),
+            )
+        });
+
+        if hide_counts {
+            vote_count = 0;
+            voter_count = 0;
+            points = 0;
+            remaining_capacity = None;
+        }
+
+        Ok(ChoiceReport {
+            id: choice_id,
+            description: choice.description.into(),
+            vote_count,
+            voter_count,
+            points,
+            remaining_capacity,
+        })
+    }
+
+    /// Applies a configuration to a poll, only storing the settings that differ from the
+    /// defaults of the corresponding getters.
+    pub(crate) fn apply_poll_config(&mut self, poll_id: PollId, config: &PollConfig) {
+        if config.visibility == PollVisibility::Public {
+            self.poll_visibility.remove(poll_id);
+        } else {
+            self.poll_visibility.insert(poll_id, &config.visibility);
+        }
+
+        if config.tally_strategy == TallyStrategy::Plurality {
+            self.tally_strategies.remove(poll_id);
+        } else {
+            self.tally_strategies
+                .insert(poll_id, &config.tally_strategy);
+        }
+
+        if config.hide_results_until_end {
+            self.hidden_results.insert(poll_id, &());
+        } else {
+            self.hidden_results.remove(poll_id);
+        }
+
+        if config.challenge_period == 0 {
+            self.challenge_periods.remove(poll_id);
+        } else {
+            self.challenge_periods
+                .insert(poll_id, &config.challenge_period);
+        }
+    }
+
+    /// Returns the hash of the configuration of a poll: its description, owner, schedule, tally
+    /// strategy and choices.
+    pub(crate) fn config_hash(&self, poll_id: PollId, poll: &Poll) -> [u8; 32] {
+        let choices: Vec<(ChoiceId, Description)> = self
+            .choice_id_list(poll_id)
+            .into_iter()
+            .filter_map(|choice_id| {
+                let choice = self.choices.get((poll_id, choice_id))?;
+                Some((choice_id, choice.description))
+            })
+            .collect();
+
+        self.env().hash_encoded::<ink::env::hash::Blake2x256, _>(&(
+            self.poll_descriptions.get(poll_id).unwrap_or_default(),
+            poll.owner,
+            poll.start_at,
+            poll.end_at,
+            self.get_tally_strategy(poll_id),
+            choices,
+        ))
+    }
+
+    /// Checks that a description is at most `MAX_DESCRIPTION_LENGTH` bytes long and contains
+    /// none of the terms of the denylist, and returns it as stored. Terms are the runs of
+    /// alphanumeric characters of the description.
+    pub(crate) fn check_description(&self, description: &str) -> Result<Description, Error> {
+        // Check if the description is too long.
+        let bounded = Description::new(description.into()).ok_or(Error::DescriptionTooLong)?;
+
+        // Skip hashing when the denylist is empty.
+        if self.denied_term_count == 0 {
+            return Ok(bounded);
+        }
+
+        let denied = description
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|term| !term.is_empty())
+            .any(|term| self.is_term_denied(self.hash_term(term.into())));
+
+        if denied {
+            return Err(Error::DescriptionContainsDeniedTerm);
+        }
+
+        Ok(bounded)
+    }
+
+    /// Adds a started poll to the discovery feed, keeping at most `MAX_FEED_LENGTH` polls in each
+    /// list (the oldest started polls and the polls ending the latest are dropped).
+    pub(crate) fn add_to_feed(&mut self, poll_id: PollId, end_at: Timestamp) {
+        let mut feed = self.active_feed.get().unwrap_or_default();
+
+        feed.recently_started.insert(0, poll_id);
+        feed.recently_started.truncate(MAX_FEED_LENGTH as usize);
+
+        let position = feed
+            .ending_soon
+            .partition_point(|(_, other_end_at)| *other_end_at <= end_at);
+        feed.ending_soon.insert(position, (poll_id, end_at));
+        feed.ending_soon.truncate(MAX_FEED_LENGTH as usize);
+
+        self.active_feed.set(&feed);
+    }
+
+    /// Removes a poll that is no longer running from the discovery feed.
+    pub(crate) fn remove_from_feed(&mut self, poll_id: PollId) {
+        let mut feed = self.active_feed.get().unwrap_or_default();
+
+        feed.recently_started.retain(|id| *id != poll_id);
+        feed.ending_soon.retain(|(id, _)| *id != poll_id);
+
+        self.active_feed.set(&feed);
+    }
+
+    /// Stores the winner of a poll that has been transitioned to ended and emits `PollEnded`
+    /// event.
+    pub(crate) fn close_poll(&mut self, poll_id: PollId, mut poll: Poll) {
+        // The poll is no longer active.
+        self.active_poll_count = self.active_poll_count.saturating_sub(1);
+        self.remove_from_feed(poll_id);
+
+        // Get the winner of the poll according to its tally strategy.
+        let mut winner = match self.get_tally_strategy(poll_id) {
+            TallyStrategy::Plurality | TallyStrategy::WeightedMedian => {
+                self.plurality_winner(poll_id)
+            }
+            TallyStrategy::Condorcet => self.condorcet_winner(poll_id),
+            TallyStrategy::Borda => self.borda_winner(poll_id),
+            TallyStrategy::Stv { seats } => self.stv_winner(poll_id, seats),
+        };
+
+        // Discard the winner if the poll has not reached its quorum.
+        let cast_weight = self.total_votes(poll_id);
+        let required_weight = self.required_weight(poll_id);
+
+        if cast_weight < required_weight {
+            winner = None;
+
+            self.env().emit_event(QuorumNotReached {
+                schema_version: EVENT_SCHEMA_VERSION,
+                poll_id,
+                namespace: self.topic_namespaces.get(poll_id),
+                cast_weight,
+                required_weight,
+            });
+        }
+
+        // Change the winner of the poll.
+        poll.winner = winner;
+
+        // Insert the poll into the storage.
+        self.polls.insert(poll_id, &poll);
+        self.end_blocks.insert(poll_id, &self.env().block_number());
+
+        // Open the challenge period of the result.
+        if let Some(period) = self.challenge_periods.get(poll_id) {
+            let deadline = self.env().block_timestamp().saturating_add(period);
+            self.challenge_deadlines.insert(poll_id, &deadline);
+        }
+
+        // Emit the event.
+        self.env().emit_event(PollEnded {
+            schema_version: EVENT_SCHEMA_VERSION,
+            poll_id,
+            namespace: self.topic_namespaces.get(poll_id),
+            winner,
+            total_ballots: self.total_ballots.get(poll_id).unwrap_or_default(),
+            winner_topic: winner.map_or(NO_WINNER_TOPIC, u64::from),
+            winner_vote_count: winner
+                .and_then(|winner| self.vote_counts.get((poll_id, winner)))
+                .unwrap_or_default(),
+            winner_voter_count: winner
+                .and_then(|winner| self.voter_counts.get((poll_id, winner)))
+                .unwrap_or_default(),
+        });
+        self.emit_lifecycle_event(poll_id, PollStage::Ended);
+
+        // Notify the observer of the poll.
+        self.notify_observer(poll_id, winner);
+
+        // Push the result to the results publisher.
+        self.publish_result(poll_id, winner);
+    }
+
+    /// Returns the Blake2x256 hash of the SCALE encoding of `(POLL_TOPIC_DOMAIN, poll_id)`, the
+    /// value of the topic of the `PollLifecycle` events of a poll.
+    pub(crate) fn poll_topic(&self, poll_id: PollId) -> [u8; 32] {
+        self.env()
+            .hash_encoded::<ink::env::hash::Blake2x256, _>(&(POLL_TOPIC_DOMAIN, poll_id))
+    }
+
+    /// Emits the `PollLifecycle` event of a poll reaching the given stage.
+    pub(crate) fn emit_lifecycle_event(&self, poll_id: PollId, stage: PollStage) {
+        self.env().emit_event(PollLifecycle {
+            schema_version: EVENT_SCHEMA_VERSION,
+            poll_topic: self.poll_topic(poll_id),
+            poll_id,
+            namespace: self.topic_namespaces.get(poll_id),
+            stage,
+        });
+    }
+
+    /// Returns true if the list of eligible voters of a poll has been sealed.
+    pub(crate) fn is_voter_list_sealed(&self, poll_id: PollId) -> bool {
+        self.voter_list_uploads
+            .get(poll_id)
+            .is_some_and(|upload| upload.sealed)
+    }
+
+    /// Returns the total eligible weight of a poll: the weight attested by its owner, or else the
+    /// total of the uploaded weights (for polls using them) or else the total weight of the
+    /// registered accounts (for polls requiring registration).
+    pub(crate) fn eligible_weight(&self, poll_id: PollId) -> u128 {
+        self.eligible_weights
+            .get(poll_id)
+            .or_else(|| {
+                self.weighted_polls
+                    .contains(poll_id)
+                    .then(|| self.uploaded_weights.get(poll_id).unwrap_or_default())
+            })
+            .unwrap_or_else(|| self.registered_weights.get(poll_id).unwrap_or_default())
+    }
+
+    /// Returns the weight of the votes required for a poll to reach its quorum (rounded up).
+    pub(crate) fn required_weight(&self, poll_id: PollId) -> u128 {
+        let bps = u128::from(self.quorum_weight_bps.get(poll_id).unwrap_or_default());
+        let eligible_weight = self.eligible_weight(poll_id);
+
+        // Calculate `eligible_weight * bps / 10_000` (rounded up) without overflowing.
+        eligible_weight / 10_000 * bps + (eligible_weight % 10_000 * bps).div_ceil(10_000)
+    }
+
+    /// Returns the total number of votes cast on a poll.
+    pub(crate) fn total_votes(&self, poll_id: PollId) -> u128 {
+        self.choice_id_list(poll_id)
+            .into_iter()
+            .map(|choice_id| {
+                self.vote_counts
+                    .get((poll_id, choice_id))
+                    .unwrap_or_default()
+            })
+            .fold(0u128, u128::saturating_add)
+    }
+}
@@ -0,0 +1,462 @@
+//! Builders of the reports returned by the query messages of the voting contract.
+
+use super::*;
+
+impl VotingContract {
+    /// Builds the chunk of `ExportRecord`s of a poll starting at the given cursor and the cursor of
+    /// the next chunk (see `export_state`).
+    pub(crate) fn export_chunk(
+        &self,
+        poll_id: PollId,
+        cursor: ExportCursor,
+    ) -> Result<(Vec<u8>, Option<ExportCursor>), Error> {
+        // Get the poll and return error if it does not exist.
+        let poll = self
+            .polls
+            .get(poll_id)
+            .ok_or(Error::PollWithIdDoesNotExist)?;
+
+        // Check if the caller can view the poll.
+        if !self.can_view(poll_id) {
+            return Err(Error::CallerCannotViewPoll);
+        }
+
+        let hide_counts = self.hides_counts(poll_id, &poll);
+        let choice_ids = self.choice_id_list(poll_id);
+        let voter_slots = self.public_ballots.get(poll_id).unwrap_or_default();
+
+        // The poll is the first record, followed by the choices and the ballots (the slots of
+        // retracted ballots are skipped).
+        let total = 1 + choice_ids.len() + voter_slots as usize;
+        let start = cursor as usize;
+        let end = start
+            .saturating_add(MAX_EXPORT_CHUNK_SIZE as usize)
+            .min(total);
+
+        let mut records = Vec::new();
+
+        if start == 0 {
+            records.push(ExportRecord::Poll {
+                poll,
+                description: self.poll_descriptions.get(poll_id).unwrap_or_default(),
+            });
+        }
+
+        for index in start.max(1)..end {
+            let record = if let Some(&choice_id) = choice_ids.get(index - 1) {
+                ExportRecord::Choice(self.choice_report(poll_id, choice_id, hide_counts)?)
+            } else {
+                let slot = (index - 1 - choice_ids.len()) as u32;
+                let Some(voter) = self.public_voters.get((poll_id, slot)) else {
+                    continue;
+                };
+                let choice_id = self.ballots.get((poll_id, voter)).unwrap_or_default();
+
+                ExportRecord::Ballot { voter, choice_id }
+            };
+
+            records.push(record);
+        }
+
+        let next = (end < total).then_some(end as ExportCursor);
+
+        Ok((scale::Encode::encode(&records), next))
+    }
+
+    /// Estimates the storage deposit of creating a poll and adding its choices (see
+    /// `estimate_poll_deposit`).
+    pub(crate) fn deposit_estimate(
+        &self,
+        description_len: u32,
+        num_choices: u32,
+        avg_choice_len: u32,
+    ) -> Balance {
+        let compact_len = |len: u32| -> u32 {
+            <scale::Compact<u32> as scale::CompactLen<u32>>::compact_len(&len) as u32
+        };
+        let choice_id_len = core::mem::size_of::<ChoiceId>() as u32;
+
+        // Size of a poll.
+        let poll_len = scale::Encode::encoded_size(&Poll {
+            status: PollStatus::NotStarted,
+            owner: self.env().caller(),
+            winner: None,
+            start_at: 0,
+            end_at: 0,
+        }) as u32;
+
+        // The poll, its description and its storage version.
+        let description_len = compact_len(description_len).saturating_add(description_len);
+        let mut bytes = Balance::from(poll_len) + Balance::from(description_len) + 1;
+        let mut items: Balance = 3;
+
+        // The choices, the slots and the length of the list of choice ids and the next choice
+        // id.
+        if num_choices > 0 {
+            let choices_len = num_choices
+                .saturating_mul(compact_len(avg_choice_len).saturating_add(avg_choice_len));
+            let choice_slots_len = num_choices.saturating_mul(choice_id_len);
+
+            bytes += Balance::from(choices_len)
+                + Balance::from(choice_slots_len)
+                + Balance::from(core::mem::size_of::<u32>() as u32)
+                + Balance::from(choice_id_len);
+            items += 2 * Balance::from(num_choices) + 2;
+        }
+
+        bytes
+            .saturating_mul(self.deposit_per_byte)
+            .saturating_add(items.saturating_mul(self.deposit_per_item))
+    }
+
+    /// Returns a page of the retained entries of the admin log (see `get_admin_log`).
+    pub(crate) fn admin_log_page(
+        &self,
+        offset: u64,
+        limit: u32,
+    ) -> Result<Vec<AdminLogEntry>, Error> {
+        // Check if the number of requested entries is within the limit.
+        if limit > MAX_ADMIN_LOG_PAGE_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
+
+        // Index of the oldest entry which has not been overwritten yet.
+        let oldest = self.admin_log_length.saturating_sub(ADMIN_LOG_CAPACITY);
+        let start = oldest.saturating_add(offset);
+        let end = start
+            .saturating_add(u64::from(limit))
+            .min(self.admin_log_length);
+
+        Ok((start..end)
+            .filter_map(|index| self.admin_log.get(index % ADMIN_LOG_CAPACITY))
+            .collect())
+    }
+
+    /// Builds the SCALE-encoded attestation of the result of an ended poll (see
+    /// `get_result_attestation`).
+    pub(crate) fn result_attestation(&self, poll_id: PollId) -> Result<Vec<u8>, Error> {
+        // Get the poll and return error if it does not exist.
+        let poll = self
+            .polls
+            .get(poll_id)
+            .ok_or(Error::PollWithIdDoesNotExist)?;
+
+        // Check if the caller can view the poll.
+        if !self.can_view(poll_id) {
+            return Err(Error::CallerCannotViewPoll);
+        }
+
+        // Check if the poll has ended.
+        if poll.status != PollStatus::Ended {
+            return Err(Error::PollHasNotEnded);
+        }
+
+        let attestation = ResultAttestation {
+            poll_id,
+            config_hash: self.config_hash(poll_id, &poll),
+            winner: poll.winner,
+            counts: self.choice_counts(poll_id, |choice_id| {
+                self.vote_counts.get((poll_id, choice_id))
+            }),
+            end_block: self.end_blocks.get(poll_id).unwrap_or_default(),
+            ballot_root: self.ballot_trees.get(poll_id).unwrap_or_default().root(),
+        };
+
+        Ok(scale::Encode::encode(&attestation))
+    }
+
+    /// Returns the status of the result of an ended poll (see `get_result_status`).
+    pub(crate) fn result_status(&self, poll_id: PollId) -> Result<ResultStatus, Error> {
+        // Get the poll and return error if it does not exist.
+        let poll = self
+            .polls
+            .get(poll_id)
+            .ok_or(Error::PollWithIdDoesNotExist)?;
+
+        // Check if the poll has ended.
+        if poll.status != PollStatus::Ended {
+            return Err(Error::PollHasNotEnded);
+        }
+
+        if self.challenges.contains(poll_id) {
+            return Ok(ResultStatus::Challenged);
+        }
+
+        if self.voided_polls.contains(poll_id) {
+            return Ok(ResultStatus::Voided);
+        }
+
+        match self.challenge_deadlines.get(poll_id) {
+            Some(deadline) if self.env().block_timestamp() < deadline => {
+                Ok(ResultStatus::Provisional)
+            }
+            _ => Ok(ResultStatus::Final),
+        }
+    }
+
+    /// Builds the quorum report of a poll (see `get_quorum`).
+    pub(crate) fn quorum_report(&self, poll_id: PollId) -> Result<QuorumReport, Error> {
+        // Check if the poll exists.
+        if !self.polls.contains(poll_id) {
+            return Err(Error::PollWithIdDoesNotExist);
+        }
+
+        let required_weight = self.required_weight(poll_id);
+        let cast_weight = self.total_votes(poll_id);
+
+        Ok(QuorumReport {
+            quorum_weight_bps: self.quorum_weight_bps.get(poll_id).unwrap_or_default(),
+            eligible_weight: self.eligible_weight(poll_id),
+            required_weight,
+            cast_weight,
+            reached: cast_weight >= required_weight,
+        })
+    }
+
+    /// Returns the choices of a poll visible to the caller (see `get_choices`).
+    pub(crate) fn visible_choices(&self, poll_id: PollId) -> Vec<(ChoiceId, Choice)> {
+        // Check if the caller can view the poll.
+        if !self.can_view(poll_id) {
+            return Vec::new();
+        }
+
+        // Get the list of choice ids for the poll.
+        let choice_list = self.choice_id_list(poll_id);
+
+        // Get the choices from storage (skipping the ids missing from the storage, see
+        // `repair_indexes`).
+        choice_list
+            .into_iter()
+            .filter_map(|choice_id| {
+                self.choices
+                    .get((poll_id, choice_id))
+                    .map(|choice| (choice_id, choice))
+            })
+            .collect()
+    }
+
+    /// Builds the report of a poll (see `get_report`).
+    pub(crate) fn poll_report(&self, poll_id: PollId) -> Result<PollReport, Error> {
+        let poll = self
+            .polls
+            .get(&poll_id)
+            .ok_or(Error::PollWithIdDoesNotExist)?;
+
+        // Check if the caller can view the poll.
+        if !self.can_view(poll_id) {
+            return Err(Error::CallerCannotViewPoll);
+        }
+
+        let hide_counts = self.hides_counts(poll_id, &poll);
+
+        let choices = self
+            .choice_id_list(poll_id)
+            .into_iter()
+            .map(|choice_id| self.choice_report(poll_id, choice_id, hide_counts))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let report = PollReport {
+            id: poll_id,
+            description: self
+                .poll_descriptions
+                .get(poll_id)
+                .unwrap_or_default()
+                .into(),
+            status: poll.status,
+            owner: poll.owner,
+            choices,
+            winner: poll.winner,
+            start_at: poll.start_at,
+            end_at: poll.end_at,
+            shuffle_seed: None,
+            questions: self.question_reports(poll_id, &poll, hide_counts),
+            recusal_count: self.recusal_counts.get(poll_id).unwrap_or_default(),
+        };
+
+        Ok(report)
+    }
+
+    /// Builds the report of a poll with its owner and a page of its voters resolved to their
+    /// display names (see `get_report_with_identities`).
+    pub(crate) fn identity_report(
+        &self,
+        poll_id: PollId,
+        offset: u32,
+        limit: u32,
+    ) -> Result<IdentityReport, Error> {
+        // Check if the number of requested voters is within the limit.
+        if limit > MAX_IDENTITY_PAGE_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
+
+        let report = self.get_report(poll_id)?;
+        let owner_name = self.display_name(report.owner);
+
+        let voter_slots = self.public_ballots.get(poll_id).unwrap_or_default();
+        let end = offset.saturating_add(limit).min(voter_slots);
+
+        let voters = (offset..end)
+            .filter_map(|slot| {
+                let account = self.public_voters.get((poll_id, slot))?;
+                let choice_id = self.ballots.get((poll_id, account))?;
+
+                Some(VoterIdentity {
+                    account,
+                    display_name: self.display_name(account),
+                    choice_id,
+                })
+            })
+            .collect();
+
+        Ok(IdentityReport {
+            report,
+            owner_name,
+            voters,
+            next_offset: (end < voter_slots).then_some(end),
+        })
+    }
+
+    /// Builds the report of a poll as presented to an account (see `get_report_for`).
+    pub(crate) fn report_for_account(
+        &self,
+        poll_id: PollId,
+        account: AccountId,
+    ) -> Result<PollReport, Error> {
+        let mut report = self.get_report(poll_id)?;
+
+        if self.shuffled_polls.contains(poll_id) {
+            let seed = self
+                .env()
+                .hash_encoded::<ink::env::hash::Blake2x256, _>(&(poll_id, account));
+
+            // Fisher-Yates shuffle driven by hashes of the seed.
+            for i in (1..report.choices.len()).rev() {
+                let hash = self
+                    .env()
+                    .hash_encoded::<ink::env::hash::Blake2x256, _>(&(seed, i as u32));
+                let random = u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]]);
+
+                report.choices.swap(i, random as usize % (i + 1));
+            }
+
+            report.shuffle_seed = Some(seed);
+        }
+
+        Ok(report)
+    }
+
+    /// Returns the vote counts of the choices of a poll visible to the caller (see
+    /// `get_vote_counts`).
+    pub(crate) fn visible_vote_counts(&self, poll_id: PollId) -> Vec<(ChoiceId, u128)> {
+        match self.polls.get(poll_id) {
+            Some(poll) if self.can_view(poll_id) => {
+                let hide_counts = self.hides_counts(poll_id, &poll);
+
+                self.choice_counts(poll_id, |choice_id| {
+                    (!hide_counts)
+                        .then(|| self.vote_counts.get((poll_id, choice_id)))
+                        .flatten()
+                })
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Builds the turnout report of a poll (see `get_turnout`).
+    pub(crate) fn turnout_report(&self, poll_id: PollId) -> Result<TurnoutReport, Error> {
+        // Check if the poll exists.
+        if !self.polls.contains(poll_id) {
+            return Err(Error::PollWithIdDoesNotExist);
+        }
+
+        Ok(TurnoutReport {
+            id: poll_id,
+            total_ballots: self.total_ballots.get(poll_id).unwrap_or_default(),
+            registered_voters: self.registered_counts.get(poll_id).unwrap_or_default(),
+            changed_ballots: self.changed_ballots.get(poll_id).unwrap_or_default(),
+        })
+    }
+
+    /// Builds the summary of a poll (see `get_summary`).
+    pub(crate) fn poll_summary(&self, poll_id: PollId) -> Result<PollSummary, Error> {
+        let poll = self
+            .polls
+            .get(poll_id)
+            .ok_or(Error::PollWithIdDoesNotExist)?;
+
+        let total_votes = self.total_votes(poll_id);
+
+        let short_description = self
+            .poll_descriptions
+            .get(poll_id)
+            .unwrap_or_default()
+            .chars()
+            .take(SUMMARY_DESCRIPTION_LENGTH)
+            .collect();
+
+        Ok(PollSummary {
+            id: poll_id,
+            short_description,
+            status: poll.status,
+            owner: poll.owner,
+            total_votes,
+            winner: poll.winner,
+        })
+    }
+
+    /// Builds the summaries of a batch of polls (see `get_summaries`).
+    pub(crate) fn poll_summaries(
+        &self,
+        poll_ids: Vec<PollId>,
+    ) -> Result<Vec<Option<PollSummary>>, Error> {
+        // Check if the number of requested polls is within the limit.
+        if poll_ids.len() > MAX_SUMMARY_BATCH_SIZE as usize {
+            return Err(Error::BatchTooLarge);
+        }
+
+        Ok(poll_ids
+            .into_iter()
+            .map(|poll_id| self.get_summary(poll_id).ok())
+            .collect())
+    }
+
+    /// Builds the dashboard of the polls created by an account (see `get_owner_dashboard`).
+    pub(crate) fn owner_dashboard(&self, owner: AccountId) -> OwnerDashboard {
+        let now = self.env().block_timestamp();
+
+        let polls = self
+            .owned_polls
+            .get(owner)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|poll_id| {
+                let poll = self.polls.get(poll_id)?;
+                let has_choices = self.choice_count.get(poll_id).unwrap_or_default() > 0;
+
+                let pending_actions = match poll.status {
+                    PollStatus::NotStarted if !has_choices => vec![PendingAction::AddChoices],
+                    PollStatus::NotStarted if now >= poll.start_at => {
+                        vec![PendingAction::Start]
+                    }
+                    PollStatus::Started if now >= poll.end_at => {
+                        vec![PendingAction::Finalize]
+                    }
+                    PollStatus::Ended if self.deposits.contains(poll_id) => {
+                        vec![PendingAction::ClaimDeposit]
+                    }
+                    _ => Vec::new(),
+                };
+
+                Some(OwnerPollEntry {
+                    id: poll_id,
+                    status: poll.status,
+                    total_ballots: self.total_ballots.get(poll_id).unwrap_or_default(),
+                    registered_voters: self.registered_counts.get(poll_id).unwrap_or_default(),
+                    pending_actions,
+                })
+            })
+            .collect();
+
+        OwnerDashboard { owner, polls }
+    }
+}
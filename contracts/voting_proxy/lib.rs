@@ -0,0 +1,275 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+//! Proxy which runs the logic of the voting contract behind a stable address using
+//! `delegate_call`: every call is executed by the code of the current implementation against the
+//! storage of the proxy, so that several versions of the logic can run behind stable addresses and
+//! an upgrade can be rolled back instantly by pointing back to the previous implementation.
+//!
+//! The proxy is installed on an existing instance of the voting contract using its `enable_proxy`
+//! message, which records the admin and the current code of the contract (as the implementation)
+//! under the storage keys of the proxy before switching the code of the contract to the proxy.
+
+#[ink::contract]
+mod voting_proxy {
+    use ink::{
+        env::{call::build_call, CallFlags},
+        prelude::vec,
+        prelude::vec::Vec,
+        primitives::KeyComposer,
+        storage::{traits::ManualKey, Lazy},
+    };
+
+    /// Storage key of the admin of the proxy. The voting contract writes it in `enable_proxy`.
+    const ADMIN_KEY: u32 = KeyComposer::from_str("VotingProxy::admin");
+
+    /// Storage key of the code hash of the current implementation. The voting contract writes it
+    /// in `enable_proxy`.
+    const IMPLEMENTATION_KEY: u32 = KeyComposer::from_str("VotingProxy::implementation");
+
+    /// Storage key of the code hash of the previous implementation (restored by `Rollback`).
+    const PREVIOUS_IMPLEMENTATION_KEY: u32 =
+        KeyComposer::from_str("VotingProxy::previous_implementation");
+
+    /// Root storage of the implementation, kept as is. It is decoded from (and encoded to) all
+    /// the bytes of the root storage cell, which the proxy shares with the implementation.
+    #[derive(Debug, Default, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct ImplementationRoot(Vec<u8>);
+
+    impl scale::Encode for ImplementationRoot {
+        fn size_hint(&self) -> usize {
+            self.0.len()
+        }
+
+        fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+            dest.write(&self.0);
+        }
+    }
+
+    impl scale::Decode for ImplementationRoot {
+        fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+            let length = input
+                .remaining_len()?
+                .ok_or("unknown length of the root storage")?;
+            let mut bytes = vec![0; length];
+            input.read(&mut bytes)?;
+
+            Ok(Self(bytes))
+        }
+    }
+
+    /// Defines the storage of the proxy.
+    #[ink(storage)]
+    pub struct VotingProxy {
+        /// Root storage of the implementation.
+        root: ImplementationRoot,
+        /// Admin of the proxy.
+        admin: Lazy<AccountId, ManualKey<ADMIN_KEY>>,
+        /// Code hash of the current implementation.
+        implementation: Lazy<Hash, ManualKey<IMPLEMENTATION_KEY>>,
+        /// Code hash of the previous implementation (if any).
+        previous_implementation: Lazy<Hash, ManualKey<PREVIOUS_IMPLEMENTATION_KEY>>,
+    }
+
+    /// Commands handled by the proxy itself (all the other calls are delegated).
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum ProxyCommand {
+        /// Points the proxy to a new implementation, keeping the current one as the previous one.
+        Upgrade([u8; 32]),
+        /// Points the proxy back to the previous implementation, keeping the current one as the
+        /// previous one (so that a rollback can itself be rolled back).
+        Rollback,
+        /// Changes the admin of the proxy.
+        ChangeAdmin(AccountId),
+    }
+
+    /// Errors that can occur in the proxy.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the admin of the proxy.
+        CallerIsNotAdmin,
+        /// Returned if there is no previous implementation to roll back to.
+        NoPreviousImplementation,
+    }
+
+    #[ink(event)]
+    /// Event emitted when the proxy is pointed to another implementation.
+    pub struct ImplementationChanged {
+        #[ink(topic)]
+        /// Code hash of the new implementation.
+        implementation: [u8; 32],
+        /// Code hash of the previous implementation (if any).
+        previous_implementation: Option<[u8; 32]>,
+        /// Admin that changed the implementation.
+        admin: AccountId,
+    }
+
+    impl VotingProxy {
+        /// Instantiates a proxy to the given implementation, with the caller as admin. The root
+        /// storage of the implementation is not initialized, so an existing instance of the
+        /// voting contract is normally switched to the proxy using `enable_proxy` instead.
+        #[ink(constructor)]
+        pub fn new(implementation: [u8; 32]) -> Self {
+            let mut proxy = Self {
+                root: ImplementationRoot::default(),
+                admin: Lazy::new(),
+                implementation: Lazy::new(),
+                previous_implementation: Lazy::new(),
+            };
+
+            proxy.admin.set(&Self::env().caller());
+            proxy.implementation.set(&implementation.into());
+
+            proxy
+        }
+
+        /// Executes a command of the proxy (see `ProxyCommand`). Only the admin can execute
+        /// commands.
+        #[ink(message, selector = @)]
+        pub fn execute(&mut self, command: ProxyCommand) -> Result<(), Error> {
+            // Check if the caller is the admin.
+            if Some(self.env().caller()) != self.admin.get() {
+                return Err(Error::CallerIsNotAdmin);
+            }
+
+            match command {
+                ProxyCommand::Upgrade(implementation) => {
+                    self.point_to(implementation.into(), self.implementation.get());
+                }
+                ProxyCommand::Rollback => {
+                    let previous_implementation = self
+                        .previous_implementation
+                        .get()
+                        .ok_or(Error::NoPreviousImplementation)?;
+
+                    self.point_to(previous_implementation, self.implementation.get());
+                }
+                ProxyCommand::ChangeAdmin(admin) => self.admin.set(&admin),
+            }
+
+            Ok(())
+        }
+
+        /// Delegates the call to the current implementation, which returns directly to the
+        /// caller.
+        #[ink(message, payable, selector = _)]
+        pub fn forward(&self) {
+            let implementation = self
+                .implementation
+                .get()
+                .unwrap_or_else(|| panic!("proxy has no implementation"));
+
+            build_call::<Environment>()
+                .delegate(implementation)
+                .call_flags(CallFlags::CLONE_INPUT | CallFlags::TAIL_CALL)
+                .invoke();
+
+            unreachable!("the implementation returns directly to the caller (tail call)");
+        }
+
+        /// Points the proxy to an implementation and records the previous one.
+        fn point_to(&mut self, implementation: Hash, previous_implementation: Option<Hash>) {
+            self.implementation.set(&implementation);
+
+            if let Some(previous_implementation) = previous_implementation {
+                self.previous_implementation.set(&previous_implementation);
+            }
+
+            // Emit the event.
+            self.env().emit_event(ImplementationChanged {
+                implementation: implementation.into(),
+                previous_implementation: previous_implementation.map(Into::into),
+                admin: self.env().caller(),
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+
+        /// Decodes the `ImplementationChanged` events emitted so far.
+        fn implementation_changes() -> Vec<ImplementationChanged> {
+            ink::env::test::recorded_events()
+                .map(|event| {
+                    <ImplementationChanged as scale::Decode>::decode(&mut &event.data[..])
+                        .expect("invalid event")
+                })
+                .collect()
+        }
+
+        #[ink::test]
+        /// Tests that the admin can upgrade the proxy and roll the upgrade back.
+        fn test_upgrade_and_rollback() {
+            let mut proxy = VotingProxy::new([1; 32]);
+
+            assert_eq!(
+                proxy.execute(ProxyCommand::Rollback),
+                Err(Error::NoPreviousImplementation)
+            );
+
+            assert_eq!(proxy.execute(ProxyCommand::Upgrade([2; 32])), Ok(()));
+            assert_eq!(proxy.implementation.get(), Some(Hash::from([2; 32])));
+
+            assert_eq!(proxy.execute(ProxyCommand::Rollback), Ok(()));
+            assert_eq!(proxy.implementation.get(), Some(Hash::from([1; 32])));
+            assert_eq!(
+                proxy.previous_implementation.get(),
+                Some(Hash::from([2; 32]))
+            );
+
+            let changes = implementation_changes();
+            assert_eq!(changes.len(), 2);
+            assert_eq!(changes[0].implementation, [2; 32]);
+            assert_eq!(changes[0].previous_implementation, Some([1; 32]));
+            assert_eq!(changes[1].implementation, [1; 32]);
+            assert_eq!(changes[1].previous_implementation, Some([2; 32]));
+        }
+
+        #[ink::test]
+        /// Tests that only the admin can execute commands and that the admin can be changed.
+        fn test_only_admin() {
+            let default_accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let mut proxy = VotingProxy::new([1; 32]);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(
+                proxy.execute(ProxyCommand::Upgrade([2; 32])),
+                Err(Error::CallerIsNotAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.alice);
+            assert_eq!(
+                proxy.execute(ProxyCommand::ChangeAdmin(default_accounts.bob)),
+                Ok(())
+            );
+            assert_eq!(
+                proxy.execute(ProxyCommand::Upgrade([2; 32])),
+                Err(Error::CallerIsNotAdmin)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(default_accounts.bob);
+            assert_eq!(proxy.execute(ProxyCommand::Upgrade([2; 32])), Ok(()));
+        }
+
+        #[ink::test]
+        /// Tests that the root storage of the implementation is decoded from all the bytes of the
+        /// root storage cell and encoded back unchanged.
+        fn test_implementation_root() {
+            let bytes = vec![1, 2, 3, 4, 5];
+            let root =
+                <ImplementationRoot as scale::Decode>::decode(&mut &bytes[..]).expect("decode");
+
+            assert_eq!(root, ImplementationRoot(bytes.clone()));
+            assert_eq!(scale::Encode::encode(&root), bytes);
+        }
+    }
+}